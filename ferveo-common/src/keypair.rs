@@ -6,10 +6,6 @@ use ark_std::{
     rand::{prelude::StdRng, RngCore, SeedableRng},
     UniformRand,
 };
-use generic_array::{
-    typenum::{Unsigned, U96},
-    GenericArray,
-};
 use serde::*;
 use serde_with::serde_as;
 
@@ -40,25 +36,23 @@ pub struct PublicKey<E: Pairing> {
 }
 
 impl<E: Pairing> PublicKey<E> {
-    pub fn to_bytes(&self) -> Result<GenericArray<u8, U96>> {
-        let as_bytes = to_bytes(&self.encryption_key)?;
-        Ok(GenericArray::<u8, U96>::from_slice(&as_bytes).to_owned())
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        to_bytes(&self.encryption_key)
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey<E>> {
-        let bytes =
-            GenericArray::<u8, U96>::from_exact_iter(bytes.iter().cloned())
-                .ok_or_else(|| {
-                    Error::InvalidByteLength(
-                        Self::serialized_size(),
-                        bytes.len(),
-                    )
-                })?;
-        from_bytes(&bytes).map(|encryption_key| PublicKey { encryption_key })
+        if bytes.len() != Self::serialized_size() {
+            return Err(Error::InvalidByteLength(
+                Self::serialized_size(),
+                bytes.len(),
+            ));
+        }
+        from_bytes(bytes).map(|encryption_key| PublicKey { encryption_key })
     }
 
+    /// The size of a compressed G2 point of the curve `E`.
     pub fn serialized_size() -> usize {
-        U96::to_usize()
+        E::G2Affine::generator().compressed_size()
     }
 }
 
@@ -164,6 +158,20 @@ mod tests {
         assert!(keypair.is_err());
     }
 
+    #[test]
+    fn test_public_key_serialization() {
+        let public_key =
+            Keypair::<E>::new(&mut rand::thread_rng()).public_key();
+        let bytes = public_key.to_bytes().unwrap();
+        assert_eq!(bytes.len(), 96);
+        assert_eq!(PublicKey::<E>::serialized_size(), 96);
+        assert_eq!(PublicKey::<E>::from_bytes(&bytes).unwrap(), public_key);
+        assert!(matches!(
+            PublicKey::<E>::from_bytes(&bytes[1..]),
+            Err(Error::InvalidByteLength(96, 95))
+        ));
+    }
+
     #[test]
     fn test_keypair_sorting() {
         let mut keypairs = vec![];
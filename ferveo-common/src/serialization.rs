@@ -2,9 +2,11 @@
 //! [arkworks](http://arkworks.rs/) types that implement [CanonicalSerialize] and [CanonicalDeserialize].
 //! Adapted from [o1-labs/proof-systems](https://raw.githubusercontent.com/o1-labs/proof-systems/31c76ceae3122f0ce09cded8260960ed5cbbe3d8/utils/src/serialization.rs).
 
+use ark_ec::AffineRepr;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bincode::Options;
 use serde::{self, Deserialize, Serialize};
-use serde_with::Bytes;
+use serde_with::{Bytes, DeserializeAs, SerializeAs};
 
 //
 // Serialization with serde
@@ -88,6 +90,72 @@ where
     }
 }
 
+/// Like [SerdeAs], but for a point dealt by another party, which must not be
+/// the identity
+pub struct NonIdentity;
+
+impl<P: AffineRepr> SerializeAs<P> for NonIdentity {
+    fn serialize_as<S>(val: &P, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SerdeAs::serialize_as(val, serializer)
+    }
+}
+
+impl<'de, P: AffineRepr> DeserializeAs<'de, P> for NonIdentity {
+    fn deserialize_as<D>(deserializer: D) -> Result<P, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let point: P = SerdeAs::deserialize_as(deserializer)?;
+        if point.is_zero() {
+            return Err(serde::de::Error::custom("unexpected identity point"));
+        }
+        Ok(point)
+    }
+}
+
+/// Like [NonIdentity], but for a non-empty vector of points
+pub struct NonIdentityVec;
+
+impl<P: AffineRepr> SerializeAs<Vec<P>> for NonIdentityVec {
+    fn serialize_as<S>(val: &Vec<P>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SerdeAs::serialize_as(val, serializer)
+    }
+}
+
+impl<'de, P: AffineRepr> DeserializeAs<'de, Vec<P>> for NonIdentityVec {
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<P>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let points: Vec<P> = SerdeAs::deserialize_as(deserializer)?;
+        if points.is_empty() {
+            return Err(serde::de::Error::custom("unexpected empty points"));
+        }
+        if points.iter().any(|point| point.is_zero()) {
+            return Err(serde::de::Error::custom("unexpected identity point"));
+        }
+        Ok(points)
+    }
+}
+
+/// Upper bound on the size of any bincode encoding. Decoding reads from a slice,
+/// so no length prefix can make it allocate more than the encoding itself.
+pub const MAX_SERIALIZED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The options of `bincode::serialize`, with [MAX_SERIALIZED_SIZE] as a limit
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(MAX_SERIALIZED_SIZE)
+}
+
 // TODO: Trait aliases are experimental
 // trait ByteSerializable = ToBytes + FromBytes;
 
@@ -101,18 +169,24 @@ pub trait FromBytes: Sized {
 
 impl<T: Serialize> ToBytes for T {
     fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
-        bincode::serialize(self)
+        bincode_options().serialize(self)
     }
 }
 
 impl<T: for<'de> Deserialize<'de>> FromBytes for T {
     fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
-        bincode::deserialize(bytes)
+        if bytes.len() as u64 > MAX_SERIALIZED_SIZE {
+            return Err(Box::new(bincode::ErrorKind::SizeLimit));
+        }
+        bincode_options().deserialize(bytes)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use ark_bls12_381::G1Affine;
+    use serde_with::serde_as;
+
     use super::*;
 
     #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -128,4 +202,57 @@ mod test {
         let test2 = Test::from_bytes(&bytes).unwrap();
         assert_eq!(test, test2);
     }
+
+    #[test]
+    fn test_serde_rejects_oversized_input() {
+        let bytes = vec![0u8; MAX_SERIALIZED_SIZE as usize + 1];
+        let err = Vec::<u8>::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(*err, bincode::ErrorKind::SizeLimit));
+
+        // A length prefix beyond the input fails without allocating for it
+        let mut bytes = u64::MAX.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0u8; 16]);
+        assert!(String::from_bytes(&bytes).is_err());
+    }
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Points {
+        #[serde_as(as = "NonIdentity")]
+        point: G1Affine,
+        #[serde_as(as = "NonIdentityVec")]
+        points: Vec<G1Affine>,
+    }
+
+    #[test]
+    fn test_serde_rejects_identity_points() {
+        let g = G1Affine::generator();
+        let good = Points {
+            point: g,
+            points: vec![g, g],
+        };
+        let bytes = good.to_bytes().unwrap();
+        assert_eq!(Points::from_bytes(&bytes).unwrap(), good);
+
+        let bad_point = Points {
+            point: G1Affine::zero(),
+            ..good
+        };
+        let bytes = bad_point.to_bytes().unwrap();
+        assert!(Points::from_bytes(&bytes).is_err());
+
+        let bad_points = Points {
+            point: g,
+            points: vec![g, G1Affine::zero()],
+        };
+        let bytes = bad_points.to_bytes().unwrap();
+        assert!(Points::from_bytes(&bytes).is_err());
+
+        let no_points = Points {
+            point: g,
+            points: vec![],
+        };
+        let bytes = no_points.to_bytes().unwrap();
+        assert!(Points::from_bytes(&bytes).is_err());
+    }
 }
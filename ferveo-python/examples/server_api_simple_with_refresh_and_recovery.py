@@ -0,0 +1,117 @@
+from ferveo import (
+    encrypt,
+    combine_decryption_shares_simple,
+    decrypt_with_shared_secret,
+    Keypair,
+    Validator,
+    ValidatorMessage,
+    Dkg,
+    AggregatedTranscript,
+)
+
+
+def gen_eth_addr(i: int) -> str:
+    return f"0x{i:040x}"  # TODO: Randomize - #207
+
+
+tau = 1
+security_threshold = 3
+shares_num = 4
+validators_num = shares_num
+validator_keypairs = [Keypair.random() for _ in range(0, validators_num)]
+validators = [
+    Validator(gen_eth_addr(i), keypair.public_key(), i)
+    for i, keypair in enumerate(validator_keypairs)
+]
+
+
+def make_dkg(me: Validator) -> Dkg:
+    return Dkg(
+        tau=tau,
+        shares_num=shares_num,
+        security_threshold=security_threshold,
+        validators=validators,
+        me=me,
+    )
+
+
+# Each validator holds their own DKG instance and generates a transcript every
+# validator, including themselves
+dkgs = [make_dkg(validator) for validator in validators]
+messages = [
+    ValidatorMessage(validator, dkg.generate_transcript())
+    for validator, dkg in zip(validators, dkgs)
+]
+aggregate = AggregatedTranscript(messages)
+assert aggregate.verify(validators_num, messages)
+
+# The client creates a ciphertext before the refresh
+msg = "abc".encode()
+aad = "my-aad".encode()
+ciphertext = encrypt(msg, aad, aggregate.public_key)
+
+# Every validator deals a refresh transcript. Refresh transcripts can be
+# serialized for transport.
+validator_keys = {
+    validator.share_index: validator.public_key for validator in validators
+}
+refresh_transcripts = {
+    validator.share_index: dkg.generate_refresh_transcript()
+    for validator, dkg in zip(validators, dkgs)
+}
+refreshed_aggregate = aggregate.refresh(refresh_transcripts, validator_keys)
+assert refreshed_aggregate.public_key == aggregate.public_key
+
+# The last validator lost its key share, and recovers it under a new key with
+# the help of `security_threshold` other validators
+recovery_index = shares_num - 1
+helper_indices = list(range(security_threshold))
+helper_keys = {i: validator_keys[i] for i in helper_indices}
+recovery_transcripts = {
+    i: dkgs[i].generate_recovery_transcript(helper_indices, recovery_index)
+    for i in helper_indices
+}
+
+# The recovering validator sends a handover transcript to each helper, who
+# answers with a recovery share
+recovering_keypair = Keypair.random()
+recovery_shares = []
+for i in helper_indices:
+    handover_transcript = dkgs[0].generate_recovery_handover_transcript(
+        refreshed_aggregate, recovery_transcripts, i, recovering_keypair
+    )
+    recovery_shares.append(
+        refreshed_aggregate.create_recovery_share(
+            recovery_transcripts,
+            helper_keys,
+            recovery_index,
+            handover_transcript,
+            validator_keypairs[i],
+        )
+    )
+
+# Anyone can combine the recovery shares
+recovered_aggregate = refreshed_aggregate.recover_share(
+    recovery_transcripts, helper_keys, recovery_index, recovery_shares
+)
+
+validator_keypairs[recovery_index] = recovering_keypair
+validators[recovery_index] = Validator(
+    gen_eth_addr(recovery_index),
+    recovering_keypair.public_key(),
+    recovery_index,
+)
+
+# The recovering validator decrypts together with the others
+decryption_shares = []
+for validator, validator_keypair in list(zip(validators, validator_keypairs))[1:]:
+    decryption_share = recovered_aggregate.create_decryption_share_simple(
+        make_dkg(validator), ciphertext.header, aad, validator_keypair
+    )
+    decryption_shares.append(decryption_share)
+
+shared_secret = combine_decryption_shares_simple(decryption_shares)
+plaintext = decrypt_with_shared_secret(ciphertext, aad, shared_secret)
+assert bytes(plaintext) == msg
+
+print("Success!")
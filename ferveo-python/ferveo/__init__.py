@@ -2,6 +2,8 @@ from ._ferveo import (
     encrypt,
     combine_decryption_shares_simple,
     combine_decryption_shares_precomputed,
+    combine_blinded_decryption_shares_simple,
+    combine_blinded_decryption_shares_precomputed,
     decrypt_with_shared_secret,
     Keypair,
     FerveoPublicKey,
@@ -10,10 +12,21 @@ from ._ferveo import (
     Dkg,
     Ciphertext,
     CiphertextHeader,
+    VerifiedCiphertextHeader,
     DecryptionShareSimple,
     DecryptionSharePrecomputed,
+    DecryptionShareProof,
+    BlindedDecryptionShareSimple,
+    BlindedDecryptionSharePrecomputed,
+    RequesterKeypair,
+    RequesterPublicKey,
     AggregatedTranscript,
     HandoverTranscript,
+    KeyRotation,
+    HandoverResult,
+    RefreshTranscript,
+    RecoveryTranscript,
+    RecoveryShare,
     DkgPublicKey,
     SharedSecret,
     ValidatorMessage,
@@ -39,4 +52,20 @@ from ._ferveo import (
     UnknownValidator,
     TooManyTranscripts,
     DuplicateTranscript,
+    InsufficientRefreshTargets,
+    StaleShare,
+    InsufficientRecoveryHelpers,
+    InvalidRecoveryShare,
+    InsufficientResharingDealers,
+    InvalidResharingTranscript,
+    InvalidHandoverResult,
+    DuplicatedValidatorPublicKey,
+    InvalidKeyRotation,
+    ShareGenerationMismatch,
+    ShareGenerationOverflow,
+    EmptyTranscript,
+    InvalidTranscriptSize,
+    IdentityPointInTranscript,
+    UnsupportedTranscriptFormat,
+    InvalidEvaluationDomainSize,
 )
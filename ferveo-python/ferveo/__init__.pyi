@@ -1,4 +1,4 @@
-from typing import Sequence, final
+from typing import Mapping, Sequence, final
 
 @final
 class Keypair:
@@ -81,6 +81,20 @@ class Dkg:
         handover_slot_index: int,
         incoming_validator_keypair: Keypair,
     ) -> HandoverTranscript: ...
+    def generate_refresh_transcript(self) -> RefreshTranscript: ...
+    def generate_refresh_transcript_for(
+        self, share_indices: Sequence[int]
+    ) -> RefreshTranscript: ...
+    def generate_recovery_transcript(
+        self, helper_indices: Sequence[int], recovery_index: int
+    ) -> RecoveryTranscript: ...
+    def generate_recovery_handover_transcript(
+        self,
+        aggregate: AggregatedTranscript,
+        recovery_transcripts: Mapping[int, RecoveryTranscript],
+        helper_index: int,
+        recovering_validator_keypair: Keypair,
+    ) -> HandoverTranscript: ...
 
 @final
 class Ciphertext:
@@ -90,12 +104,18 @@ class Ciphertext:
     @staticmethod
     def from_bytes(data: bytes) -> Ciphertext: ...
     def __bytes__(self) -> bytes: ...
+    def verify(self, aad: bytes) -> VerifiedCiphertextHeader: ...
 
 @final
 class CiphertextHeader:
     @staticmethod
     def from_bytes(data: bytes) -> CiphertextHeader: ...
     def __bytes__(self) -> bytes: ...
+    def verify(self, aad: bytes) -> VerifiedCiphertextHeader: ...
+
+@final
+class VerifiedCiphertextHeader:
+    header: CiphertextHeader
 
 @final
 class DecryptionShareSimple:
@@ -109,12 +129,75 @@ class DecryptionSharePrecomputed:
     def from_bytes(data: bytes) -> DecryptionSharePrecomputed: ...
     def __bytes__(self) -> bytes: ...
 
+@final
+class DecryptionShareProof:
+    @staticmethod
+    def from_bytes(data: bytes) -> DecryptionShareProof: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class BlindedDecryptionShareSimple:
+    @staticmethod
+    def from_bytes(data: bytes) -> BlindedDecryptionShareSimple: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class BlindedDecryptionSharePrecomputed:
+    @staticmethod
+    def from_bytes(data: bytes) -> BlindedDecryptionSharePrecomputed: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class RequesterKeypair:
+    @staticmethod
+    def random() -> RequesterKeypair: ...
+    @staticmethod
+    def from_bytes(data: bytes) -> RequesterKeypair: ...
+    def __bytes__(self) -> bytes: ...
+    def public_key(self) -> RequesterPublicKey: ...
+
+@final
+class RequesterPublicKey:
+    @staticmethod
+    def from_bytes(data: bytes) -> RequesterPublicKey: ...
+    def __bytes__(self) -> bytes: ...
+
 @final
 class HandoverTranscript:
     @staticmethod
     def from_bytes(data: bytes) -> HandoverTranscript: ...
     def __bytes__(self) -> bytes: ...
 
+@final
+class HandoverResult:
+    @staticmethod
+    def from_bytes(data: bytes) -> HandoverResult: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class RefreshTranscript:
+    @staticmethod
+    def from_bytes(data: bytes) -> RefreshTranscript: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class RecoveryTranscript:
+    @staticmethod
+    def from_bytes(data: bytes) -> RecoveryTranscript: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class RecoveryShare:
+    @staticmethod
+    def from_bytes(data: bytes) -> RecoveryShare: ...
+    def __bytes__(self) -> bytes: ...
+
+@final
+class KeyRotation:
+    @staticmethod
+    def from_bytes(data: bytes) -> KeyRotation: ...
+    def __bytes__(self) -> bytes: ...
+
 @final
 class AggregatedTranscript:
     public_key: DkgPublicKey
@@ -137,11 +220,123 @@ class AggregatedTranscript:
         validator_keypair: Keypair,
         selected_validators: Sequence[Validator],
     ) -> DecryptionSharePrecomputed: ...
+    def create_decryption_share_simple_verified(
+        self,
+        dkg: Dkg,
+        ciphertext_header: VerifiedCiphertextHeader,
+        validator_keypair: Keypair,
+    ) -> DecryptionShareSimple: ...
+    def create_decryption_share_precomputed_verified(
+        self,
+        dkg: Dkg,
+        ciphertext_header: VerifiedCiphertextHeader,
+        validator_keypair: Keypair,
+        selected_validators: Sequence[Validator],
+    ) -> DecryptionSharePrecomputed: ...
+    def create_blinded_decryption_share_simple(
+        self,
+        dkg: Dkg,
+        ciphertext_header: CiphertextHeader,
+        aad: bytes,
+        validator_keypair: Keypair,
+        requester_public_key: RequesterPublicKey,
+    ) -> BlindedDecryptionShareSimple: ...
+    def create_blinded_decryption_share_precomputed(
+        self,
+        dkg: Dkg,
+        ciphertext_header: CiphertextHeader,
+        aad: bytes,
+        validator_keypair: Keypair,
+        selected_validators: Sequence[Validator],
+        requester_public_key: RequesterPublicKey,
+    ) -> BlindedDecryptionSharePrecomputed: ...
+    def verify_blinded_decryption_share_simple(
+        self,
+        decryption_share: BlindedDecryptionShareSimple,
+        ciphertext_header: CiphertextHeader,
+        requester_public_key: RequesterPublicKey,
+    ) -> bool: ...
+    def verify_blinded_decryption_share_precomputed(
+        self,
+        dkg: Dkg,
+        decryption_share: BlindedDecryptionSharePrecomputed,
+        ciphertext_header: CiphertextHeader,
+        selected_validators: Sequence[Validator],
+        requester_public_key: RequesterPublicKey,
+    ) -> bool: ...
+    def create_decryption_share_proof_simple(
+        self,
+        dkg: Dkg,
+        decryption_share: DecryptionShareSimple,
+        ciphertext_header: CiphertextHeader,
+        validator_keypair: Keypair,
+    ) -> DecryptionShareProof: ...
+    def create_decryption_share_proof_precomputed(
+        self,
+        dkg: Dkg,
+        decryption_share: DecryptionSharePrecomputed,
+        ciphertext_header: CiphertextHeader,
+        validator_keypair: Keypair,
+        selected_validators: Sequence[Validator],
+    ) -> DecryptionShareProof: ...
+    def verify_decryption_share_simple(
+        self,
+        decryption_share: DecryptionShareSimple,
+        proof: DecryptionShareProof,
+        ciphertext_header: CiphertextHeader,
+    ) -> bool: ...
+    def verify_decryption_share_precomputed(
+        self,
+        dkg: Dkg,
+        decryption_share: DecryptionSharePrecomputed,
+        proof: DecryptionShareProof,
+        ciphertext_header: CiphertextHeader,
+        selected_validators: Sequence[Validator],
+    ) -> bool: ...
     def finalize_handover(
         self,
         handover_transcript: HandoverTranscript,
         validator_keypair: Keypair,
     ) -> AggregatedTranscript: ...
+    def create_handover_result(
+        self,
+        handover_transcript: HandoverTranscript,
+        validator_keypair: Keypair,
+    ) -> HandoverResult: ...
+    def apply_handover_result(
+        self, handover_result: HandoverResult
+    ) -> AggregatedTranscript: ...
+    def is_share_current(self, share_index: int) -> bool: ...
+    def refresh(
+        self,
+        refresh_transcripts: Mapping[int, RefreshTranscript],
+        validator_keys: Mapping[int, FerveoPublicKey],
+    ) -> AggregatedTranscript: ...
+    def create_recovery_share(
+        self,
+        recovery_transcripts: Mapping[int, RecoveryTranscript],
+        helper_keys: Mapping[int, FerveoPublicKey],
+        recovery_index: int,
+        handover_transcript: HandoverTranscript,
+        validator_keypair: Keypair,
+    ) -> RecoveryShare: ...
+    def recover_share(
+        self,
+        recovery_transcripts: Mapping[int, RecoveryTranscript],
+        helper_keys: Mapping[int, FerveoPublicKey],
+        recovery_index: int,
+        recovery_shares: Sequence[RecoveryShare],
+    ) -> AggregatedTranscript: ...
+    def create_key_rotation(
+        self,
+        share_index: int,
+        validator_keypair: Keypair,
+        new_validator_keypair: Keypair,
+    ) -> KeyRotation: ...
+    def verify_key_rotation(self, key_rotation: KeyRotation) -> bool: ...
+    def apply_key_rotation(
+        self, key_rotation: KeyRotation
+    ) -> AggregatedTranscript: ...
     @staticmethod
     def from_bytes(data: bytes) -> AggregatedTranscript: ...
     def __bytes__(self) -> bytes: ...
@@ -167,6 +362,14 @@ def combine_decryption_shares_simple(
 def combine_decryption_shares_precomputed(
     decryption_shares: Sequence[DecryptionSharePrecomputed],
 ) -> SharedSecret: ...
+def combine_blinded_decryption_shares_simple(
+    decryption_shares: Sequence[BlindedDecryptionShareSimple],
+    requester_keypair: RequesterKeypair,
+) -> SharedSecret: ...
+def combine_blinded_decryption_shares_precomputed(
+    decryption_shares: Sequence[BlindedDecryptionSharePrecomputed],
+    requester_keypair: RequesterKeypair,
+) -> SharedSecret: ...
 def decrypt_with_shared_secret(
     ciphertext: Ciphertext,
     aad: bytes,
@@ -226,3 +429,51 @@ class TooManyTranscripts(Exception):
 
 class DuplicateTranscript(Exception):
     pass
+
+class InsufficientRefreshTargets(Exception):
+    pass
+
+class StaleShare(Exception):
+    pass
+
+class InsufficientRecoveryHelpers(Exception):
+    pass
+
+class InvalidRecoveryShare(Exception):
+    pass
+
+class InsufficientResharingDealers(Exception):
+    pass
+
+class InvalidResharingTranscript(Exception):
+    pass
+
+class InvalidHandoverResult(Exception):
+    pass
+
+class DuplicatedValidatorPublicKey(Exception):
+    pass
+
+class InvalidKeyRotation(Exception):
+    pass
+
+class ShareGenerationMismatch(Exception):
+    pass
+
+class ShareGenerationOverflow(Exception):
+    pass
+
+class EmptyTranscript(Exception):
+    pass
+
+class InvalidTranscriptSize(Exception):
+    pass
+
+class IdentityPointInTranscript(Exception):
+    pass
+
+class UnsupportedTranscriptFormat(Exception):
+    pass
+
+class InvalidEvaluationDomainSize(Exception):
+    pass
@@ -4,6 +4,8 @@ from ferveo import (
     encrypt,
     combine_decryption_shares_simple,
     combine_decryption_shares_precomputed,
+    combine_blinded_decryption_shares_simple,
+    combine_blinded_decryption_shares_precomputed,
     decrypt_with_shared_secret,
     AggregatedTranscript,
     Keypair,
@@ -11,8 +13,10 @@ from ferveo import (
     ValidatorMessage,
     Dkg,
     DkgPublicKey,
+    DecryptionShareProof,
     ThresholdEncryptionError,
     FerveoVariant,
+    RequesterKeypair,
 )
 
 
@@ -193,6 +197,121 @@ def test_precomputed_tdec_doesnt_have_enough_messages():
         )
 
 
+@pytest.mark.parametrize("variant", [FerveoVariant.Simple, FerveoVariant.Precomputed])
+def test_blinded_decryption_shares(variant):
+    tau = 1
+    shares_num = 4
+    threshold = 3
+    validator_keypairs = [Keypair.random() for _ in range(0, shares_num)]
+    validators = [
+        Validator(gen_eth_addr(i), keypair.public_key(), i)
+        for i, keypair in enumerate(validator_keypairs)
+    ]
+    messages = [
+        ValidatorMessage(
+            sender,
+            Dkg(tau, shares_num, threshold, validators, sender).generate_transcript(),
+        )
+        for sender in validators
+    ]
+    aggregate = AggregatedTranscript(messages)
+
+    msg = "abc".encode()
+    aad = "my-aad".encode()
+    ciphertext = encrypt(msg, aad, aggregate.public_key)
+
+    # The requester sends an ephemeral public key along with the decryption request
+    requester_keypair = RequesterKeypair.random()
+    requester_public_key = requester_keypair.public_key()
+
+    if variant == FerveoVariant.Precomputed:
+        selected_validators = validators
+    else:
+        selected_validators = validators[:threshold]
+
+    decryption_shares = []
+    for validator, validator_keypair in zip(selected_validators, validator_keypairs):
+        dkg = Dkg(tau, shares_num, threshold, validators, validator)
+        if variant == FerveoVariant.Simple:
+            decryption_share = aggregate.create_blinded_decryption_share_simple(
+                dkg, ciphertext.header, aad, validator_keypair, requester_public_key
+            )
+            assert aggregate.verify_blinded_decryption_share_simple(
+                decryption_share, ciphertext.header, requester_public_key
+            )
+        else:
+            decryption_share = aggregate.create_blinded_decryption_share_precomputed(
+                dkg,
+                ciphertext.header,
+                aad,
+                validator_keypair,
+                selected_validators,
+                requester_public_key,
+            )
+            assert aggregate.verify_blinded_decryption_share_precomputed(
+                dkg,
+                decryption_share,
+                ciphertext.header,
+                selected_validators,
+                requester_public_key,
+            )
+        decryption_shares.append(decryption_share)
+
+    if variant == FerveoVariant.Simple:
+        shared_secret = combine_blinded_decryption_shares_simple(
+            decryption_shares, requester_keypair
+        )
+    else:
+        shared_secret = combine_blinded_decryption_shares_precomputed(
+            decryption_shares, requester_keypair
+        )
+    plaintext = decrypt_with_shared_secret(ciphertext, aad, shared_secret)
+    assert bytes(plaintext) == msg
+
+
+def test_decryption_share_proofs():
+    tau = 1
+    shares_num = 4
+    threshold = 3
+    validator_keypairs = [Keypair.random() for _ in range(0, shares_num)]
+    validators = [
+        Validator(gen_eth_addr(i), keypair.public_key(), i)
+        for i, keypair in enumerate(validator_keypairs)
+    ]
+    messages = [
+        ValidatorMessage(
+            sender,
+            Dkg(tau, shares_num, threshold, validators, sender).generate_transcript(),
+        )
+        for sender in validators
+    ]
+    aggregate = AggregatedTranscript(messages)
+
+    msg = "abc".encode()
+    aad = "my-aad".encode()
+    ciphertext = encrypt(msg, aad, aggregate.public_key)
+
+    decryption_shares = []
+    for validator, validator_keypair in zip(validators[:threshold], validator_keypairs):
+        dkg = Dkg(tau, shares_num, threshold, validators, validator)
+        decryption_share = aggregate.create_decryption_share_simple(
+            dkg, ciphertext.header, aad, validator_keypair
+        )
+        proof = aggregate.create_decryption_share_proof_simple(
+            dkg, decryption_share, ciphertext.header, validator_keypair
+        )
+        # Proofs can be published and checked by anyone holding the aggregate
+        proof = DecryptionShareProof.from_bytes(bytes(proof))
+        assert aggregate.verify_decryption_share_simple(
+            decryption_share, proof, ciphertext.header
+        )
+        decryption_shares.append(decryption_share)
+
+    shared_secret = combine_decryption_shares_simple(decryption_shares)
+    plaintext = decrypt_with_shared_secret(ciphertext, aad, shared_secret)
+    assert bytes(plaintext) == msg
+
+
 PARAMS = [
     (1, FerveoVariant.Simple),
     (3, FerveoVariant.Simple),
@@ -0,0 +1,97 @@
+#![allow(clippy::redundant_closure)]
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::{UniformRand, Zero};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+};
+use ferveo_nucypher_tdec::{
+    lagrange_basis_at, lagrange_basis_at_fft_domain,
+    lagrange_basis_at_subproduct,
+};
+use rand::prelude::StdRng;
+use rand_core::SeedableRng;
+
+type E = Bls12_381;
+
+const NUM_SHARES_CASES: [usize; 6] = [16, 32, 64, 128, 256, 512];
+
+fn naive_lagrange_basis_at(shares_x: &[Fr], x_i: &Fr) -> Vec<Fr> {
+    shares_x
+        .iter()
+        .map(|x_j| {
+            shares_x
+                .iter()
+                .filter(|x_m| *x_m != x_j)
+                .map(|x_m| (*x_m - x_i) / (*x_m - x_j))
+                .product()
+        })
+        .collect()
+}
+
+pub fn bench_lagrange_arbitrary_points(c: &mut Criterion) {
+    let rng = &mut StdRng::seed_from_u64(0);
+
+    let mut group = c.benchmark_group("LAGRANGE ARBITRARY POINTS");
+    group.sample_size(10);
+
+    for shares_num in NUM_SHARES_CASES {
+        let shares_x =
+            (0..shares_num).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let x_i = Fr::zero();
+
+        group.bench_function(BenchmarkId::new("naive", shares_num), |b| {
+            b.iter(|| black_box(naive_lagrange_basis_at(&shares_x, &x_i)))
+        });
+        group.bench_function(
+            BenchmarkId::new("batch_inversion", shares_num),
+            |b| b.iter(|| black_box(lagrange_basis_at::<E>(&shares_x, &x_i))),
+        );
+        group.bench_function(
+            BenchmarkId::new("subproduct_domain", shares_num),
+            |b| {
+                b.iter(|| {
+                    black_box(lagrange_basis_at_subproduct::<E>(
+                        &shares_x, &x_i,
+                    ))
+                })
+            },
+        );
+    }
+}
+
+pub fn bench_lagrange_fft_domain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("LAGRANGE FFT DOMAIN");
+    group.sample_size(10);
+
+    for shares_num in NUM_SHARES_CASES {
+        let domain = GeneralEvaluationDomain::<Fr>::new(shares_num).unwrap();
+        // Select two thirds of the domain, as in a typical threshold setting
+        let selected = domain
+            .elements()
+            .take(shares_num * 2 / 3)
+            .collect::<Vec<_>>();
+        let x_i = Fr::zero();
+
+        group.bench_function(
+            BenchmarkId::new("batch_inversion", shares_num),
+            |b| b.iter(|| black_box(lagrange_basis_at::<E>(&selected, &x_i))),
+        );
+        group.bench_function(BenchmarkId::new("fft_domain", shares_num), |b| {
+            b.iter(|| {
+                black_box(lagrange_basis_at_fft_domain::<E>(
+                    &domain, &selected, &x_i,
+                ))
+            })
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_lagrange_arbitrary_points,
+    bench_lagrange_fft_domain,
+);
+
+criterion_main!(benches);
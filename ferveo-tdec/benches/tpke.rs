@@ -245,6 +245,23 @@ pub fn bench_share_encrypt_decrypt(c: &mut Criterion) {
                 );
             }
         };
+        let mut encrypt_with_key = {
+            let mut rng = rng.clone();
+            let setup = SetupSimple::new(shares_num, msg_size, &mut rng);
+            let encryption_key = EncryptionKey::new(&setup.shared.pubkey);
+            move || {
+                let setup = setup.clone();
+                black_box(
+                    encrypt_with_key::<E>(
+                        SecretBox::new(setup.shared.msg),
+                        &setup.shared.aad,
+                        &encryption_key,
+                        &mut rng,
+                    )
+                    .unwrap(),
+                );
+            }
+        };
         let decrypt = {
             let setup = SetupSimple::new(shares_num, msg_size, rng);
             move || {
@@ -262,6 +279,10 @@ pub fn bench_share_encrypt_decrypt(c: &mut Criterion) {
         group.bench_function(BenchmarkId::new("encrypt", msg_size), |b| {
             b.iter(|| encrypt())
         });
+        group.bench_function(
+            BenchmarkId::new("encrypt_with_key", msg_size),
+            |b| b.iter(|| encrypt_with_key()),
+        );
         group.bench_function(BenchmarkId::new("decrypt", msg_size), |b| {
             b.iter(|| decrypt())
         });
@@ -1,11 +1,25 @@
 //! Contains the public API of the library.
+//!
+//! The API is instantiated over BLS12-381, unless the `bn254` or `bls12-377`
+//! feature selects another curve. At most one of them can be enabled.
 
+#[cfg(all(feature = "bn254", feature = "bls12-377"))]
+compile_error!(
+    "The `bn254` and `bls12-377` features select the curve of the API, \
+     so only one of them can be enabled"
+);
+
+#[cfg(feature = "bn254")]
+pub type E = ark_bn254::Bn254;
+#[cfg(all(feature = "bls12-377", not(feature = "bn254")))]
+pub type E = ark_bls12_377::Bls12_377;
+#[cfg(not(any(feature = "bls12-377", feature = "bn254")))]
 pub type E = ark_bls12_381::Bls12_381;
 pub type G1Prepared = <E as ark_ec::pairing::Pairing>::G1Prepared;
 pub type G1Affine = <E as ark_ec::pairing::Pairing>::G1Affine;
 pub type G2Affine = <E as ark_ec::pairing::Pairing>::G2Affine;
-pub type Fr = ark_bls12_381::Fr;
-pub type PrivateKey = ark_bls12_381::G2Affine;
+pub type Fr = <E as ark_ec::pairing::Pairing>::ScalarField;
+pub type PrivateKey = <E as ark_ec::pairing::Pairing>::G2Affine;
 pub type Result<T> = crate::Result<T>;
 pub type PrivateDecryptionContextSimple =
     crate::PrivateDecryptionContextSimple<E>;
@@ -14,10 +28,11 @@ pub type DecryptionShareSimple = crate::DecryptionShareSimple<E>;
 pub type Ciphertext = crate::Ciphertext<E>;
 
 pub type CiphertextHeader = crate::CiphertextHeader<E>;
+pub type VerifiedCiphertextHeader = crate::VerifiedCiphertextHeader<E>;
 pub type TargetField = <E as ark_ec::pairing::Pairing>::TargetField;
 
 pub use crate::{
-    decrypt_symmetric, decrypt_with_shared_secret, encrypt,
-    prepare_combine_simple, share_combine_precomputed, share_combine_simple,
-    SecretBox, SharedSecret,
+    decrypt_symmetric, decrypt_with_shared_secret,
+    decrypt_with_verified_header, encrypt, prepare_combine_simple,
+    share_combine_precomputed, share_combine_simple, SecretBox, SharedSecret,
 };
@@ -0,0 +1,541 @@
+use std::ops::Mul;
+
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use ferveo_common::serialization;
+use itertools::izip;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    CiphertextHeader, Error, HashToG2, MaskedShare, MaskingBases, MaskingProof,
+    PrivateKeyShare, Result, ShareCommitment, SharedSecret,
+};
+
+const BLINDED_SHARE_PROOF_DST: &[u8] = b"FERVEO_BLINDED_DECRYPTION_SHARE";
+
+/// Ephemeral keypair of a decryption requester.
+/// Validators blind their decryption shares to the public key, so that only
+/// the holder of this keypair can combine them.
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct RequesterKeypair<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
+    secret: E::ScalarField,
+}
+
+impl<E: Pairing> RequesterKeypair<E> {
+    pub fn random<R: rand::RngCore>(rng: &mut R) -> Self {
+        // A zero secret has the identity as its public key, which validators
+        // refuse to blind shares to
+        loop {
+            let secret = E::ScalarField::rand(rng);
+            if !secret.is_zero() {
+                return Self { secret };
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> RequesterPublicKey<E> {
+        RequesterPublicKey(E::G1Affine::generator().mul(self.secret).into())
+    }
+}
+
+/// Public key `P` of a decryption requester. The identity is rejected, since
+/// shares blinded to it are not blinded at all.
+#[serde_as]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequesterPublicKey<E: Pairing>(
+    #[serde_as(as = "serialization::NonIdentity")] pub E::G1Affine,
+);
+
+impl<E: Pairing> RequesterPublicKey<E> {
+    fn check(&self) -> Result<()> {
+        if self.0.is_zero() {
+            return Err(Error::InvalidRequesterPublicKey);
+        }
+        Ok(())
+    }
+}
+
+/// A decryption share (simple variant) blinded to a requester's public key `P`.
+/// The share `e(U, Z_i)` is masked with `e(P, H)^{-r_i}`.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindedDecryptionShareSimple<E: Pairing> {
+    /// `[r_i] G`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub commitment: E::G1Affine,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub blinded_share: E::TargetField,
+    #[serde(bound(
+        serialize = "MaskingProof<E>: Serialize",
+        deserialize = "MaskingProof<E>: DeserializeOwned"
+    ))]
+    pub proof: MaskingProof<E>,
+}
+
+impl<E: HashToG2> BlindedDecryptionShareSimple<E> {
+    /// Create a blinded decryption share from the given parameters.
+    /// This function checks that the ciphertext is valid.
+    pub fn create<R: rand::RngCore>(
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        requester_public_key: &RequesterPublicKey<E>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        requester_public_key.check()?;
+        ciphertext_header.check(aad)?;
+        let (masked_share, proof) = MaskingProof::create(
+            &proof_context(ciphertext_header),
+            &blinding_bases(ciphertext_header.commitment, requester_public_key),
+            private_key_share,
+            share_commitment,
+            rng,
+        )?;
+        Ok(Self {
+            commitment: masked_share.commitment,
+            blinded_share: masked_share.masked_share,
+            proof,
+        })
+    }
+
+    /// Verify the blinded share against the share commitment `A_i` of the
+    /// validator that produced it
+    pub fn verify(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        share_commitment: &ShareCommitment<E>,
+        requester_public_key: &RequesterPublicKey<E>,
+    ) -> Result<bool> {
+        self.proof.verify(
+            &proof_context(ciphertext_header),
+            &blinding_bases(ciphertext_header.commitment, requester_public_key),
+            share_commitment,
+            &MaskedShare {
+                masked_share: self.blinded_share,
+                commitment: self.commitment,
+                auth_tag: None,
+            },
+        )
+    }
+}
+
+/// A decryption share (precomputed variant) blinded to a requester's public
+/// key `P`. The share `e([λ_i(0)] U, Z_i)` is masked with `e(P, H)^{-r_i}`.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindedDecryptionSharePrecomputed<E: Pairing> {
+    pub decrypter_index: usize,
+    /// `[r_i] G`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub commitment: E::G1Affine,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub blinded_share: E::TargetField,
+    #[serde(bound(
+        serialize = "MaskingProof<E>: Serialize",
+        deserialize = "MaskingProof<E>: DeserializeOwned"
+    ))]
+    pub proof: MaskingProof<E>,
+}
+
+impl<E: HashToG2> BlindedDecryptionSharePrecomputed<E> {
+    /// Create a blinded decryption share from the given parameters.
+    /// This function checks that the ciphertext is valid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create<R: rand::RngCore>(
+        validator_index: usize,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        lagrange_coeff: &E::ScalarField,
+        requester_public_key: &RequesterPublicKey<E>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        requester_public_key.check()?;
+        ciphertext_header.check(aad)?;
+        let (masked_share, proof) = MaskingProof::create(
+            &proof_context(ciphertext_header),
+            &blinding_bases(
+                ciphertext_header.commitment.mul(lagrange_coeff).into(),
+                requester_public_key,
+            ),
+            private_key_share,
+            share_commitment,
+            rng,
+        )?;
+        Ok(Self {
+            decrypter_index: validator_index,
+            commitment: masked_share.commitment,
+            blinded_share: masked_share.masked_share,
+            proof,
+        })
+    }
+
+    /// Verify the blinded share against the share commitment `A_i` of the
+    /// validator that produced it, and its lagrange coefficient over the
+    /// selected validators
+    pub fn verify(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        share_commitment: &ShareCommitment<E>,
+        lagrange_coeff: &E::ScalarField,
+        requester_public_key: &RequesterPublicKey<E>,
+    ) -> Result<bool> {
+        self.proof.verify(
+            &proof_context(ciphertext_header),
+            &blinding_bases(
+                ciphertext_header.commitment.mul(lagrange_coeff).into(),
+                requester_public_key,
+            ),
+            share_commitment,
+            &MaskedShare {
+                masked_share: self.blinded_share,
+                commitment: self.commitment,
+                auth_tag: None,
+            },
+        )
+    }
+}
+
+/// Unblind and combine decryption shares (simple variant)
+pub fn share_combine_blinded_simple<E: Pairing>(
+    shares: &[BlindedDecryptionShareSimple<E>],
+    lagrange_coeffs: &[E::ScalarField],
+    requester_keypair: &RequesterKeypair<E>,
+) -> SharedSecret<E> {
+    let (blinded_secret, commitment) = izip!(shares, lagrange_coeffs).fold(
+        (E::TargetField::ONE, E::G1::zero()),
+        |(blinded_secret, commitment), (share, lagrange_coeff)| {
+            (
+                blinded_secret
+                    * share.blinded_share.pow(lagrange_coeff.into_bigint()),
+                commitment + share.commitment.mul(lagrange_coeff),
+            )
+        },
+    );
+    unblind(blinded_secret, commitment, requester_keypair)
+}
+
+/// Unblind and combine decryption shares (precomputed variant)
+pub fn share_combine_blinded_precomputed<E: Pairing>(
+    shares: &[BlindedDecryptionSharePrecomputed<E>],
+    requester_keypair: &RequesterKeypair<E>,
+) -> SharedSecret<E> {
+    let (blinded_secret, commitment) = shares.iter().fold(
+        (E::TargetField::ONE, E::G1::zero()),
+        |(blinded_secret, commitment), share| {
+            (
+                blinded_secret * share.blinded_share,
+                commitment + share.commitment,
+            )
+        },
+    );
+    unblind(blinded_secret, commitment, requester_keypair)
+}
+
+/// S = ∏ B_i^{λ_i} * e([x] ∑ [λ_i r_i] G, H), where x is the requester's secret
+fn unblind<E: Pairing>(
+    blinded_secret: E::TargetField,
+    commitment: E::G1,
+    requester_keypair: &RequesterKeypair<E>,
+) -> SharedSecret<E> {
+    let mask = E::pairing(
+        commitment.mul(requester_keypair.secret),
+        E::G2Affine::generator(),
+    );
+    SharedSecret(blinded_secret * mask.0)
+}
+
+fn blinding_bases<E: Pairing>(
+    share_base: E::G1Affine,
+    requester_public_key: &RequesterPublicKey<E>,
+) -> MaskingBases<E> {
+    MaskingBases {
+        share_base,
+        masking_key: requester_public_key.0,
+        tag_base: None,
+    }
+}
+
+fn proof_context<E: Pairing>(
+    ciphertext_header: &CiphertextHeader<E>,
+) -> Vec<u8> {
+    let mut context = BLINDED_SHARE_PROOF_DST.to_vec();
+    context.extend_from_slice(&ciphertext_header.ciphertext_hash);
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Mul;
+
+    use ark_ec::{pairing::Pairing, AffineRepr};
+    use ark_ff::{Field, One};
+    use ark_std::test_rng;
+    use itertools::izip;
+
+    use super::{blinding_bases, proof_context};
+
+    use crate::{
+        encrypt, prepare_combine_simple, share_combine_blinded_precomputed,
+        share_combine_blinded_simple, share_combine_simple, test_common::*,
+        BlindedDecryptionSharePrecomputed, BlindedDecryptionShareSimple,
+        DecryptionShareSimple, Error, MaskingProof, RequesterKeypair,
+        RequesterPublicKey, SecretBox,
+    };
+
+    type E = ark_bls12_381::Bls12_381;
+    type G1Affine = <E as Pairing>::G1Affine;
+    type ScalarField = <E as Pairing>::ScalarField;
+
+    #[test]
+    fn test_blinded_shares_simple() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (8, 5);
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let ciphertext = encrypt::<E>(
+            SecretBox::new("my-msg".as_bytes().to_vec()),
+            aad,
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let header = ciphertext.header().unwrap();
+        let requester_keypair = RequesterKeypair::<E>::random(rng);
+        let requester_public_key = requester_keypair.public_key();
+
+        let public_contexts = &contexts[0].public_decryption_contexts;
+        let (blinded_shares, shares): (Vec<_>, Vec<_>) = contexts[..threshold]
+            .iter()
+            .map(|context| {
+                let share_commitment =
+                    &public_contexts[context.index].share_commitment;
+                let blinded_share = BlindedDecryptionShareSimple::create(
+                    &header,
+                    aad,
+                    &context.private_key_share,
+                    share_commitment,
+                    &requester_public_key,
+                    rng,
+                )
+                .unwrap();
+                assert!(blinded_share
+                    .verify(&header, share_commitment, &requester_public_key)
+                    .unwrap());
+                // A share is bound to the requester it was blinded to
+                let other_requester =
+                    RequesterKeypair::<E>::random(rng).public_key();
+                assert!(!blinded_share
+                    .verify(&header, share_commitment, &other_requester)
+                    .unwrap());
+                // A share moved out of GT by a -1 factor is rejected
+                let (masked_share, proof) = MaskingProof::create_negated(
+                    &proof_context(&header),
+                    &blinding_bases(header.commitment, &requester_public_key),
+                    &context.private_key_share,
+                    share_commitment,
+                    rng,
+                )
+                .unwrap();
+                let negated_share = BlindedDecryptionShareSimple {
+                    commitment: masked_share.commitment,
+                    blinded_share: masked_share.masked_share,
+                    proof,
+                };
+                assert!(!negated_share
+                    .verify(&header, share_commitment, &requester_public_key)
+                    .unwrap());
+                let share = DecryptionShareSimple::create(
+                    &context.setup_params.b,
+                    &context.private_key_share,
+                    &header,
+                    aad,
+                )
+                .unwrap();
+                (blinded_share, share)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .unzip();
+        // Blinded shares don't leak the underlying share
+        assert!(izip!(&blinded_shares, &shares)
+            .all(|(b, s)| b.blinded_share != s.decryption_share));
+
+        let domain_points = public_contexts[..threshold]
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let lagrange_coeffs = prepare_combine_simple::<E>(&domain_points);
+        let shared_secret = share_combine_blinded_simple(
+            &blinded_shares,
+            &lagrange_coeffs,
+            &requester_keypair,
+        );
+        assert_eq!(
+            shared_secret,
+            share_combine_simple::<E>(&shares, &lagrange_coeffs)
+        );
+
+        // Someone else can't unblind the shares
+        let shared_secret = share_combine_blinded_simple(
+            &blinded_shares,
+            &lagrange_coeffs,
+            &RequesterKeypair::<E>::random(rng),
+        );
+        assert_ne!(
+            shared_secret,
+            share_combine_simple::<E>(&shares, &lagrange_coeffs)
+        );
+    }
+
+    #[test]
+    fn test_identity_requester_public_key_is_rejected() {
+        let rng = &mut test_rng();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let (pubkey, _, contexts) = setup_simple::<E>(4, 3, rng);
+        let ciphertext = encrypt::<E>(
+            SecretBox::new("my-msg".as_bytes().to_vec()),
+            aad,
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let header = ciphertext.header().unwrap();
+
+        let identity = RequesterPublicKey::<E>(G1Affine::zero());
+        let bytes = bincode::serialize(&identity).unwrap();
+        assert!(bincode::deserialize::<RequesterPublicKey<E>>(&bytes).is_err());
+
+        let context = &contexts[0];
+        let share_commitment =
+            &context.public_decryption_contexts[0].share_commitment;
+        assert!(matches!(
+            BlindedDecryptionShareSimple::create(
+                &header,
+                aad,
+                &context.private_key_share,
+                share_commitment,
+                &identity,
+                rng,
+            ),
+            Err(Error::InvalidRequesterPublicKey)
+        ));
+        assert!(matches!(
+            BlindedDecryptionSharePrecomputed::create(
+                0,
+                &header,
+                aad,
+                &context.private_key_share,
+                share_commitment,
+                &ScalarField::one(),
+                &identity,
+                rng,
+            ),
+            Err(Error::InvalidRequesterPublicKey)
+        ));
+    }
+
+    #[test]
+    fn test_blinded_shares_precomputed() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (8, 8);
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let ciphertext =
+            encrypt::<E>(SecretBox::new(msg.clone()), aad, &pubkey, rng)
+                .unwrap();
+        let header = ciphertext.header().unwrap();
+        let requester_keypair = RequesterKeypair::<E>::random(rng);
+        let requester_public_key = requester_keypair.public_key();
+
+        let public_contexts = &contexts[0].public_decryption_contexts;
+        let domain_points = public_contexts
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let lagrange_coeffs = prepare_combine_simple::<E>(&domain_points);
+        let blinded_shares = contexts
+            .iter()
+            .map(|context| {
+                let share_commitment =
+                    &public_contexts[context.index].share_commitment;
+                let lagrange_coeff = &lagrange_coeffs[context.index];
+                let blinded_share = BlindedDecryptionSharePrecomputed::create(
+                    context.index,
+                    &header,
+                    aad,
+                    &context.private_key_share,
+                    share_commitment,
+                    lagrange_coeff,
+                    &requester_public_key,
+                    rng,
+                )
+                .unwrap();
+                assert!(blinded_share
+                    .verify(
+                        &header,
+                        share_commitment,
+                        lagrange_coeff,
+                        &requester_public_key
+                    )
+                    .unwrap());
+                // A share moved out of GT by a -1 factor is rejected
+                let (masked_share, proof) = MaskingProof::create_negated(
+                    &proof_context(&header),
+                    &blinding_bases(
+                        header.commitment.mul(lagrange_coeff).into(),
+                        &requester_public_key,
+                    ),
+                    &context.private_key_share,
+                    share_commitment,
+                    rng,
+                )
+                .unwrap();
+                let negated_share = BlindedDecryptionSharePrecomputed {
+                    decrypter_index: context.index,
+                    commitment: masked_share.commitment,
+                    blinded_share: masked_share.masked_share,
+                    proof,
+                };
+                assert!(!negated_share
+                    .verify(
+                        &header,
+                        share_commitment,
+                        lagrange_coeff,
+                        &requester_public_key
+                    )
+                    .unwrap());
+                // The proof is bound to the lagrange coefficient
+                assert!(!blinded_share
+                    .verify(
+                        &header,
+                        share_commitment,
+                        &lagrange_coeff.double(),
+                        &requester_public_key
+                    )
+                    .unwrap());
+                blinded_share
+            })
+            .collect::<Vec<_>>();
+
+        let shared_secret = share_combine_blinded_precomputed(
+            &blinded_shares,
+            &requester_keypair,
+        );
+        let plaintext =
+            crate::decrypt_with_shared_secret(&ciphertext, aad, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, msg);
+    }
+}
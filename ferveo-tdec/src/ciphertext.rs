@@ -14,19 +14,19 @@ use sha2::{digest::Digest, Sha256};
 use zeroize::ZeroizeOnDrop;
 
 use crate::{
-    htp_bls12381_g2, DkgPublicKey, Error, PrivateKeyShare, Result, SecretBox,
-    SharedSecret,
+    DkgPublicKey, EncryptionKey, Error, HashToG2, PrivateKeyShare, Result,
+    SecretBox, SharedSecret,
 };
 
 #[serde_as]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Ciphertext<E: Pairing> {
     // U
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentity")]
     pub commitment: E::G1Affine,
 
     // W
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentity")]
     pub auth_tag: E::G2Affine,
 
     // V
@@ -34,11 +34,21 @@ pub struct Ciphertext<E: Pairing> {
     pub ciphertext: Vec<u8>,
 }
 
-impl<E: Pairing> Ciphertext<E> {
+impl<E: HashToG2> Ciphertext<E> {
     pub fn check(&self, aad: &[u8]) -> Result<bool> {
         self.header()?.check(aad)
     }
 
+    /// Check the ciphertext once, for use without checking it again
+    pub fn verify(&self, aad: &[u8]) -> Result<VerifiedCiphertextHeader<E>> {
+        let header = self.header()?;
+        header.check(aad)?;
+        Ok(VerifiedCiphertextHeader {
+            header,
+            aad: aad.to_vec(),
+        })
+    }
+
     pub fn ciphertext_hash(&self) -> [u8; 32] {
         sha256(&self.ciphertext)
     }
@@ -58,20 +68,25 @@ impl<E: Pairing> Ciphertext<E> {
 #[serde_as]
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CiphertextHeader<E: Pairing> {
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentity")]
     pub commitment: E::G1Affine,
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentity")]
     pub auth_tag: E::G2Affine,
     pub ciphertext_hash: [u8; 32],
 }
 
-impl<E: Pairing> CiphertextHeader<E> {
+impl<E: HashToG2> CiphertextHeader<E> {
     pub fn check(&self, aad: &[u8]) -> Result<bool> {
         // Implements a variant of the check in section 4.4.2 of the Ferveo paper:
         // 'TPKE.CheckCiphertextValidity(U,W,aad)'
         // See: https://eprint.iacr.org/2022/898.pdf
         // See: https://nikkolasg.github.io/ferveo/tpke.html#to-validate-ciphertext-for-ind-cca2-security
 
+        // Identity points pass the pairing check below for any tag
+        if self.commitment.is_zero() || self.auth_tag.is_zero() {
+            return Err(Error::CiphertextVerificationFailed);
+        }
+
         // H_G2(U, sym_ctxt_digest, aad)
         let hash_g2 = E::G2Prepared::from(construct_tag_hash::<E>(
             self.commitment,
@@ -95,9 +110,37 @@ impl<E: Pairing> CiphertextHeader<E> {
             Err(Error::CiphertextVerificationFailed)
         }
     }
+
+    /// Check the header once, for use without checking it again
+    pub fn verify(&self, aad: &[u8]) -> Result<VerifiedCiphertextHeader<E>> {
+        self.check(aad)?;
+        Ok(VerifiedCiphertextHeader {
+            header: self.clone(),
+            aad: aad.to_vec(),
+        })
+    }
+}
+
+/// A ciphertext header that passed [`CiphertextHeader::check`] for `aad`.
+/// It can only be made by [`CiphertextHeader::verify`], so decryption shares
+/// and plaintexts can be made from it without checking the header again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedCiphertextHeader<E: Pairing> {
+    header: CiphertextHeader<E>,
+    aad: Vec<u8>,
+}
+
+impl<E: Pairing> VerifiedCiphertextHeader<E> {
+    pub fn header(&self) -> &CiphertextHeader<E> {
+        &self.header
+    }
+
+    pub fn aad(&self) -> &[u8] {
+        &self.aad
+    }
 }
 
-pub fn encrypt<E: Pairing>(
+pub fn encrypt<E: HashToG2>(
     message: SecretBox<Vec<u8>>,
     aad: &[u8],
     pubkey: &DkgPublicKey<E>,
@@ -116,6 +159,34 @@ pub fn encrypt<E: Pairing>(
     // u
     let commitment = g_gen.mul(rand_element).into();
 
+    encrypt_with_randomness(message, aad, rand_element, commitment, product)
+}
+
+/// Same as `encrypt`, but uses the precomputed pairing and generator table
+/// from `encryption_key` instead of computing a pairing for every message.
+pub fn encrypt_with_key<E: HashToG2>(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    encryption_key: &EncryptionKey<E>,
+    rng: &mut impl rand::Rng,
+) -> Result<Ciphertext<E>> {
+    // r
+    let rand_element = E::ScalarField::rand(rng);
+    // s
+    let product = encryption_key.shared_secret_product(&rand_element);
+    // u
+    let commitment = encryption_key.commitment(&rand_element);
+
+    encrypt_with_randomness(message, aad, rand_element, commitment, product)
+}
+
+fn encrypt_with_randomness<E: HashToG2>(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    rand_element: E::ScalarField,
+    commitment: E::G1Affine,
+    product: E::TargetField,
+) -> Result<Ciphertext<E>> {
     let nonce = Nonce::from_commitment::<E>(commitment)?;
     let shared_secret = SharedSecret::<E>(product);
 
@@ -142,7 +213,7 @@ pub fn encrypt<E: Pairing>(
     })
 }
 
-pub fn decrypt_symmetric<E: Pairing>(
+pub fn decrypt_symmetric<E: HashToG2>(
     ciphertext: &Ciphertext<E>,
     aad: &[u8],
     private_key: &PrivateKeyShare<E>,
@@ -176,7 +247,7 @@ fn decrypt_with_shared_secret_unchecked<E: Pairing>(
     Ok(plaintext)
 }
 
-pub fn decrypt_with_shared_secret<E: Pairing>(
+pub fn decrypt_with_shared_secret<E: HashToG2>(
     ciphertext: &Ciphertext<E>,
     aad: &[u8],
     shared_secret: &SharedSecret<E>,
@@ -185,6 +256,23 @@ pub fn decrypt_with_shared_secret<E: Pairing>(
     decrypt_with_shared_secret_unchecked(ciphertext, aad, shared_secret)
 }
 
+/// Decrypt a ciphertext whose header has already been checked. The ciphertext
+/// must match the header, and is authenticated with the aad it was checked for.
+pub fn decrypt_with_verified_header<E: HashToG2>(
+    ciphertext: &Ciphertext<E>,
+    verified_header: &VerifiedCiphertextHeader<E>,
+    shared_secret: &SharedSecret<E>,
+) -> Result<Vec<u8>> {
+    if ciphertext.header()? != verified_header.header {
+        return Err(Error::CiphertextVerificationFailed);
+    }
+    decrypt_with_shared_secret_unchecked(
+        ciphertext,
+        &verified_header.aad,
+        shared_secret,
+    )
+}
+
 fn sha256(input: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(input);
@@ -223,16 +311,7 @@ impl Nonce {
     }
 }
 
-fn hash_to_g2<T: ark_serialize::CanonicalDeserialize>(
-    message: &[u8],
-) -> Result<T> {
-    let point = htp_bls12381_g2(message);
-    let mut point_ser: Vec<u8> = Vec::new();
-    point.serialize_compressed(&mut point_ser)?;
-    T::deserialize_compressed(&point_ser[..]).map_err(Error::ArkSerializeError)
-}
-
-fn construct_tag_hash<E: Pairing>(
+pub(crate) fn construct_tag_hash<E: HashToG2>(
     commitment: E::G1Affine,
     ciphertext_hash: &[u8],
     aad: &[u8],
@@ -241,12 +320,14 @@ fn construct_tag_hash<E: Pairing>(
     commitment.serialize_compressed(&mut hash_input)?;
     hash_input.extend_from_slice(ciphertext_hash);
     hash_input.extend_from_slice(aad);
-    hash_to_g2(&hash_input)
+    E::hash_to_g2(&hash_input)
 }
 
 #[cfg(test)]
 mod tests {
+    use ark_ec::{pairing::Pairing, AffineRepr};
     use ark_std::test_rng;
+    use ferveo_common::{FromBytes, ToBytes};
 
     use crate::{test_common::*, *};
 
@@ -276,6 +357,39 @@ mod tests {
         assert!(decrypt_symmetric(&ciphertext, bad, &privkey).is_err());
     }
 
+    #[test]
+    fn encryption_with_precomputed_key() {
+        let rng = &mut test_rng();
+        let shares_num = 16;
+        let threshold = shares_num * 2 / 3;
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, privkey, _) =
+            setup_simple::<E>(threshold, shares_num, rng);
+        let encryption_key = EncryptionKey::new(&pubkey);
+
+        // Only the public key is serialized, and the precomputed values are
+        // rebuilt from it on load
+        let bytes = bincode::serialize(&encryption_key).unwrap();
+        assert_eq!(bytes, bincode::serialize(&pubkey).unwrap());
+        let encryption_key: EncryptionKey<E> =
+            bincode::deserialize(&bytes).unwrap();
+        assert_eq!(encryption_key, EncryptionKey::new(&pubkey));
+
+        let ciphertext = encrypt_with_key::<E>(
+            SecretBox::new(msg.clone()),
+            aad,
+            &encryption_key,
+            rng,
+        )
+        .unwrap();
+        assert!(ciphertext.check(aad).is_ok());
+
+        let plaintext = decrypt_symmetric(&ciphertext, aad, &privkey).unwrap();
+        assert_eq!(msg, plaintext);
+    }
+
     #[test]
     fn ciphertext_validity_check() {
         let rng = &mut test_rng();
@@ -298,4 +412,62 @@ mod tests {
         let aad = "bad aad".as_bytes();
         assert!(ciphertext.check(aad).is_err());
     }
+
+    #[test]
+    fn decryption_with_verified_header() {
+        let rng = &mut test_rng();
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let (pubkey, privkey, _) = setup_simple::<E>(3, 4, rng);
+        let ciphertext =
+            encrypt::<E>(SecretBox::new(msg.clone()), aad, &pubkey, rng)
+                .unwrap();
+        let shared_secret =
+            SharedSecret(E::pairing(ciphertext.commitment, privkey.0).0);
+
+        // Only a valid header can be verified
+        assert!(ciphertext.verify(b"bad-aad").is_err());
+        let verified_header = ciphertext.verify(aad).unwrap();
+        assert_eq!(verified_header.aad(), aad);
+        assert_eq!(
+            decrypt_with_verified_header(
+                &ciphertext,
+                &verified_header,
+                &shared_secret
+            )
+            .unwrap(),
+            msg
+        );
+
+        // The header doesn't vouch for a different ciphertext
+        let mut other_ciphertext = ciphertext.clone();
+        other_ciphertext.ciphertext[0] ^= 1;
+        assert!(decrypt_with_verified_header(
+            &other_ciphertext,
+            &verified_header,
+            &shared_secret
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn ciphertext_with_identity_points_is_rejected() {
+        let rng = &mut test_rng();
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let (pubkey, _, _) = setup_simple::<E>(3, 4, rng);
+        let ciphertext =
+            encrypt::<E>(SecretBox::new(msg), aad, &pubkey, rng).unwrap();
+
+        let mut bad_ciphertext = ciphertext.clone();
+        bad_ciphertext.commitment = <E as Pairing>::G1Affine::zero();
+        bad_ciphertext.auth_tag = <E as Pairing>::G2Affine::zero();
+        assert!(bad_ciphertext.check(aad).is_err());
+
+        // Such a ciphertext doesn't deserialize either
+        let bytes = bad_ciphertext.to_bytes().unwrap();
+        assert!(Ciphertext::<E>::from_bytes(&bytes).is_err());
+        let bytes = bad_ciphertext.header().unwrap().to_bytes().unwrap();
+        assert!(CiphertextHeader::<E>::from_bytes(&bytes).is_err());
+    }
 }
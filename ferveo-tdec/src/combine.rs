@@ -1,11 +1,15 @@
 #![allow(non_snake_case)]
 
+use std::collections::{HashMap, HashSet};
+
 use ark_ec::pairing::Pairing;
-use ark_ff::{Field, One, PrimeField, Zero};
+use ark_ff::{FftField, Field, One, PrimeField, Zero};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
 use ferveo_common::serialization;
 use itertools::izip;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use subproductdomain::SubproductDomain;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[serde_as]
@@ -16,32 +20,186 @@ pub struct SharedSecret<E: Pairing>(
     #[serde_as(as = "serialization::SerdeAs")] pub(crate) E::TargetField,
 );
 
-use crate::{DecryptionSharePrecomputed, DecryptionShareSimple};
+use crate::{DecryptionSharePrecomputed, DecryptionShareSimple, DomainPoint};
 
+/// Lagrange coefficients at zero for the domain points of the selected shares.
+///
+/// Domain points are roots of unity, so this uses `lagrange_basis_at_fft_domain`
+/// over the smallest evaluation domain that contains them, and only falls
+/// back to `lagrange_basis_at` for arbitrary points.
 pub fn prepare_combine_simple<E: Pairing>(
     domain: &[E::ScalarField],
 ) -> Vec<E::ScalarField> {
-    // In this formula x_i = 0, hence numerator is x_m
-    // See https://en.wikipedia.org/wiki/Lagrange_polynomial#Optimal_algorithm
-    lagrange_basis_at::<E>(domain, &E::ScalarField::zero())
+    let zero = E::ScalarField::zero();
+    enclosing_fft_domain(domain)
+        .and_then(|fft_domain| {
+            lagrange_basis_at_fft_domain::<E>(&fft_domain, domain, &zero)
+        })
+        .unwrap_or_else(|| lagrange_basis_at::<E>(domain, &zero))
+}
+
+/// Lagrange coefficient at zero of the domain point of `share_index`, over the
+/// domain points of all the selected shares, or `None` if `share_index` is not
+/// selected
+pub fn lagrange_coeff_at_zero<E: Pairing>(
+    share_index: u32,
+    domain_points: &HashMap<u32, DomainPoint<E>>,
+) -> Option<E::ScalarField> {
+    let mut domain_points = domain_points.iter().collect::<Vec<_>>();
+    domain_points.sort_by_key(|(share_index, _)| **share_index);
+    let position = domain_points
+        .iter()
+        .position(|(index, _)| **index == share_index)?;
+    let domain_points = domain_points
+        .into_iter()
+        .map(|(_, domain_point)| *domain_point)
+        .collect::<Vec<_>>();
+    Some(prepare_combine_simple::<E>(&domain_points)[position])
+}
+
+/// The smallest radix-2 evaluation domain containing all of `points`, or
+/// `None` if one of them is not a root of unity of power-of-two order.
+///
+/// Every root of unity of order `2^k` belongs to the unique subgroup of that
+/// order, so the domain only depends on the largest order among the points.
+pub fn enclosing_fft_domain<F: FftField>(
+    points: &[F],
+) -> Option<GeneralEvaluationDomain<F>> {
+    let mut log_size = 0;
+    for point in points {
+        let mut power = *point;
+        let mut log_order = 0;
+        while !power.is_one() {
+            if log_order >= F::TWO_ADICITY {
+                return None;
+            }
+            power.square_in_place();
+            log_order += 1;
+        }
+        log_size = log_size.max(log_order);
+    }
+    GeneralEvaluationDomain::new(1 << log_size)
 }
 
-/// Calculate lagrange coefficients using optimized formula
+/// Calculate lagrange coefficients for arbitrary points
+///
+/// Numerators are computed with prefix and suffix products and all the
+/// denominators are inverted at once, so this takes O(n²) multiplications
+/// but a single field inversion. It is the reference the faster variants
+/// below are tested against.
 pub fn lagrange_basis_at<E: Pairing>(
     shares_x: &[E::ScalarField],
     x_i: &E::ScalarField,
 ) -> Vec<<E>::ScalarField> {
-    let mut lagrange_coeffs = vec![];
-    for x_j in shares_x {
-        let mut prod = E::ScalarField::one();
-        for x_m in shares_x {
-            if x_j != x_m {
-                prod *= (*x_m - x_i) / (*x_m - *x_j);
-            }
-        }
-        lagrange_coeffs.push(prod);
+    // L_j(x_i) = ∏_{m != j} (x_m - x_i) / ∏_{m != j} (x_m - x_j)
+    let mut denominators = shares_x
+        .iter()
+        .map(|x_j| {
+            shares_x
+                .iter()
+                .filter(|x_m| *x_m != x_j)
+                .map(|x_m| *x_m - x_j)
+                .product::<E::ScalarField>()
+        })
+        .collect::<Vec<_>>();
+    ark_ff::batch_inversion(&mut denominators);
+    let numerators = products_except_self::<E::ScalarField>(
+        shares_x.iter().map(|x_m| *x_m - x_i),
+    );
+    izip!(numerators, denominators)
+        .map(|(num, den_inv)| num * den_inv)
+        .collect()
+}
+
+/// Calculate lagrange coefficients for points taken from a multiplicative
+/// FFT domain.
+///
+/// For a domain of size `N` with vanishing polynomial `Z(x) = x^N - 1`, we
+/// have `Z'(ω_j) = N / ω_j`, so for a subset `S` of the domain the inverse of
+/// `∏_{m ∈ S, m != j} (ω_j - ω_m)` is `ω_j ∏_{m ∉ S} (ω_j - ω_m) / N`.
+/// No inversion other than `1/N` is needed, and the cost is linear in `|S|`
+/// when the whole domain is selected.
+///
+/// Returns `None` if any of the points is not an element of `domain`.
+pub fn lagrange_basis_at_fft_domain<E: Pairing>(
+    domain: &GeneralEvaluationDomain<E::ScalarField>,
+    shares_x: &[E::ScalarField],
+    x_i: &E::ScalarField,
+) -> Option<Vec<E::ScalarField>> {
+    let selected = shares_x.iter().collect::<HashSet<_>>();
+    let elements = domain.elements().collect::<HashSet<_>>();
+    if selected.len() != shares_x.len()
+        || !shares_x.iter().all(|x| elements.contains(x))
+    {
+        return None;
     }
-    lagrange_coeffs
+    let unselected = domain
+        .elements()
+        .filter(|x| !selected.contains(x))
+        .collect::<Vec<_>>();
+    let size_inv = domain.size_as_field_element().inverse()?;
+    // L_j(x_i) = ∏_{m != j} (x_i - x_m) / ∏_{m != j} (x_j - x_m)
+    let numerators = products_except_self::<E::ScalarField>(
+        shares_x.iter().map(|x_m| *x_i - x_m),
+    );
+    let coeffs = izip!(shares_x, numerators)
+        .map(|(x_j, num)| {
+            let den_inv = unselected
+                .iter()
+                .map(|x_m| *x_j - x_m)
+                .product::<E::ScalarField>()
+                * x_j
+                * size_inv;
+            num * den_inv
+        })
+        .collect();
+    Some(coeffs)
+}
+
+/// Calculate lagrange coefficients for arbitrary points using a subproduct
+/// tree, in O(n log² n) field operations.
+///
+/// The constant factors are large: see `benches/lagrange.rs` for where this
+/// overtakes `lagrange_basis_at`.
+pub fn lagrange_basis_at_subproduct<E: Pairing>(
+    shares_x: &[E::ScalarField],
+    x_i: &E::ScalarField,
+) -> Vec<E::ScalarField> {
+    if shares_x.is_empty() {
+        return vec![];
+    }
+    // If x_i is one of the points, the basis is the indicator vector
+    if let Some(position) = shares_x.iter().position(|x| x == x_i) {
+        let mut coeffs = vec![E::ScalarField::zero(); shares_x.len()];
+        coeffs[position] = E::ScalarField::one();
+        return coeffs;
+    }
+    // L_j(x_i) = m(x_i) / ((x_i - x_j) * m'(x_j))
+    let subproduct_domain = SubproductDomain::new(shares_x.to_vec());
+    let m_at_x_i = subproduct_domain.t.m.evaluate(x_i);
+    let mut coeffs = subproduct_domain.inverse_lagrange_coefficients();
+    for (coeff, x_j) in coeffs.iter_mut().zip(shares_x) {
+        *coeff *= *x_i - x_j;
+    }
+    ark_ff::batch_inversion_and_mul(&mut coeffs, &m_at_x_i);
+    coeffs
+}
+
+/// For each position `j`, the product of all the items except the `j`-th one
+fn products_except_self<F: Field>(items: impl Iterator<Item = F>) -> Vec<F> {
+    let items = items.collect::<Vec<_>>();
+    let mut products = Vec::with_capacity(items.len());
+    let mut prefix = F::one();
+    for item in &items {
+        products.push(prefix);
+        prefix *= item;
+    }
+    let mut suffix = F::one();
+    for (product, item) in products.iter_mut().zip(&items).rev() {
+        *product *= suffix;
+        suffix *= item;
+    }
+    products
 }
 
 pub fn share_combine_simple<E: Pairing>(
@@ -70,15 +228,40 @@ pub fn share_combine_precomputed<E: Pairing>(
 
 #[cfg(test)]
 mod tests {
-    type ScalarField =
-        <ark_bls12_381::Bls12_381 as ark_ec::pairing::Pairing>::ScalarField;
+    use ark_ff::{Field, One, UniformRand, Zero};
+    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+    use ark_std::test_rng;
+
+    use super::*;
+
+    type E = ark_bls12_381::Bls12_381;
+    type ScalarField = <E as Pairing>::ScalarField;
+
+    fn naive_lagrange_basis_at(
+        shares_x: &[ScalarField],
+        x_i: &ScalarField,
+    ) -> Vec<ScalarField> {
+        shares_x
+            .iter()
+            .map(|x_j| {
+                shares_x
+                    .iter()
+                    .filter(|x_m| *x_m != x_j)
+                    .map(|x_m| (*x_m - x_i) / (*x_m - x_j))
+                    .product()
+            })
+            .collect()
+    }
+
+    fn random_points(n: usize) -> Vec<ScalarField> {
+        let rng = &mut test_rng();
+        (0..n).map(|_| ScalarField::rand(rng)).collect()
+    }
 
     #[test]
     fn test_lagrange() {
-        use ark_poly::EvaluationDomain;
-        use ark_std::One;
         let fft_domain =
-            ark_poly::GeneralEvaluationDomain::<ScalarField>::new(500).unwrap();
+            GeneralEvaluationDomain::<ScalarField>::new(500).unwrap();
 
         let mut domain = Vec::with_capacity(500);
         let mut point = ScalarField::one();
@@ -87,13 +270,157 @@ mod tests {
             point *= fft_domain.group_gen();
         }
 
-        // TODO: #197
-        let mut lagrange_n_0 = domain.iter().product::<ScalarField>();
-        if domain.len() % 2 == 1 {
-            lagrange_n_0 = -lagrange_n_0;
+        let expected = naive_lagrange_basis_at(&domain, &ScalarField::zero());
+        assert_eq!(prepare_combine_simple::<E>(&domain), expected);
+        assert_eq!(
+            lagrange_basis_at::<E>(&domain, &ScalarField::zero()),
+            expected
+        );
+        assert_eq!(
+            lagrange_basis_at_subproduct::<E>(&domain, &ScalarField::zero()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_prepare_combine_simple_matches_reference() {
+        let fft_domain =
+            GeneralEvaluationDomain::<ScalarField>::new(30).unwrap();
+        let elements = fft_domain.elements().collect::<Vec<_>>();
+        for selected in [
+            elements.clone(),
+            elements[..20].to_vec(),
+            elements.iter().skip(1).step_by(3).copied().collect(),
+            vec![elements[5]],
+        ] {
+            assert_eq!(
+                prepare_combine_simple::<E>(&selected),
+                lagrange_basis_at::<E>(&selected, &ScalarField::zero())
+            );
+        }
+
+        // Arbitrary points fall back to the reference implementation
+        let shares_x = random_points(7);
+        assert!(enclosing_fft_domain(&shares_x).is_none());
+        assert_eq!(
+            prepare_combine_simple::<E>(&shares_x),
+            lagrange_basis_at::<E>(&shares_x, &ScalarField::zero())
+        );
+    }
+
+    #[test]
+    fn test_lagrange_coeff_at_zero() {
+        let fft_domain =
+            GeneralEvaluationDomain::<ScalarField>::new(8).unwrap();
+        let elements = fft_domain.elements().collect::<Vec<_>>();
+        let selected = [6u32, 1, 3, 4];
+        let domain_points = selected
+            .iter()
+            .map(|i| (*i, elements[*i as usize]))
+            .collect::<HashMap<_, _>>();
+
+        // Coefficients follow the order of the share indices
+        let sorted_points = [1, 3, 4, 6].map(|i| elements[i]);
+        let expected =
+            naive_lagrange_basis_at(&sorted_points, &ScalarField::zero());
+        for (share_index, coeff) in [1u32, 3, 4, 6].into_iter().zip(expected) {
+            assert_eq!(
+                lagrange_coeff_at_zero::<E>(share_index, &domain_points),
+                Some(coeff)
+            );
+        }
+        assert_eq!(lagrange_coeff_at_zero::<E>(2, &domain_points), None);
+    }
+
+    #[test]
+    fn test_enclosing_fft_domain() {
+        let fft_domain =
+            GeneralEvaluationDomain::<ScalarField>::new(64).unwrap();
+        let elements = fft_domain.elements().collect::<Vec<_>>();
+        // Even powers of the generator are in the domain of size 32
+        let even = elements.iter().step_by(2).copied().collect::<Vec<_>>();
+        assert_eq!(enclosing_fft_domain(&even).unwrap().size(), 32);
+        assert_eq!(enclosing_fft_domain(&elements).unwrap().size(), 64);
+        assert!(enclosing_fft_domain(&[ScalarField::zero()]).is_none());
+    }
+
+    #[test]
+    fn test_lagrange_arbitrary_points() {
+        let rng = &mut test_rng();
+        for n in [1, 2, 3, 7, 16, 33] {
+            let shares_x = random_points(n);
+            let x_i = ScalarField::rand(rng);
+            let expected = naive_lagrange_basis_at(&shares_x, &x_i);
+            assert_eq!(lagrange_basis_at::<E>(&shares_x, &x_i), expected);
+            assert_eq!(
+                lagrange_basis_at_subproduct::<E>(&shares_x, &x_i),
+                expected
+            );
+            // Coefficients interpolate the constant polynomial
+            assert_eq!(
+                expected.iter().sum::<ScalarField>(),
+                ScalarField::one()
+            );
+        }
+    }
+
+    #[test]
+    fn test_lagrange_at_share_point() {
+        let shares_x = random_points(5);
+        let expected = naive_lagrange_basis_at(&shares_x, &shares_x[2]);
+        assert_eq!(lagrange_basis_at::<E>(&shares_x, &shares_x[2]), expected);
+        assert_eq!(
+            lagrange_basis_at_subproduct::<E>(&shares_x, &shares_x[2]),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_lagrange_fft_domain() {
+        let rng = &mut test_rng();
+        let fft_domain =
+            GeneralEvaluationDomain::<ScalarField>::new(16).unwrap();
+        let elements = fft_domain.elements().collect::<Vec<_>>();
+        let x_i = ScalarField::rand(rng);
+
+        // Whole domain and subsets of it
+        for selected in [
+            elements.clone(),
+            elements.iter().step_by(2).copied().collect::<Vec<_>>(),
+            elements[3..14].to_vec(),
+        ] {
+            for x in [ScalarField::zero(), x_i, selected[0]] {
+                assert_eq!(
+                    lagrange_basis_at_fft_domain::<E>(
+                        &fft_domain,
+                        &selected,
+                        &x
+                    )
+                    .unwrap(),
+                    naive_lagrange_basis_at(&selected, &x)
+                );
+            }
         }
-        let s = subproductdomain::SubproductDomain::<ScalarField>::new(domain);
-        let mut lagrange = s.inverse_lagrange_coefficients();
-        ark_ff::batch_inversion_and_mul(&mut lagrange, &lagrange_n_0);
+
+        // At zero, every coefficient over the whole domain is 1/N
+        let size_inv = fft_domain.size_as_field_element().inverse().unwrap();
+        assert!(lagrange_basis_at_fft_domain::<E>(
+            &fft_domain,
+            &elements,
+            &ScalarField::zero()
+        )
+        .unwrap()
+        .iter()
+        .all(|c| *c == size_inv));
+
+        // Points outside of the domain are rejected
+        let mut outside = elements[..4].to_vec();
+        outside.push(x_i);
+        assert!(lagrange_basis_at_fft_domain::<E>(
+            &fft_domain,
+            &outside,
+            &ScalarField::zero()
+        )
+        .is_none());
     }
 }
@@ -2,8 +2,8 @@ use ark_ec::pairing::Pairing;
 
 use crate::{
     prepare_combine_simple, BlindedKeyShare, CiphertextHeader,
-    DecryptionSharePrecomputed, DecryptionShareSimple, PrivateKeyShare, Result,
-    ShareCommitment,
+    DecryptionSharePrecomputed, DecryptionShareSimple, HashToG2,
+    PrivateKeyShare, Result, ShareCommitment,
 };
 
 #[derive(Clone, Debug)]
@@ -33,7 +33,7 @@ pub struct PrivateDecryptionContextSimple<E: Pairing> {
     pub public_decryption_contexts: Vec<PublicDecryptionContextSimple<E>>,
 }
 
-impl<E: Pairing> PrivateDecryptionContextSimple<E> {
+impl<E: HashToG2> PrivateDecryptionContextSimple<E> {
     pub fn create_share(
         &self,
         ciphertext_header: &CiphertextHeader<E>,
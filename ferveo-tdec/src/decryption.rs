@@ -8,8 +8,9 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
 
 use crate::{
-    Ciphertext, CiphertextHeader, PrivateKeyShare,
-    PublicDecryptionContextSimple, Result,
+    Ciphertext, CiphertextHeader, DecryptionShareProof, Error, HashToG2,
+    PrivateKeyShare, PublicDecryptionContextSimple, Result, ShareCommitment,
+    VerifiedCiphertextHeader,
 };
 
 #[serde_as]
@@ -30,7 +31,7 @@ impl<E: Pairing> ValidatorShareChecksum<E> {
             .mul(
                 validator_decryption_key
                     .inverse()
-                    .expect("Inverse of this key doesn't exist"),
+                    .ok_or(Error::InvalidValidatorDecryptionKey)?,
             )
             .into_affine();
         Ok(Self { checksum })
@@ -76,7 +77,7 @@ pub struct DecryptionShareSimple<E: Pairing> {
     pub validator_checksum: ValidatorShareChecksum<E>,
 }
 
-impl<E: Pairing> DecryptionShareSimple<E> {
+impl<E: HashToG2> DecryptionShareSimple<E> {
     /// Create a decryption share from the given parameters.
     /// This function checks that the ciphertext is valid.
     pub fn create(
@@ -93,6 +94,19 @@ impl<E: Pairing> DecryptionShareSimple<E> {
         )
     }
 
+    /// Create a decryption share for an already checked ciphertext header.
+    pub fn create_verified(
+        validator_decryption_key: &E::ScalarField,
+        private_key_share: &PrivateKeyShare<E>,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+    ) -> Result<Self> {
+        Self::create_unchecked(
+            validator_decryption_key,
+            private_key_share,
+            ciphertext_header.header(),
+        )
+    }
+
     /// Create a decryption share from the given parameters.
     /// This function does not check that the ciphertext is valid.
     pub fn create_unchecked(
@@ -128,6 +142,40 @@ impl<E: Pairing> DecryptionShareSimple<E> {
             ciphertext,
         )
     }
+
+    /// Create a proof that this decryption share was computed with the
+    /// private key share committed to by `share_commitment`.
+    pub fn create_proof<R: rand::RngCore>(
+        &self,
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        rng: &mut R,
+    ) -> Result<DecryptionShareProof<E>> {
+        DecryptionShareProof::create(
+            &ciphertext_header.ciphertext_hash,
+            &ciphertext_header.commitment,
+            private_key_share,
+            share_commitment,
+            rng,
+        )
+    }
+
+    /// Verify that the decryption share is valid using a proof instead of
+    /// the validator checksum.
+    pub fn verify_proof(
+        &self,
+        proof: &DecryptionShareProof<E>,
+        share_commitment: &ShareCommitment<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+    ) -> Result<bool> {
+        proof.verify(
+            &ciphertext_header.ciphertext_hash,
+            &ciphertext_header.commitment,
+            share_commitment,
+            &self.decryption_share,
+        )
+    }
 }
 
 /// A decryption share for a precomputed variant of the threshold decryption scheme.
@@ -148,7 +196,7 @@ pub struct DecryptionSharePrecomputed<E: Pairing> {
     pub validator_checksum: ValidatorShareChecksum<E>,
 }
 
-impl<E: Pairing> DecryptionSharePrecomputed<E> {
+impl<E: HashToG2> DecryptionSharePrecomputed<E> {
     /// Create a decryption share from the given parameters.
     /// This function checks that the ciphertext is valid.
     pub fn create(
@@ -169,6 +217,23 @@ impl<E: Pairing> DecryptionSharePrecomputed<E> {
         )
     }
 
+    /// Create a decryption share for an already checked ciphertext header.
+    pub fn create_verified(
+        validator_index: usize,
+        validator_decryption_key: &E::ScalarField,
+        private_key_share: &PrivateKeyShare<E>,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+        lagrange_coeff: &E::ScalarField,
+    ) -> Result<Self> {
+        Self::create_unchecked(
+            validator_index,
+            validator_decryption_key,
+            private_key_share,
+            ciphertext_header.header(),
+            lagrange_coeff,
+        )
+    }
+
     /// Create a decryption share from the given parameters.
     /// This function does not check that the ciphertext is valid.
     pub fn create_unchecked(
@@ -211,9 +276,51 @@ impl<E: Pairing> DecryptionSharePrecomputed<E> {
             ciphertext,
         )
     }
+
+    /// Create a proof that this decryption share was computed with the
+    /// private key share committed to by `share_commitment`.
+    pub fn create_proof<R: rand::RngCore>(
+        &self,
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        lagrange_coeff: &E::ScalarField,
+        rng: &mut R,
+    ) -> Result<DecryptionShareProof<E>> {
+        DecryptionShareProof::create(
+            &ciphertext_header.ciphertext_hash,
+            &ciphertext_header
+                .commitment
+                .mul(lagrange_coeff)
+                .into_affine(),
+            private_key_share,
+            share_commitment,
+            rng,
+        )
+    }
+
+    /// Verify that the decryption share is valid using a proof instead of
+    /// the validator checksum.
+    pub fn verify_proof(
+        &self,
+        proof: &DecryptionShareProof<E>,
+        share_commitment: &ShareCommitment<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        lagrange_coeff: &E::ScalarField,
+    ) -> Result<bool> {
+        proof.verify(
+            &ciphertext_header.ciphertext_hash,
+            &ciphertext_header
+                .commitment
+                .mul(lagrange_coeff)
+                .into_affine(),
+            share_commitment,
+            &self.decryption_share,
+        )
+    }
 }
 
-pub fn verify_decryption_shares_simple<E: Pairing>(
+pub fn verify_decryption_shares_simple<E: HashToG2>(
     pub_contexts: &Vec<PublicDecryptionContextSimple<E>>,
     ciphertext: &Ciphertext<E>,
     decryption_shares: &Vec<DecryptionShareSimple<E>>,
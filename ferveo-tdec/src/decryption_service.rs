@@ -0,0 +1,422 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Mul,
+    sync::Mutex,
+};
+
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::Field;
+use ferveo_common::Keypair;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    lagrange_coeff_at_zero, BlindedKeyShare, CiphertextHeader,
+    DecryptionSharePrecomputed, DecryptionShareSimple, DomainPoint, Error,
+    HashToG2, PrivateKeyShare, Result, ValidatorShareChecksum,
+    VerifiedCiphertextHeader,
+};
+
+/// Default number of selected-validator sets for which we keep the lagrange coefficient
+pub const DEFAULT_LAGRANGE_CACHE_CAPACITY: usize = 32;
+
+/// Long-lived, validator-side decryption context for a single ritual.
+///
+/// Unblinding the key share and preparing it for pairings is done once, on
+/// construction, instead of on every call to `create_decryption_share_*`.
+/// Lagrange coefficients for the precomputed variant are cached per
+/// selected-validator set.
+pub struct DecryptionService<E: Pairing> {
+    share_index: u32,
+    domain_points: HashMap<u32, DomainPoint<E>>,
+    // Z_i, unblinded once and zeroized on drop
+    private_key_share: PrivateKeyShare<E>,
+    // Note that arkworks doesn't let us zeroize the prepared form of Z_i
+    prepared_key_share: E::G2Prepared,
+    // dk_i^{-1}, used to create validator checksums
+    decryption_key_inverse: DecryptionKeyInverse<E>,
+    lagrange_cache: Mutex<LagrangeCache<E>>,
+}
+
+impl<E: HashToG2> DecryptionService<E> {
+    /// Creates a decryption context for the validator with the given share index.
+    /// `domain_points` maps share indices of the ritual to their domain points.
+    pub fn new(
+        share_index: u32,
+        blinded_key_share: &BlindedKeyShare<E>,
+        validator_keypair: &Keypair<E>,
+        domain_points: HashMap<u32, DomainPoint<E>>,
+        lagrange_cache_capacity: usize,
+    ) -> Result<Self> {
+        if !domain_points.contains_key(&share_index) {
+            return Err(Error::InvalidShareIndex(share_index));
+        }
+        let decryption_key_inverse = DecryptionKeyInverse::<E>(
+            validator_keypair
+                .decryption_key
+                .inverse()
+                .ok_or(Error::InvalidValidatorDecryptionKey)?,
+        );
+        let private_key_share = PrivateKeyShare(
+            blinded_key_share
+                .blinded_key_share
+                .mul(decryption_key_inverse.0)
+                .into_affine(),
+        );
+        let prepared_key_share = E::G2Prepared::from(private_key_share.0);
+        Ok(Self {
+            share_index,
+            domain_points,
+            private_key_share,
+            prepared_key_share,
+            decryption_key_inverse,
+            lagrange_cache: Mutex::new(LagrangeCache::new(
+                lagrange_cache_capacity,
+            )),
+        })
+    }
+
+    pub fn share_index(&self) -> u32 {
+        self.share_index
+    }
+
+    pub fn private_key_share(&self) -> &PrivateKeyShare<E> {
+        &self.private_key_share
+    }
+
+    /// Make a decryption share (simple variant) for a given ciphertext
+    pub fn create_decryption_share_simple(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+    ) -> Result<DecryptionShareSimple<E>> {
+        ciphertext_header.check(aad)?;
+        Ok(self.decryption_share_simple(ciphertext_header))
+    }
+
+    /// Make a decryption share (simple variant) for an already checked
+    /// ciphertext header
+    pub fn create_decryption_share_simple_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+    ) -> DecryptionShareSimple<E> {
+        self.decryption_share_simple(ciphertext_header.header())
+    }
+
+    /// Make a decryption share (precomputed variant) for a given ciphertext.
+    /// `selected_share_indices` must contain the share index of this validator.
+    pub fn create_decryption_share_precomputed(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        selected_share_indices: &[u32],
+    ) -> Result<DecryptionSharePrecomputed<E>> {
+        let lagrange_coeff = self.lagrange_coeff(selected_share_indices)?;
+        ciphertext_header.check(aad)?;
+        Ok(
+            self.decryption_share_precomputed(
+                ciphertext_header,
+                lagrange_coeff,
+            ),
+        )
+    }
+
+    /// Make a decryption share (precomputed variant) for an already checked
+    /// ciphertext header
+    pub fn create_decryption_share_precomputed_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+        selected_share_indices: &[u32],
+    ) -> Result<DecryptionSharePrecomputed<E>> {
+        let lagrange_coeff = self.lagrange_coeff(selected_share_indices)?;
+        Ok(self.decryption_share_precomputed(
+            ciphertext_header.header(),
+            lagrange_coeff,
+        ))
+    }
+
+    /// Lagrange coefficient at 0 of this validator's domain point, over the
+    /// domain points of the selected validators
+    pub fn lagrange_coeff(
+        &self,
+        selected_share_indices: &[u32],
+    ) -> Result<E::ScalarField> {
+        let mut selected = selected_share_indices.to_vec();
+        selected.sort_unstable();
+        selected.dedup();
+        if !selected.contains(&self.share_index) {
+            return Err(Error::InvalidShareIndex(self.share_index));
+        }
+
+        let mut cache = self
+            .lagrange_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(lagrange_coeff) = cache.get(&selected) {
+            return Ok(lagrange_coeff);
+        }
+
+        let selected_domain_points = selected
+            .iter()
+            .map(|share_index| {
+                self.domain_points
+                    .get(share_index)
+                    .map(|domain_point| (*share_index, *domain_point))
+                    .ok_or(Error::InvalidShareIndex(*share_index))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        let lagrange_coeff = lagrange_coeff_at_zero::<E>(
+            self.share_index,
+            &selected_domain_points,
+        )
+        .ok_or(Error::InvalidShareIndex(self.share_index))?;
+        cache.insert(selected, lagrange_coeff);
+        Ok(lagrange_coeff)
+    }
+
+    fn decryption_share_simple(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+    ) -> DecryptionShareSimple<E> {
+        // D_i = e(U, Z_i)
+        let decryption_share =
+            self.pair_with_key_share(ciphertext_header.commitment);
+        DecryptionShareSimple {
+            decryption_share,
+            validator_checksum: self.validator_checksum(ciphertext_header),
+        }
+    }
+
+    fn decryption_share_precomputed(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        lagrange_coeff: E::ScalarField,
+    ) -> DecryptionSharePrecomputed<E> {
+        // C_{λ_i} = e([λ_{i}(0)] U, Z_i)
+        let decryption_share = self.pair_with_key_share(
+            ciphertext_header
+                .commitment
+                .mul(lagrange_coeff)
+                .into_affine(),
+        );
+        DecryptionSharePrecomputed {
+            decrypter_index: self.share_index as usize,
+            decryption_share,
+            validator_checksum: self.validator_checksum(ciphertext_header),
+        }
+    }
+
+    fn pair_with_key_share(&self, g1: E::G1Affine) -> E::TargetField {
+        E::multi_pairing([g1], [self.prepared_key_share.clone()]).0
+    }
+
+    fn validator_checksum(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+    ) -> ValidatorShareChecksum<E> {
+        // C_i = dk_i^{-1} * U
+        ValidatorShareChecksum {
+            checksum: ciphertext_header
+                .commitment
+                .mul(self.decryption_key_inverse.0)
+                .into_affine(),
+        }
+    }
+}
+
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct DecryptionKeyInverse<E: Pairing>(E::ScalarField);
+
+/// A least-recently-used cache of lagrange coefficients, keyed by the sorted
+/// share indices of the selected validators
+struct LagrangeCache<E: Pairing> {
+    capacity: usize,
+    entries: HashMap<Vec<u32>, E::ScalarField>,
+    // Most recently used at the back
+    recency: VecDeque<Vec<u32>>,
+}
+
+impl<E: Pairing> LagrangeCache<E> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            recency: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn get(&mut self, key: &[u32]) -> Option<E::ScalarField> {
+        let value = *self.entries.get(key)?;
+        self.touch(key);
+        Some(value)
+    }
+
+    fn insert(&mut self, key: Vec<u32>, value: E::ScalarField) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key.clone(), value).is_some() {
+            self.touch(&key);
+            return;
+        }
+        self.recency.push_back(key);
+        if self.recency.len() > self.capacity {
+            if let Some(evicted) = self.recency.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    fn touch(&mut self, key: &[u32]) {
+        if let Some(position) = self.recency.iter().position(|k| k == key) {
+            if let Some(key) = self.recency.remove(position) {
+                self.recency.push_back(key);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ark_ec::pairing::Pairing;
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::{
+        decryption_service::LagrangeCache, prepare_combine_simple,
+        test_common::setup_simple, DecryptionService,
+        DecryptionSharePrecomputed, DecryptionShareSimple, Error, SecretBox,
+        DEFAULT_LAGRANGE_CACHE_CAPACITY,
+    };
+
+    type E = ark_bls12_381::Bls12_381;
+    type ScalarField = <E as Pairing>::ScalarField;
+
+    #[test]
+    fn test_decryption_service_matches_direct_share_creation() {
+        let rng = &mut test_rng();
+        let shares_num = 8;
+        let threshold = 5;
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let ciphertext =
+            crate::encrypt::<E>(SecretBox::new(msg), aad, &pubkey, rng)
+                .unwrap();
+        let header = ciphertext.header().unwrap();
+        let verified_header = header.verify(aad).unwrap();
+
+        let domain_points = contexts[0]
+            .public_decryption_contexts
+            .iter()
+            .enumerate()
+            .map(|(share_index, ctxt)| (share_index as u32, ctxt.domain))
+            .collect::<HashMap<_, _>>();
+        let selected = (0..threshold as u32).collect::<Vec<_>>();
+        let selected_domain_points = selected
+            .iter()
+            .map(|i| domain_points[i])
+            .collect::<Vec<_>>();
+        let lagrange_coeffs =
+            prepare_combine_simple::<E>(&selected_domain_points);
+
+        for context in &contexts[..threshold] {
+            let blinded_key_share = &context.public_decryption_contexts
+                [context.index]
+                .blinded_key_share;
+            let keypair = ferveo_common::Keypair::<E> {
+                decryption_key: context.setup_params.b,
+            };
+            let service = DecryptionService::new(
+                context.index as u32,
+                blinded_key_share,
+                &keypair,
+                domain_points.clone(),
+                DEFAULT_LAGRANGE_CACHE_CAPACITY,
+            )
+            .unwrap();
+            assert_eq!(service.private_key_share(), &context.private_key_share);
+
+            let simple = service
+                .create_decryption_share_simple(&header, aad)
+                .unwrap();
+            assert_eq!(
+                simple,
+                DecryptionShareSimple::create(
+                    &context.setup_params.b,
+                    &context.private_key_share,
+                    &header,
+                    aad,
+                )
+                .unwrap()
+            );
+
+            let precomputed = service
+                .create_decryption_share_precomputed(&header, aad, &selected)
+                .unwrap();
+            assert_eq!(
+                precomputed,
+                DecryptionSharePrecomputed::create(
+                    context.index,
+                    &context.setup_params.b,
+                    &context.private_key_share,
+                    &header,
+                    aad,
+                    &lagrange_coeffs[context.index],
+                )
+                .unwrap()
+            );
+
+            // Shares for a verified header are the same, without checking it again
+            assert_eq!(
+                service
+                    .create_decryption_share_simple_verified(&verified_header),
+                simple
+            );
+            assert_eq!(
+                service
+                    .create_decryption_share_precomputed_verified(
+                        &verified_header,
+                        &selected
+                    )
+                    .unwrap(),
+                precomputed
+            );
+
+            // A set that doesn't include this validator is rejected
+            let others =
+                (threshold as u32..shares_num as u32).collect::<Vec<_>>();
+            assert!(matches!(
+                service
+                    .create_decryption_share_precomputed(&header, aad, &others),
+                Err(Error::InvalidShareIndex(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_lagrange_cache_evicts_least_recently_used() {
+        let rng = &mut test_rng();
+        let mut cache = LagrangeCache::<E>::new(2);
+        let (a, b, c) = (vec![0, 1], vec![0, 2], vec![0, 3]);
+        let value = ScalarField::rand(rng);
+
+        cache.insert(a.clone(), value);
+        cache.insert(b.clone(), value);
+        // Touch `a`, so that `b` is now the least recently used
+        assert_eq!(cache.get(&a), Some(value));
+        cache.insert(c.clone(), value);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&a), Some(value));
+        assert_eq!(cache.get(&b), None);
+        assert_eq!(cache.get(&c), Some(value));
+    }
+}
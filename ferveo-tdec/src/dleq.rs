@@ -0,0 +1,326 @@
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{Field, One, PrimeField, UniformRand};
+use ark_serialize::CanonicalSerialize;
+use ferveo_common::serialization;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{digest::Digest, Sha256};
+
+use crate::{PrivateKeyShare, Result, ShareCommitment};
+
+const DECRYPTION_SHARE_PROOF_DST: &[u8] = b"FERVEO_DECRYPTION_SHARE_PROOF_V1";
+
+/// Chaum-Pedersen proof that a decryption share `D_i = e(V, Z_i)` was computed
+/// with the key share `Z_i` committed to by `A_i`, i.e. `e(G, Z_i) = e(A_i, H)`.
+///
+/// The witness `Z_i` lives in G2, so the proof is for the equality of
+/// "discrete logarithms" of `D_i` and `e(A_i, H)` with respect to the bases
+/// `e(V, .)` and `e(G, .)`. It can be verified with the public share commitment
+/// alone, without the blinded key share or the validator public key.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShareProof<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub challenge: E::ScalarField,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub response: E::G2Affine,
+}
+
+impl<E: Pairing> DecryptionShareProof<E> {
+    /// Proves that `e(share_base, Z_i)` was computed with the key share `Z_i`.
+    /// `context` identifies the ciphertext, and must be reproduced by the verifier.
+    pub(crate) fn create<R: rand::RngCore>(
+        context: &[u8],
+        share_base: &E::G1Affine,
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let g = E::G1Affine::generator();
+        let h = E::G2Affine::generator();
+
+        let decryption_share = E::pairing(*share_base, private_key_share.0);
+        let key_share = E::pairing(share_commitment.0, h);
+
+        let blinding = (h * E::ScalarField::rand(rng)).into_affine();
+        let challenge = challenge(
+            context,
+            share_base,
+            share_commitment,
+            &[
+                decryption_share,
+                key_share,
+                E::pairing(*share_base, blinding),
+                E::pairing(g, blinding),
+            ],
+        )?;
+        let response =
+            (blinding + private_key_share.0 * challenge).into_affine();
+        Ok(Self {
+            challenge,
+            response,
+        })
+    }
+
+    /// Verifies that `decryption_share = e(share_base, Z_i)` for the key share
+    /// `Z_i` committed to by `share_commitment`.
+    pub(crate) fn verify(
+        &self,
+        context: &[u8],
+        share_base: &E::G1Affine,
+        share_commitment: &ShareCommitment<E>,
+        decryption_share: &E::TargetField,
+    ) -> Result<bool> {
+        // The proof says nothing about a component of the share outside of
+        // GT, so such shares (and zero) are rejected up front
+        if !is_in_target_subgroup::<E>(decryption_share) {
+            return Ok(false);
+        }
+        let g = E::G1Affine::generator();
+        let h = E::G2Affine::generator();
+
+        let decryption_share = PairingOutput(*decryption_share);
+        let key_share = E::pairing(share_commitment.0, h);
+
+        // Recover the prover's commitments as e(., s) - c * statement
+        let share_blinding = E::pairing(*share_base, self.response)
+            - decryption_share * self.challenge;
+        let key_share_blinding =
+            E::pairing(g, self.response) - key_share * self.challenge;
+
+        let expected_challenge = challenge(
+            context,
+            share_base,
+            share_commitment,
+            &[
+                decryption_share,
+                key_share,
+                share_blinding,
+                key_share_blinding,
+            ],
+        )?;
+        Ok(expected_challenge == self.challenge)
+    }
+}
+
+/// Whether `element` is in the subgroup of order `r` of the target field,
+/// where pairing outputs live. This rules out zero, which is not invertible.
+pub(crate) fn is_in_target_subgroup<E: Pairing>(
+    element: &E::TargetField,
+) -> bool {
+    element.pow(E::ScalarField::MODULUS) == E::TargetField::one()
+}
+
+fn challenge<E: Pairing>(
+    context: &[u8],
+    share_base: &E::G1Affine,
+    share_commitment: &ShareCommitment<E>,
+    elements: &[PairingOutput<E>],
+) -> Result<E::ScalarField> {
+    let mut hash_input = DECRYPTION_SHARE_PROOF_DST.to_vec();
+    hash_input.extend_from_slice(context);
+    share_base.serialize_compressed(&mut hash_input)?;
+    share_commitment.0.serialize_compressed(&mut hash_input)?;
+    for element in elements {
+        element.serialize_compressed(&mut hash_input)?;
+    }
+    Ok(E::ScalarField::from_be_bytes_mod_order(&Sha256::digest(
+        &hash_input,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::{Field, UniformRand, Zero};
+    use ark_std::test_rng;
+
+    use crate::{
+        encrypt, prepare_combine_simple, test_common::*,
+        DecryptionSharePrecomputed, DecryptionShareSimple, SecretBox,
+    };
+
+    use super::*;
+
+    type E = ark_bls12_381::Bls12_381;
+
+    #[test]
+    fn test_decryption_share_proofs() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (8, 5);
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let ciphertext = encrypt::<E>(
+            SecretBox::new("my-msg".as_bytes().to_vec()),
+            aad,
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let header = ciphertext.header().unwrap();
+
+        let public_contexts = &contexts[0].public_decryption_contexts;
+        let domain_points = public_contexts[..threshold]
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let lagrange_coeffs = prepare_combine_simple::<E>(&domain_points);
+
+        for (context, lagrange_coeff) in
+            contexts[..threshold].iter().zip(&lagrange_coeffs)
+        {
+            let share_commitment =
+                &public_contexts[context.index].share_commitment;
+            let other_commitment = &public_contexts
+                [(context.index + 1) % shares_num]
+                .share_commitment;
+
+            let share = DecryptionShareSimple::create(
+                &context.setup_params.b,
+                &context.private_key_share,
+                &header,
+                aad,
+            )
+            .unwrap();
+            let proof = share
+                .create_proof(
+                    &context.private_key_share,
+                    share_commitment,
+                    &header,
+                    rng,
+                )
+                .unwrap();
+            assert!(share
+                .verify_proof(&proof, share_commitment, &header)
+                .unwrap());
+            assert!(!share
+                .verify_proof(&proof, other_commitment, &header)
+                .unwrap());
+
+            let share = DecryptionSharePrecomputed::create(
+                context.index,
+                &context.setup_params.b,
+                &context.private_key_share,
+                &header,
+                aad,
+                lagrange_coeff,
+            )
+            .unwrap();
+            let proof = share
+                .create_proof(
+                    &context.private_key_share,
+                    share_commitment,
+                    &header,
+                    lagrange_coeff,
+                    rng,
+                )
+                .unwrap();
+            assert!(share
+                .verify_proof(&proof, share_commitment, &header, lagrange_coeff)
+                .unwrap());
+            // The proof binds the Lagrange coefficient applied to the share
+            assert!(!share
+                .verify_proof(
+                    &proof,
+                    share_commitment,
+                    &header,
+                    &lagrange_coeff.double()
+                )
+                .unwrap());
+        }
+
+        // A proof doesn't transfer to another share
+        let (first, second) = (&contexts[0], &contexts[1]);
+        let share = DecryptionShareSimple::create(
+            &first.setup_params.b,
+            &first.private_key_share,
+            &header,
+            aad,
+        )
+        .unwrap();
+        let proof = DecryptionShareSimple::create(
+            &second.setup_params.b,
+            &second.private_key_share,
+            &header,
+            aad,
+        )
+        .unwrap()
+        .create_proof(
+            &second.private_key_share,
+            &public_contexts[second.index].share_commitment,
+            &header,
+            rng,
+        )
+        .unwrap();
+        assert!(!share
+            .verify_proof(
+                &proof,
+                &public_contexts[second.index].share_commitment,
+                &header
+            )
+            .unwrap());
+
+        // A malformed share is rejected rather than panicking the verifier
+        let mut malformed = share.clone();
+        malformed.decryption_share = Zero::zero();
+        assert!(!malformed
+            .verify_proof(
+                &proof,
+                &public_contexts[second.index].share_commitment,
+                &header
+            )
+            .unwrap());
+    }
+
+    #[test]
+    fn test_decryption_share_outside_target_subgroup() {
+        let rng = &mut test_rng();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let (pubkey, _, contexts) = setup_simple::<E>(4, 3, rng);
+        let ciphertext = encrypt::<E>(
+            SecretBox::new("my-msg".as_bytes().to_vec()),
+            aad,
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let header = ciphertext.header().unwrap();
+        let context = &contexts[0];
+        let share_commitment =
+            &context.public_decryption_contexts[0].share_commitment;
+
+        let share = DecryptionShareSimple::create(
+            &context.setup_params.b,
+            &context.private_key_share,
+            &header,
+            aad,
+        )
+        .unwrap();
+        let proof = share
+            .create_proof(
+                &context.private_key_share,
+                share_commitment,
+                &header,
+                rng,
+            )
+            .unwrap();
+        assert!(is_in_target_subgroup::<E>(&share.decryption_share));
+
+        // -1 has order 2, so it moves the share out of the subgroup of order r
+        let mut malformed = share.clone();
+        malformed.decryption_share = -share.decryption_share;
+        assert!(!is_in_target_subgroup::<E>(&malformed.decryption_share));
+        assert!(!malformed
+            .verify_proof(&proof, share_commitment, &header)
+            .unwrap());
+
+        // Neither a random field element nor zero is a pairing output
+        let random = <E as Pairing>::TargetField::rand(rng);
+        assert!(!is_in_target_subgroup::<E>(&random));
+        assert!(!is_in_target_subgroup::<E>(&Zero::zero()));
+    }
+}
@@ -0,0 +1,468 @@
+use std::{collections::HashMap, ops::Add};
+
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use ark_serialize::CanonicalSerialize;
+use ferveo_common::serialization;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{digest::Digest, Sha256};
+
+use crate::{
+    DecryptionShareProof, DkgPublicKey, Error, PrivateKeyShare, Result,
+    ShareCommitment,
+};
+
+const ELGAMAL_CONTEXT_DST: &[u8] = b"FERVEO_ELGAMAL_V1";
+const ELGAMAL_BALLOT_PROOF_DST: &[u8] = b"FERVEO_ELGAMAL_BALLOT_PROOF_V1";
+
+/// Lifted ElGamal ciphertext `(R, M) = ([r] G, [m] G + [r] Y)` of a small
+/// integer `m` under the DKG public key `Y`.
+///
+/// Ciphertexts are additively homomorphic: adding them adds the plaintexts.
+/// Since validators hold their key shares in G2, the plaintext is recovered in
+/// the target group as `e(M, H) - e(R, Z) = m * e(G, H)`.
+#[serde_as]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalCiphertext<E: Pairing> {
+    // R
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub randomness_commitment: E::G1Affine,
+    // M
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub masked_message: E::G1Affine,
+}
+
+impl<E: Pairing> ElGamalCiphertext<E> {
+    fn encrypt_with_randomness(
+        value: u64,
+        pubkey: &DkgPublicKey<E>,
+        randomness: &E::ScalarField,
+    ) -> Self {
+        let g = E::G1Affine::generator();
+        Self {
+            randomness_commitment: (g * randomness).into_affine(),
+            masked_message: (g * E::ScalarField::from(value)
+                + pubkey.0 * randomness)
+                .into_affine(),
+        }
+    }
+
+    /// Encryption of zero with no randomness, the neutral element for `add`
+    pub fn zero() -> Self {
+        Self {
+            randomness_commitment: E::G1Affine::zero(),
+            masked_message: E::G1Affine::zero(),
+        }
+    }
+
+    /// Domain-separated encoding of the ciphertext, bound to share proofs
+    fn proof_context(&self) -> Result<Vec<u8>> {
+        let mut context = ELGAMAL_CONTEXT_DST.to_vec();
+        self.randomness_commitment
+            .serialize_compressed(&mut context)?;
+        self.masked_message.serialize_compressed(&mut context)?;
+        Ok(context)
+    }
+}
+
+impl<E: Pairing> Add for ElGamalCiphertext<E> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            randomness_commitment: (self.randomness_commitment
+                + other.randomness_commitment)
+                .into_affine(),
+            masked_message: (self.masked_message + other.masked_message)
+                .into_affine(),
+        }
+    }
+}
+
+impl<E: Pairing> std::iter::Sum for ElGamalCiphertext<E> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+/// An `ElGamalCiphertext` of one input to a tally, with a Schnorr proof of
+/// knowledge of its randomness `r` bound to the tally `context`.
+///
+/// Validators only decrypt sums of ballots they verified themselves. Otherwise,
+/// anyone could have them compute `e(U, Z)` for the commitment `U` of a
+/// threshold encryption ciphertext, which is its shared secret.
+#[serde_as]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalBallot<E: Pairing> {
+    #[serde(bound(
+        serialize = "ElGamalCiphertext<E>: Serialize",
+        deserialize = "ElGamalCiphertext<E>: DeserializeOwned"
+    ))]
+    pub ciphertext: ElGamalCiphertext<E>,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub challenge: E::ScalarField,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub response: E::ScalarField,
+}
+
+impl<E: Pairing> ElGamalBallot<E> {
+    pub fn encrypt(
+        value: u64,
+        pubkey: &DkgPublicKey<E>,
+        context: &[u8],
+        rng: &mut impl rand::Rng,
+    ) -> Result<Self> {
+        let randomness = E::ScalarField::rand(rng);
+        let ciphertext = ElGamalCiphertext::encrypt_with_randomness(
+            value,
+            pubkey,
+            &randomness,
+        );
+
+        let blinding = E::ScalarField::rand(rng);
+        let challenge = ballot_challenge(
+            context,
+            &ciphertext,
+            &(E::G1Affine::generator() * blinding).into_affine(),
+        )?;
+        Ok(Self {
+            ciphertext,
+            challenge,
+            response: blinding + randomness * challenge,
+        })
+    }
+
+    /// Verify the proof of knowledge of the randomness for `context`
+    pub fn verify(&self, context: &[u8]) -> Result<bool> {
+        // Recover the prover's commitment as [s] G - [c] R
+        let blinding = (E::G1Affine::generator() * self.response
+            - self.ciphertext.randomness_commitment * self.challenge)
+            .into_affine();
+        let expected_challenge =
+            ballot_challenge(context, &self.ciphertext, &blinding)?;
+        Ok(expected_challenge == self.challenge)
+    }
+}
+
+fn ballot_challenge<E: Pairing>(
+    context: &[u8],
+    ciphertext: &ElGamalCiphertext<E>,
+    blinding: &E::G1Affine,
+) -> Result<E::ScalarField> {
+    let mut hash_input = ELGAMAL_BALLOT_PROOF_DST.to_vec();
+    hash_input.extend_from_slice(context);
+    ciphertext
+        .randomness_commitment
+        .serialize_compressed(&mut hash_input)?;
+    ciphertext
+        .masked_message
+        .serialize_compressed(&mut hash_input)?;
+    blinding.serialize_compressed(&mut hash_input)?;
+    Ok(E::ScalarField::from_be_bytes_mod_order(&Sha256::digest(
+        &hash_input,
+    )))
+}
+
+/// The sum of ballots that passed [`ElGamalBallot::verify`] for a context.
+/// It can only be made by [`VerifiedElGamalTally::new`], so validators only
+/// make decryption shares of ciphertexts whose randomness is known to their
+/// authors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedElGamalTally<E: Pairing> {
+    ciphertext: ElGamalCiphertext<E>,
+}
+
+impl<E: Pairing> VerifiedElGamalTally<E> {
+    /// Verify every ballot for `context` and sum them.
+    /// Fails with `Error::InvalidElGamalBallot` on the first invalid ballot.
+    pub fn new(ballots: &[ElGamalBallot<E>], context: &[u8]) -> Result<Self> {
+        for (index, ballot) in ballots.iter().enumerate() {
+            if !ballot.verify(context)? {
+                return Err(Error::InvalidElGamalBallot(index as u32));
+            }
+        }
+        Ok(Self {
+            ciphertext: ballots.iter().map(|ballot| ballot.ciphertext).sum(),
+        })
+    }
+
+    pub fn ciphertext(&self) -> &ElGamalCiphertext<E> {
+        &self.ciphertext
+    }
+}
+
+/// A decryption share `D_i = e(R, Z_i)` of an `ElGamalCiphertext`, with a
+/// proof that it matches the share commitment `A_i`
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalDecryptionShare<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub decryption_share: E::TargetField,
+    #[serde(bound(
+        serialize = "DecryptionShareProof<E>: Serialize",
+        deserialize = "DecryptionShareProof<E>: DeserializeOwned"
+    ))]
+    pub proof: DecryptionShareProof<E>,
+}
+
+impl<E: Pairing> ElGamalDecryptionShare<E> {
+    pub fn create<R: rand::RngCore>(
+        tally: &VerifiedElGamalTally<E>,
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let ciphertext = tally.ciphertext();
+        let decryption_share =
+            E::pairing(ciphertext.randomness_commitment, private_key_share.0).0;
+        let proof = DecryptionShareProof::create(
+            &ciphertext.proof_context()?,
+            &ciphertext.randomness_commitment,
+            private_key_share,
+            share_commitment,
+            rng,
+        )?;
+        Ok(Self {
+            decryption_share,
+            proof,
+        })
+    }
+
+    pub fn verify(
+        &self,
+        ciphertext: &ElGamalCiphertext<E>,
+        share_commitment: &ShareCommitment<E>,
+    ) -> Result<bool> {
+        self.proof.verify(
+            &ciphertext.proof_context()?,
+            &ciphertext.randomness_commitment,
+            share_commitment,
+            &self.decryption_share,
+        )
+    }
+}
+
+/// Baby-step giant-step table for discrete logarithms of `m * e(G, H)` with
+/// `m` in `[0, max_value]`. The table can be reused across tallies.
+pub struct DiscreteLogTable<E: Pairing> {
+    baby_steps: HashMap<PairingOutput<E>, u64>,
+    giant_step: PairingOutput<E>,
+    max_value: u64,
+}
+
+impl<E: Pairing> DiscreteLogTable<E> {
+    pub fn new(max_value: u64) -> Self {
+        let base =
+            E::pairing(E::G1Affine::generator(), E::G2Affine::generator());
+        let steps = (max_value as f64 + 1.0).sqrt().ceil() as u64;
+        let mut baby_steps = HashMap::with_capacity(steps as usize);
+        let mut point = PairingOutput::<E>::zero();
+        for j in 0..steps {
+            baby_steps.insert(point, j);
+            point += base;
+        }
+        // `point` is now `steps * base`
+        Self {
+            baby_steps,
+            giant_step: point,
+            max_value,
+        }
+    }
+
+    pub fn max_value(&self) -> u64 {
+        self.max_value
+    }
+
+    /// Find `m <= max_value` such that `target = m * e(G, H)`
+    pub fn solve(&self, target: &PairingOutput<E>) -> Option<u64> {
+        let steps = self.baby_steps.len() as u64;
+        let mut point = *target;
+        for i in 0..=steps {
+            if let Some(j) = self.baby_steps.get(&point) {
+                let value = i * steps + j;
+                return (value <= self.max_value).then_some(value);
+            }
+            point -= self.giant_step;
+        }
+        None
+    }
+}
+
+/// Combine decryption shares of an `ElGamalCiphertext` and recover its
+/// plaintext, which must be at most `table.max_value()`
+pub fn combine_elgamal_shares<E: Pairing>(
+    ciphertext: &ElGamalCiphertext<E>,
+    decryption_shares: &[ElGamalDecryptionShare<E>],
+    lagrange_coeffs: &[E::ScalarField],
+    table: &DiscreteLogTable<E>,
+) -> Result<u64> {
+    // e(R, Z) = ∏ D_i^{λ_i}
+    let mask = decryption_shares.iter().zip(lagrange_coeffs).fold(
+        E::TargetField::ONE,
+        |acc, (share, lambda)| {
+            acc * share.decryption_share.pow(lambda.into_bigint())
+        },
+    );
+    let target =
+        E::pairing(ciphertext.masked_message, E::G2Affine::generator())
+            - PairingOutput(mask);
+    table
+        .solve(&target)
+        .ok_or(Error::PlaintextOutOfRange(table.max_value()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ec::{pairing::Pairing, AffineRepr};
+    use ark_std::test_rng;
+
+    use crate::{
+        combine_elgamal_shares, encrypt, prepare_combine_simple,
+        test_common::*, DiscreteLogTable, ElGamalBallot, ElGamalCiphertext,
+        ElGamalDecryptionShare, Error, SecretBox, VerifiedElGamalTally,
+    };
+
+    type E = ark_bls12_381::Bls12_381;
+    type ScalarField = <E as Pairing>::ScalarField;
+
+    const CONTEXT: &[u8] = b"tally-context";
+
+    #[test]
+    fn test_elgamal_tally() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (8, 5);
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+
+        let votes = [3u64, 0, 7, 1, 12, 0, 5];
+        let ballots = votes
+            .iter()
+            .map(|vote| {
+                ElGamalBallot::<E>::encrypt(*vote, &pubkey, CONTEXT, rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let verified_tally =
+            VerifiedElGamalTally::new(&ballots, CONTEXT).unwrap();
+        let tally = *verified_tally.ciphertext();
+        assert_eq!(
+            tally,
+            ballots
+                .iter()
+                .map(|ballot| ballot.ciphertext)
+                .sum::<ElGamalCiphertext<E>>()
+        );
+
+        let public_contexts = &contexts[0].public_decryption_contexts;
+        let decryption_shares = contexts[..threshold]
+            .iter()
+            .map(|context| {
+                let share_commitment =
+                    &public_contexts[context.index].share_commitment;
+                let share = ElGamalDecryptionShare::create(
+                    &verified_tally,
+                    &context.private_key_share,
+                    share_commitment,
+                    rng,
+                )
+                .unwrap();
+                assert!(share.verify(&tally, share_commitment).unwrap());
+                // Shares are bound to the ciphertext they decrypt
+                let other = ballots[0].ciphertext;
+                assert!(!share.verify(&other, share_commitment).unwrap());
+                share
+            })
+            .collect::<Vec<_>>();
+
+        let domain_points = public_contexts[..threshold]
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let lagrange_coeffs = prepare_combine_simple::<E>(&domain_points);
+
+        let table = DiscreteLogTable::<E>::new(100);
+        let result = combine_elgamal_shares(
+            &tally,
+            &decryption_shares,
+            &lagrange_coeffs,
+            &table,
+        )
+        .unwrap();
+        assert_eq!(result, votes.iter().sum::<u64>());
+
+        // Plaintexts above the table bound are not recovered
+        let table = DiscreteLogTable::<E>::new(20);
+        assert!(matches!(
+            combine_elgamal_shares(
+                &tally,
+                &decryption_shares,
+                &lagrange_coeffs,
+                &table,
+            ),
+            Err(Error::PlaintextOutOfRange(20))
+        ));
+    }
+
+    #[test]
+    fn test_elgamal_ballots_are_bound_to_their_randomness() {
+        let rng = &mut test_rng();
+        let (pubkey, _, _) = setup_simple::<E>(4, 3, rng);
+        let ballot =
+            ElGamalBallot::<E>::encrypt(1, &pubkey, CONTEXT, rng).unwrap();
+        assert!(ballot.verify(CONTEXT).unwrap());
+
+        // Ballots are bound to the tally context
+        assert!(!ballot.verify(b"other-tally").unwrap());
+        assert!(matches!(
+            VerifiedElGamalTally::new(&[ballot], b"other-tally"),
+            Err(Error::InvalidElGamalBallot(0))
+        ));
+
+        // The commitment `U` of a threshold encryption ciphertext can't be
+        // passed as `R` to get its shared secret decrypted
+        let ciphertext = encrypt::<E>(
+            SecretBox::new(b"secret".to_vec()),
+            b"aad",
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let mut forged = ballot;
+        forged.ciphertext.randomness_commitment = ciphertext.commitment;
+        assert!(!forged.verify(CONTEXT).unwrap());
+        assert!(matches!(
+            VerifiedElGamalTally::new(&[ballot, forged], CONTEXT),
+            Err(Error::InvalidElGamalBallot(1))
+        ));
+
+        // Neither can it be hidden in a sum with an honest ballot
+        let mut forged = ballot;
+        forged.ciphertext = forged.ciphertext
+            + ElGamalCiphertext {
+                randomness_commitment: ciphertext.commitment,
+                masked_message: ciphertext.commitment,
+            };
+        assert!(matches!(
+            VerifiedElGamalTally::new(&[forged], CONTEXT),
+            Err(Error::InvalidElGamalBallot(0))
+        ));
+    }
+
+    #[test]
+    fn test_discrete_log_table() {
+        let table = DiscreteLogTable::<E>::new(1000);
+        let base = E::pairing(
+            <E as Pairing>::G1Affine::generator(),
+            <E as Pairing>::G2Affine::generator(),
+        );
+        for value in [0u64, 1, 31, 32, 33, 999, 1000] {
+            let target = base * ScalarField::from(value);
+            assert_eq!(table.solve(&target), Some(value));
+        }
+        assert_eq!(table.solve(&(base * ScalarField::from(1001u64))), None);
+    }
+}
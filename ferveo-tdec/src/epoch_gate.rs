@@ -0,0 +1,260 @@
+//! Epoch-gated decryption: ciphertexts that the committee only helps decrypt
+//! once a given epoch is reached.
+//!
+//! This is not timelock encryption in the `tlock` sense. There is no
+//! per-epoch threshold signature, and nothing cryptographic ties decryption to
+//! the passage of time. Each validator checks its own clock before issuing a
+//! decryption share for the ciphertext, and simply refuses to do so earlier.
+//! Release before the epoch is therefore prevented only as long as fewer than
+//! `threshold` validators are dishonest or run a clock ahead of time. A
+//! colluding threshold can decrypt any of these ciphertexts at any time.
+//!
+//! What the construction does guarantee is that the epoch is bound to the
+//! ciphertext, so a requester can't lie about it to honest validators.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ark_ec::pairing::Pairing;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    decrypt_with_shared_secret, encrypt, Ciphertext, CiphertextHeader,
+    DecryptionShareSimple, DkgPublicKey, Error, HashToG2, PrivateKeyShare,
+    Result, SecretBox, SharedSecret,
+};
+
+const EPOCH_GATE_AAD_DST: &[u8] = b"FERVEO_EPOCH_GATE_V1";
+
+/// Source of the current epoch, used by validators to decide whether
+/// ciphertexts gated on an epoch can be released
+pub trait EpochClock {
+    fn current_epoch(&self) -> u64;
+}
+
+/// Epochs of `period` seconds, counted from `genesis_time` (UNIX time)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemEpochClock {
+    pub genesis_time: u64,
+    pub period: u64,
+}
+
+impl SystemEpochClock {
+    pub fn epoch_at(&self, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(self.genesis_time) / self.period.max(1)
+    }
+}
+
+impl EpochClock for SystemEpochClock {
+    fn current_epoch(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        self.epoch_at(now)
+    }
+}
+
+/// A ciphertext that validators only decrypt once `epoch` is reached.
+///
+/// The epoch is bound to the ciphertext header through the AAD, so it can't be
+/// changed without invalidating the header.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochGatedCiphertext<E: Pairing> {
+    pub epoch: u64,
+    #[serde(bound(
+        serialize = "Ciphertext<E>: Serialize",
+        deserialize = "Ciphertext<E>: DeserializeOwned"
+    ))]
+    pub ciphertext: Ciphertext<E>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochGatedCiphertextHeader<E: Pairing> {
+    pub epoch: u64,
+    #[serde(bound(
+        serialize = "CiphertextHeader<E>: Serialize",
+        deserialize = "CiphertextHeader<E>: DeserializeOwned"
+    ))]
+    pub header: CiphertextHeader<E>,
+}
+
+pub fn encrypt_to_epoch<E: HashToG2>(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    epoch: u64,
+    pubkey: &DkgPublicKey<E>,
+    rng: &mut impl rand::Rng,
+) -> Result<EpochGatedCiphertext<E>> {
+    let ciphertext = encrypt(message, &epoch_aad(epoch, aad), pubkey, rng)?;
+    Ok(EpochGatedCiphertext { epoch, ciphertext })
+}
+
+impl<E: HashToG2> EpochGatedCiphertext<E> {
+    pub fn header(&self) -> Result<EpochGatedCiphertextHeader<E>> {
+        Ok(EpochGatedCiphertextHeader {
+            epoch: self.epoch,
+            header: self.ciphertext.header()?,
+        })
+    }
+
+    pub fn decrypt_with_shared_secret(
+        &self,
+        aad: &[u8],
+        shared_secret: &SharedSecret<E>,
+    ) -> Result<Vec<u8>> {
+        decrypt_with_shared_secret(
+            &self.ciphertext,
+            &epoch_aad(self.epoch, aad),
+            shared_secret,
+        )
+    }
+}
+
+impl<E: HashToG2> EpochGatedCiphertextHeader<E> {
+    pub fn check(&self, aad: &[u8]) -> Result<bool> {
+        self.header.check(&epoch_aad(self.epoch, aad))
+    }
+
+    /// Fails with `Error::EpochNotReached` until `clock` reaches the epoch
+    pub fn check_unlocked(&self, clock: &impl EpochClock) -> Result<()> {
+        let current_epoch = clock.current_epoch();
+        if current_epoch < self.epoch {
+            return Err(Error::EpochNotReached(self.epoch, current_epoch));
+        }
+        Ok(())
+    }
+
+    /// The AAD the underlying header was encrypted with
+    pub fn header_aad(&self, aad: &[u8]) -> Vec<u8> {
+        epoch_aad(self.epoch, aad)
+    }
+
+    /// Create a decryption share (simple variant), once the epoch is reached
+    pub fn create_decryption_share_simple(
+        &self,
+        aad: &[u8],
+        validator_decryption_key: &E::ScalarField,
+        private_key_share: &PrivateKeyShare<E>,
+        clock: &impl EpochClock,
+    ) -> Result<DecryptionShareSimple<E>> {
+        self.check_unlocked(clock)?;
+        DecryptionShareSimple::create(
+            validator_decryption_key,
+            private_key_share,
+            &self.header,
+            &self.header_aad(aad),
+        )
+    }
+}
+
+fn epoch_aad(epoch: u64, aad: &[u8]) -> Vec<u8> {
+    let mut epoch_aad = EPOCH_GATE_AAD_DST.to_vec();
+    epoch_aad.extend_from_slice(&epoch.to_be_bytes());
+    epoch_aad.extend_from_slice(aad);
+    epoch_aad
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use ark_std::test_rng;
+
+    use crate::{
+        prepare_combine_simple, share_combine_simple, test_common::*,
+        EpochClock, Error, SecretBox, SystemEpochClock,
+    };
+
+    type E = ark_bls12_381::Bls12_381;
+
+    struct SimulatedClock(Cell<u64>);
+
+    impl EpochClock for SimulatedClock {
+        fn current_epoch(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn test_epoch_gated_encryption() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (8, 5);
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let epoch = 42;
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let ciphertext = encrypt_to_epoch::<E>(
+            SecretBox::new(msg.clone()),
+            aad,
+            epoch,
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let header = ciphertext.header().unwrap();
+        assert!(header.check(aad).unwrap());
+
+        // The epoch can't be changed without invalidating the header
+        let mut early_header = header.clone();
+        early_header.epoch = epoch - 1;
+        assert!(early_header.check(aad).is_err());
+
+        let clock = SimulatedClock(Cell::new(epoch - 1));
+        let create_shares = |clock: &SimulatedClock| {
+            contexts[..threshold]
+                .iter()
+                .map(|context| {
+                    header.create_decryption_share_simple(
+                        aad,
+                        &context.setup_params.b,
+                        &context.private_key_share,
+                        clock,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        // Validators refuse to release shares before the epoch
+        assert!(matches!(
+            create_shares(&clock),
+            Err(Error::EpochNotReached(e, current)) if e == epoch && current == epoch - 1
+        ));
+
+        clock.0.set(epoch);
+        let decryption_shares = create_shares(&clock).unwrap();
+        let domain_points = contexts[0].public_decryption_contexts[..threshold]
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let lagrange_coeffs = prepare_combine_simple::<E>(&domain_points);
+        let shared_secret =
+            share_combine_simple::<E>(&decryption_shares, &lagrange_coeffs);
+
+        let plaintext = ciphertext
+            .decrypt_with_shared_secret(aad, &shared_secret)
+            .unwrap();
+        assert_eq!(plaintext, msg);
+
+        // The ciphertext is not decryptable without the epoch binding
+        assert!(decrypt_with_shared_secret(
+            &ciphertext.ciphertext,
+            aad,
+            &shared_secret
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_system_epoch_clock() {
+        let clock = SystemEpochClock {
+            genesis_time: 1_000,
+            period: 30,
+        };
+        assert_eq!(clock.epoch_at(0), 0);
+        assert_eq!(clock.epoch_at(1_029), 0);
+        assert_eq!(clock.epoch_at(1_030), 1);
+        assert!(clock.current_epoch() > 0);
+    }
+}
@@ -1,14 +1,33 @@
 #![allow(non_snake_case)]
 #![allow(clippy::many_single_char_names)]
 #![allow(clippy::zero_prefixed_literal)]
-#![allow(dead_code)]
 
 use ark_bls12_381::g2::Config;
-use ark_ec::{short_weierstrass::Affine, AffineRepr};
+use ark_ec::{
+    hashing::{
+        curve_maps::swu::parity,
+        map_to_curve_hasher::{MapToCurve, MapToCurveBasedHasher},
+        HashToCurve, HashToCurveError,
+    },
+    pairing::Pairing,
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    AffineRepr,
+};
+use ark_ff::{field_hashers::HashToField, Field, One, PrimeField, Zero};
 use miracl_core::{
     bls12381::{big::BIG, dbig::DBIG, ecp, ecp2::ECP2, fp::FP, fp2::FP2, rom},
     hmac,
 };
+use sha2::{Digest, Sha256};
+
+use crate::Result;
+
+#[cfg(feature = "bls12-377")]
+const BLS12377_G2_DST: &[u8] =
+    b"FERVEO-V01-CS01-with-BLS12377G2_XMD:SHA-256_SSWU_RO_";
+#[cfg(feature = "bn254")]
+const BN254_G2_DST: &[u8] =
+    b"FERVEO-V01-CS01-with-BN254G2_XMD:SHA-256_SVDW_RO_";
 
 fn ceil(a: usize, b: usize) -> usize {
     (a - 1) / b + 1
@@ -85,8 +104,252 @@ fn to_affine(compressed_rev: &mut [u8; 96]) -> Affine<Config> {
         .expect("Failed to convert to affine point")
 }
 
+/// Pairing curves with a hash function to their G2 group.
+///
+/// BLS12-381 keeps using `htp_bls12381_g2`, so that ciphertexts created by
+/// earlier versions remain valid. The other curves use the RFC 9380
+/// `hash_to_curve` construction, through `hash_to_curve_rfc9380`. RFC 9380
+/// defines no suite for the G2 group of either curve, so their hashes are
+/// specific to this crate.
+pub trait HashToG2: Pairing {
+    /// Hashes `msg` to a point of the G2 group of the curve.
+    fn hash_to_g2(msg: &[u8]) -> Result<Self::G2Affine>;
+}
+
+impl HashToG2 for ark_bls12_381::Bls12_381 {
+    fn hash_to_g2(msg: &[u8]) -> Result<Self::G2Affine> {
+        Ok(htp_bls12381_g2(msg))
+    }
+}
+
+#[cfg(feature = "bls12-377")]
+impl HashToG2 for ark_bls12_377::Bls12_377 {
+    fn hash_to_g2(msg: &[u8]) -> Result<Self::G2Affine> {
+        hash_to_curve_rfc9380::<
+            ark_bls12_377::g2::Config,
+            ark_ec::hashing::curve_maps::wb::WBMap<ark_bls12_377::g2::Config>,
+        >(BLS12377_G2_DST, msg)
+    }
+}
+
+#[cfg(feature = "bn254")]
+impl HashToG2 for ark_bn254::Bn254 {
+    fn hash_to_g2(msg: &[u8]) -> Result<Self::G2Affine> {
+        hash_to_curve_rfc9380::<
+            ark_bn254::g2::Config,
+            SVDWMap<ark_bn254::g2::Config>,
+        >(BN254_G2_DST, msg)
+    }
+}
+
+/// Hashes `msg` to the prime-order subgroup of the curve `P` following the
+/// `hash_to_curve` construction of RFC 9380, with `expand_message_xmd` over
+/// SHA-256 and the map `M`.
+pub fn hash_to_curve_rfc9380<P, M>(dst: &[u8], msg: &[u8]) -> Result<Affine<P>>
+where
+    P: SWCurveConfig,
+    M: MapToCurve<Projective<P>>,
+{
+    let hasher =
+        MapToCurveBasedHasher::<Projective<P>, XmdSha256FieldHasher, M>::new(
+            dst,
+        )?;
+    Ok(hasher.hash(msg)?)
+}
+
+/// Input block size of SHA-256, in bytes
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// Security level of `hash_to_field`, in bits
+const HASH_TO_FIELD_SECURITY: usize = 128;
+
+/// `hash_to_field` from RFC 9380, Section 5.2, with `expand_message_xmd` over
+/// SHA-256.
+///
+/// The `DefaultFieldHasher` of arkworks pads the message with as many zero
+/// bytes as it takes to encode a field element, instead of the input block
+/// size of the hash. Both are 64 bytes for BLS12-381 and BLS12-377, but a
+/// BN254 element only takes 48 bytes, so its outputs don't match the RFC.
+pub struct XmdSha256FieldHasher {
+    dst: Vec<u8>,
+    len_per_base_elem: usize,
+}
+
+impl<F: Field> HashToField<F> for XmdSha256FieldHasher {
+    fn new(dst: &[u8]) -> Self {
+        let modulus_bits = F::BasePrimeField::MODULUS_BIT_SIZE as usize;
+        XmdSha256FieldHasher {
+            dst: dst.to_vec(),
+            len_per_base_elem: (modulus_bits + HASH_TO_FIELD_SECURITY)
+                .div_ceil(8),
+        }
+    }
+
+    fn hash_to_field(&self, msg: &[u8], count: usize) -> Vec<F> {
+        let m = F::extension_degree() as usize;
+        let uniform_bytes = expand_message_xmd(
+            msg,
+            &self.dst,
+            count * m * self.len_per_base_elem,
+        );
+        uniform_bytes
+            .chunks(m * self.len_per_base_elem)
+            .map(|element_bytes| {
+                let base_elements = element_bytes
+                    .chunks(self.len_per_base_elem)
+                    .map(F::BasePrimeField::from_be_bytes_mod_order)
+                    .collect::<Vec<_>>();
+                F::from_base_prime_field_elems(&base_elements)
+                    .expect("m base field elements make an element of F")
+            })
+            .collect()
+    }
+}
+
+/// `expand_message_xmd` from RFC 9380, Section 5.3.1, over SHA-256
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    // Section 5.3.3: oversized tags are replaced with their hash
+    let mut dst_prime = if dst.len() > 255 {
+        Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_in_bytes = Sha256::output_size();
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    assert!(ell <= 255, "expand_message_xmd output is too long");
+    let l_i_b_str = u16::try_from(len_in_bytes)
+        .expect("expand_message_xmd output is too long")
+        .to_be_bytes();
+
+    let b_0 = Sha256::new()
+        .chain_update([0u8; SHA256_BLOCK_SIZE])
+        .chain_update(msg)
+        .chain_update(l_i_b_str)
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=ell {
+        let b_0_xor_b_i = b_0
+            .iter()
+            .zip(b_i.iter())
+            .map(|(b_0, b_i)| b_0 ^ b_i)
+            .collect::<Vec<_>>();
+        b_i = Sha256::new()
+            .chain_update(b_0_xor_b_i)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// Parameters of the Shallue-van de Woestijne map from RFC 9380, Section
+/// 6.6.1. Unlike the simplified SWU map, it applies to any short Weierstrass
+/// curve, including curves with `A = 0` and no known isogeny, such as BN254.
+pub trait SVDWConfig: SWCurveConfig {
+    /// The `Z` constant, satisfying the criteria of RFC 9380, Appendix H.1.
+    const Z: Self::BaseField;
+}
+
+/// The Shallue-van de Woestijne map for the curve `P`, along with the
+/// constants derived from `P::Z`.
+pub struct SVDWMap<P: SVDWConfig> {
+    c1: P::BaseField,
+    c2: P::BaseField,
+    c3: P::BaseField,
+    c4: P::BaseField,
+}
+
+/// Evaluates the right-hand side of the curve equation, `x^3 + A * x + B`.
+fn curve_rhs<P: SWCurveConfig>(x: P::BaseField) -> P::BaseField {
+    (x.square() + P::COEFF_A) * x + P::COEFF_B
+}
+
+fn is_square<F: Field>(element: F) -> bool {
+    !element.legendre().is_qnr()
+}
+
+impl<P: SVDWConfig> MapToCurve<Projective<P>> for SVDWMap<P> {
+    fn new() -> std::result::Result<Self, HashToCurveError> {
+        let invalid_z = || {
+            HashToCurveError::MapToCurveError(
+                "Z does not satisfy the criteria for the SvdW map".to_string(),
+            )
+        };
+        let z = P::Z;
+        let c1 = curve_rhs::<P>(z);
+        let c2 = -z / P::BaseField::from(2u64);
+        // 3 * Z^2 + 4 * A
+        let tv = P::BaseField::from(3u64) * z.square()
+            + P::BaseField::from(4u64) * P::COEFF_A;
+        if c1.is_zero() || tv.is_zero() {
+            return Err(invalid_z());
+        }
+        if !is_square(c1) && !is_square(curve_rhs::<P>(c2)) {
+            return Err(invalid_z());
+        }
+        let mut c3 = (-c1 * tv).sqrt().ok_or_else(invalid_z)?;
+        if parity(&c3) {
+            c3 = -c3;
+        }
+        let c4 = -c1.double().double() / tv;
+        Ok(SVDWMap { c1, c2, c3, c4 })
+    }
+
+    fn map_to_curve(
+        &self,
+        u: P::BaseField,
+    ) -> std::result::Result<Affine<P>, HashToCurveError> {
+        let one = P::BaseField::one();
+        let tv1 = u.square() * self.c1;
+        let tv2 = one + tv1;
+        let tv1 = one - tv1;
+        let tv3 = (tv1 * tv2).inverse().unwrap_or_else(P::BaseField::zero);
+        let tv4 = u * tv1 * tv3 * self.c3;
+        let x1 = self.c2 - tv4;
+        let x2 = self.c2 + tv4;
+        let x3 = (tv2.square() * tv3).square() * self.c4 + P::Z;
+        let x = if is_square(curve_rhs::<P>(x1)) {
+            x1
+        } else if is_square(curve_rhs::<P>(x2)) {
+            x2
+        } else {
+            x3
+        };
+        let mut y = curve_rhs::<P>(x).sqrt().ok_or_else(|| {
+            HashToCurveError::MapToCurveError(
+                "SvdW map produced a non-square".to_string(),
+            )
+        })?;
+        if parity(&y) != parity(&u) {
+            y = -y;
+        }
+        Ok(Affine::new_unchecked(x, y))
+    }
+}
+
+#[cfg(feature = "bn254")]
+impl SVDWConfig for ark_bn254::g2::Config {
+    const Z: ark_bn254::Fq2 = ark_bn254::Fq2::ONE;
+}
+
 #[cfg(test)]
 mod tests {
+    use ark_serialize::CanonicalDeserialize;
 
     use super::*;
 
@@ -119,4 +382,149 @@ mod tests {
             "939cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6";
         test_hash_to_g2(msg, expected_hex_string)
     }
+
+    #[test]
+    fn rfc9380_hash_matches_test_vectors() {
+        // The same vectors as above, from RFC 9380, Appendix J.10.1, decoded
+        // with their sign flag
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        for (msg, expected_hex_string) in [
+            (&b""[..], "a5cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a"),
+            (&b"abc"[..], "939cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+        ] {
+            let expected = ark_bls12_381::G2Affine::deserialize_compressed(
+                &hex::decode(expected_hex_string).unwrap()[..],
+            )
+            .unwrap();
+            let res = hash_to_curve_rfc9380::<
+                Config,
+                ark_ec::hashing::curve_maps::wb::WBMap<Config>,
+            >(dst, msg)
+            .unwrap();
+            assert_eq!(res, expected);
+        }
+    }
+
+    #[test]
+    fn expand_message_xmd_matches_test_vectors() {
+        // RFC 9380, Appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        for (msg, len_in_bytes, expected_hex_string) in [
+            (&b""[..], 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+            (&b"abc"[..], 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+            (&b""[..], 0x80, "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"),
+        ] {
+            assert_eq!(
+                hex::encode(expand_message_xmd(msg, dst, len_in_bytes)),
+                expected_hex_string
+            );
+        }
+    }
+
+    #[cfg(feature = "bls12-377")]
+    #[test]
+    fn hash_to_g2_bls12_377() {
+        test_hash_to_g2_generic::<ark_bls12_377::Bls12_377>();
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn hash_to_g2_bn254() {
+        test_hash_to_g2_generic::<ark_bn254::Bn254>();
+    }
+
+    #[cfg(any(feature = "bls12-377", feature = "bn254"))]
+    fn test_hash_to_g2_generic<E: HashToG2>() {
+        use ark_ff::PrimeField;
+
+        let point = E::hash_to_g2(b"abc").unwrap();
+        assert!(!point.is_zero());
+        // Points of the prime-order subgroup vanish when multiplied by its order
+        assert!(point
+            .mul_bigint(<E::ScalarField as PrimeField>::MODULUS)
+            .is_zero());
+        assert_eq!(E::hash_to_g2(b"abc").unwrap(), point);
+        assert_ne!(E::hash_to_g2(b"abd").unwrap(), point);
+    }
+
+    /// Intermediate and final values of `hash_to_curve` for one message:
+    /// the field elements `u`, their images `q` under the map, and the point
+    /// `p`. Field elements are given by their decimal coefficients.
+    #[cfg(feature = "bn254")]
+    struct Bn254Vector {
+        msg: &'static [u8],
+        u: [(&'static str, &'static str); 2],
+        q: [Bn254Point; 2],
+        p: Bn254Point,
+    }
+
+    #[cfg(feature = "bn254")]
+    type Bn254Point =
+        ((&'static str, &'static str), (&'static str, &'static str));
+
+    #[cfg(feature = "bn254")]
+    fn bn254_fq2((c0, c1): (&str, &str)) -> ark_bn254::Fq2 {
+        use std::str::FromStr;
+        ark_bn254::Fq2::new(
+            ark_bn254::Fq::from_str(c0).unwrap(),
+            ark_bn254::Fq::from_str(c1).unwrap(),
+        )
+    }
+
+    #[cfg(feature = "bn254")]
+    fn bn254_point((x, y): Bn254Point) -> ark_bn254::G2Affine {
+        ark_bn254::G2Affine::new_unchecked(bn254_fq2(x), bn254_fq2(y))
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn hash_to_g2_bn254_regression() {
+        // RFC 9380 defines no suite for BN254 G2, and these values were not
+        // checked against another library. They only pin the outputs of this
+        // implementation, so that they can't change unnoticed.
+        let vectors = [
+            Bn254Vector {
+                msg: b"",
+                u: [
+                    ("6621864312114922987576031614677178966912302913959046392480352911453473807672", "10661834472791433594942037366683290614572746321619383488709223303666500674520"),
+                    ("4724030183309583174691926846216963080116462521597739176524464480701486776771", "16327026598704452767648768238060423412270063020588940890438187215579098516034"),
+                ],
+                q: [
+                    (("14313906650835278508276895554116451144013749048316325654150624529992937134557", "2385695835046431492635957598710134000788222250761688664508490504086035517232"), ("8674379106306276154002708209376958287382196668325349123341163054940133378466", "3122412304236086235178674387239830966057340632901686465249954487761599079068")),
+                    (("2979198058188923479296693611919691834917942708458811077583914993082466576591", "10024737364278269549519358257837294520696772917484790848616235190504946081484"), ("12673868260880681823672009384219810854770458833964259831832547174090334943243", "16633950839475793363291419779430179995026088971660107769326985925820715862570")),
+                ],
+                p: (("10676991248218028598679047198270364067294981726724685805705624281919948116694", "11201259587593603300847918446792797450971820742884729091529181210923284690551"), ("628612479022056379582992462461921453320173080358007766822570226423587226467", "14752878335960927171125223164911294591037028633434257612645415103754150579377")),
+            },
+            Bn254Vector {
+                msg: b"abc",
+                u: [
+                    ("10502236852125882782545014915861386179987616002379313731088305896120351408671", "1099873819391522234162012390178972652049227843094586755118653323951231855638"),
+                    ("21419308906757494593653229491389852091272897784804408709312224672357905065895", "13694477344001776070357022219214772230665017848765613696707124290531846474907"),
+                ],
+                q: [
+                    (("17175474848331458888163976664849468654334852578872427332920634903096866329783", "2554969299291673457308767299157969685236547461310764805139991565556221279928"), ("10336517091472333389309569016330687347634078804245686409084698067395066180901", "5283571267629972790447956209567475058181179590164580852424444720469318867451")),
+                    (("9825748967942340605556066415546500535189758754178392295432856308653886163193", "5901624143256355838455974697855647121881200823318023799316940051444302090242"), ("19102984432338954372878035181362033347690661248642468077221004512653392406505", "17800276303910467559558059080268291820020832990458342501245970612327278149850")),
+                ],
+                p: (("20433451846381479411042815745839843101930953481229304307637376970712889147104", "4968579811964960103331229239779475322669432696770290268655662555898724463941"), ("16824952643352922978604201766948670181289355651221135315088626912178085646407", "18813290556280127578326461634166802227578828885536831604243932764364314017267")),
+            },
+        ];
+
+        let field_hasher = <XmdSha256FieldHasher as HashToField<
+            ark_bn254::Fq2,
+        >>::new(BN254_G2_DST);
+        let map = SVDWMap::<ark_bn254::g2::Config>::new().unwrap();
+        for vector in vectors {
+            let u = field_hasher.hash_to_field(vector.msg, 2);
+            assert_eq!(u, vector.u.map(bn254_fq2));
+            for (u, q) in u.into_iter().zip(vector.q) {
+                let q = bn254_point(q);
+                assert!(q.is_on_curve());
+                assert_eq!(map.map_to_curve(u).unwrap(), q);
+            }
+            let p = bn254_point(vector.p);
+            assert!(p.is_on_curve());
+            assert!(p.is_in_correct_subgroup_assuming_on_curve());
+            assert_eq!(ark_bn254::Bn254::hash_to_g2(vector.msg).unwrap(), p);
+        }
+    }
 }
@@ -1,15 +1,20 @@
 use std::{collections::HashMap, ops::Mul};
 
-use ark_ec::{pairing::Pairing, CurveGroup};
-use ark_ff::Field;
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    scalar_mul::fixed_base::FixedBase,
+    AffineRepr, CurveGroup, Group,
+};
+use ark_ff::{Field, PrimeField};
 use ferveo_common::{serialization, Keypair};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::{
-    prepare_combine_simple, CiphertextHeader, DecryptionSharePrecomputed,
-    DecryptionShareSimple, DomainPoint, Result,
+    lagrange_coeff_at_zero, CiphertextHeader, DecryptionSharePrecomputed,
+    DecryptionShareSimple, DomainPoint, Error, HashToG2, Result,
+    VerifiedCiphertextHeader,
 };
 
 #[serde_as]
@@ -18,6 +23,86 @@ pub struct DkgPublicKey<E: Pairing>(
     #[serde_as(as = "serialization::SerdeAs")] pub E::G1Affine,
 );
 
+/// Window size of the fixed-base table used by `EncryptionKey`
+const ENCRYPTION_KEY_WINDOW_SIZE: usize = 4;
+
+/// A `DkgPublicKey` prepared for repeated encryption.
+/// Caches `e(pk, h)` and a fixed-base table for multiples of the G1 generator,
+/// so that encryption takes a GT exponentiation instead of a pairing.
+///
+/// Only the public key is serialized, and the cached values are rebuilt from
+/// it on load, so a tampered key can't make them disagree.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(
+    from = "DkgPublicKey<E>",
+    into = "DkgPublicKey<E>",
+    bound(
+        serialize = "DkgPublicKey<E>: Serialize",
+        deserialize = "DkgPublicKey<E>: DeserializeOwned"
+    )
+)]
+pub struct EncryptionKey<E: Pairing> {
+    public_key: DkgPublicKey<E>,
+    // e(pk, h)
+    public_key_pairing: E::TargetField,
+    // Multiples of g, see `FixedBase::get_window_table`
+    generator_table: Vec<Vec<E::G1Affine>>,
+}
+
+impl<E: Pairing> EncryptionKey<E> {
+    pub fn new(public_key: &DkgPublicKey<E>) -> Self {
+        let public_key_pairing =
+            E::pairing(public_key.0, E::G2Affine::generator()).0;
+        let generator_table = FixedBase::get_window_table(
+            E::ScalarField::MODULUS_BIT_SIZE as usize,
+            ENCRYPTION_KEY_WINDOW_SIZE,
+            E::G1::generator(),
+        );
+        Self {
+            public_key: *public_key,
+            public_key_pairing,
+            generator_table,
+        }
+    }
+
+    pub fn public_key(&self) -> &DkgPublicKey<E> {
+        &self.public_key
+    }
+
+    /// [r] G
+    pub(crate) fn commitment(&self, r: &E::ScalarField) -> E::G1Affine {
+        let scalar_size = E::ScalarField::MODULUS_BIT_SIZE as usize;
+        let outerc = scalar_size.div_ceil(ENCRYPTION_KEY_WINDOW_SIZE);
+        FixedBase::windowed_mul::<E::G1>(
+            outerc,
+            ENCRYPTION_KEY_WINDOW_SIZE,
+            &self.generator_table,
+            r,
+        )
+        .into_affine()
+    }
+
+    /// e(pk, h)^r = e([r] pk, h)
+    pub(crate) fn shared_secret_product(
+        &self,
+        r: &E::ScalarField,
+    ) -> E::TargetField {
+        (PairingOutput::<E>(self.public_key_pairing) * r).0
+    }
+}
+
+impl<E: Pairing> From<DkgPublicKey<E>> for EncryptionKey<E> {
+    fn from(public_key: DkgPublicKey<E>) -> Self {
+        Self::new(&public_key)
+    }
+}
+
+impl<E: Pairing> From<EncryptionKey<E>> for DkgPublicKey<E> {
+    fn from(encryption_key: EncryptionKey<E>) -> Self {
+        encryption_key.public_key
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ShareCommitment<E: Pairing>(
@@ -26,13 +111,16 @@ pub struct ShareCommitment<E: Pairing>(
 
 // TODO: Improve by adding share commitment here
 // TODO: Is this a test utility perhaps?
-#[derive(Debug, Copy, Clone)]
+#[serde_as]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct BlindedKeyShare<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
     pub validator_public_key: E::G2Affine, // [b] H
-    pub blinded_key_share: E::G2Affine,    // [b] Z_{i, \omega_i}
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub blinded_key_share: E::G2Affine, // [b] Z_{i, \omega_i}
 }
 
-impl<E: Pairing> BlindedKeyShare<E> {
+impl<E: HashToG2> BlindedKeyShare<E> {
     // TODO: Salvage and cleanup - #197
     // pub fn verify_blinding<R: RngCore>(
     //     &self,
@@ -68,7 +156,7 @@ impl<E: Pairing> BlindedKeyShare<E> {
         let unblinding_factor = validator_keypair
             .decryption_key
             .inverse()
-            .expect("Validator decryption key must have an inverse");
+            .ok_or(Error::InvalidValidatorDecryptionKey)?;
         Ok(PrivateKeyShare::<E>(
             self.blinded_key_share.mul(unblinding_factor).into_affine(),
         ))
@@ -88,6 +176,20 @@ impl<E: Pairing> BlindedKeyShare<E> {
         )
     }
 
+    /// Make a decryption share (simple variant) for an already checked
+    /// ciphertext header
+    pub fn create_decryption_share_simple_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+        validator_keypair: &Keypair<E>,
+    ) -> Result<DecryptionShareSimple<E>> {
+        DecryptionShareSimple::create_verified(
+            &validator_keypair.decryption_key,
+            &self.unblind(validator_keypair)?,
+            ciphertext_header,
+        )
+    }
+
     /// In precomputed variant, we offload some of the decryption related computation to the server-side:
     /// We use the `prepare_combine_simple` function to precompute the lagrange coefficients
     pub fn create_decryption_share_precomputed(
@@ -98,47 +200,39 @@ impl<E: Pairing> BlindedKeyShare<E> {
         share_index: u32,
         domain_points_map: &HashMap<u32, DomainPoint<E>>,
     ) -> Result<DecryptionSharePrecomputed<E>> {
-        // We need to turn the domain points into a vector, and sort it by share index
-        let mut domain_points = domain_points_map
-            .iter()
-            .map(|(share_index, domain_point)| (*share_index, *domain_point))
-            .collect::<Vec<_>>();
-        domain_points.sort_by_key(|(share_index, _)| *share_index);
-
-        // Now, we have to pass the domain points to the `prepare_combine_simple` function
-        // and use the resulting lagrange coefficients to create the decryption share
-
-        let only_domain_points = domain_points
-            .iter()
-            .map(|(_, domain_point)| *domain_point)
-            .collect::<Vec<_>>();
-        let lagrange_coeffs = prepare_combine_simple::<E>(&only_domain_points);
-
-        // Before we pick the lagrange coefficient for the current share index, we need
-        // to map the share index to the index in the domain points vector
-        // Given that we sorted the domain points by share index, the first element in the vector
-        // will correspond to the smallest share index, second to the second smallest, and so on
-
-        let sorted_share_indices = domain_points
-            .iter()
-            .enumerate()
-            .map(|(adjusted_share_index, (share_index, _))| {
-                (*share_index, adjusted_share_index)
-            })
-            .collect::<HashMap<u32, usize>>();
-        let adjusted_share_index =
-            *sorted_share_indices.get(&share_index).unwrap();
-
-        // Finally, pick the lagrange coefficient for the current share index
-        let lagrange_coeff = &lagrange_coeffs[adjusted_share_index];
-        let private_key_share = self.unblind(validator_keypair);
+        let lagrange_coeff =
+            lagrange_coeff_at_zero::<E>(share_index, domain_points_map)
+                .ok_or(Error::InvalidShareIndex(share_index))?;
+        let private_key_share = self.unblind(validator_keypair)?;
         DecryptionSharePrecomputed::create(
             share_index as usize,
             &validator_keypair.decryption_key,
-            &private_key_share.unwrap(),
+            &private_key_share,
             ciphertext_header,
             aad,
-            lagrange_coeff,
+            &lagrange_coeff,
+        )
+    }
+
+    /// Make a decryption share (precomputed variant) for an already checked
+    /// ciphertext header
+    pub fn create_decryption_share_precomputed_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        domain_points_map: &HashMap<u32, DomainPoint<E>>,
+    ) -> Result<DecryptionSharePrecomputed<E>> {
+        let lagrange_coeff =
+            lagrange_coeff_at_zero::<E>(share_index, domain_points_map)
+                .ok_or(Error::InvalidShareIndex(share_index))?;
+        let private_key_share = self.unblind(validator_keypair)?;
+        DecryptionSharePrecomputed::create_verified(
+            share_index as usize,
+            &validator_keypair.decryption_key,
+            &private_key_share,
+            ciphertext_header,
+            &lagrange_coeff,
         )
     }
 }
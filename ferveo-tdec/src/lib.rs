@@ -2,12 +2,21 @@
 use ark_ec::pairing::Pairing;
 
 // TODO: Use explicit imports - #194
+pub mod blinded_share;
 pub mod ciphertext;
 pub mod combine;
 pub mod context;
 pub mod decryption;
+pub mod decryption_service;
+pub mod dleq;
+pub mod elgamal;
+pub mod epoch_gate;
 pub mod hash_to_curve;
 pub mod key_share;
+pub mod masking;
+pub mod multi_key;
+pub mod multi_payload;
+pub mod reencryption;
 pub mod secret_box;
 
 // TODO: Only show the public API, tpke::api
@@ -19,12 +28,21 @@ pub mod secret_box;
 // use key_share::*;
 // use refresh::*;
 
+pub use blinded_share::*;
 pub use ciphertext::*;
 pub use combine::*;
 pub use context::*;
 pub use decryption::*;
+pub use decryption_service::*;
+pub use dleq::*;
+pub use elgamal::*;
+pub use epoch_gate::*;
 pub use hash_to_curve::*;
 pub use key_share::*;
+pub use masking::*;
+pub use multi_key::*;
+pub use multi_payload::*;
+pub use reencryption::*;
 pub use secret_box::*;
 
 #[cfg(feature = "api")]
@@ -46,6 +64,66 @@ pub enum Error {
     #[error("Symmetric encryption failed")]
     SymmetricEncryptionError(chacha20poly1305::aead::Error),
 
+    /// Share index is not part of the domain, or of the selected validators
+    #[error("Invalid share index: {0}")]
+    InvalidShareIndex(u32),
+
+    /// Multi-payload ciphertexts must have at least one payload
+    #[error("No payloads to encrypt")]
+    NoPayloads,
+
+    /// Payload index is out of range for a multi-payload ciphertext
+    #[error("Invalid payload index: {0}")]
+    InvalidPayloadIndex(u32),
+
+    /// Payload is not bound to the ciphertext header
+    #[error("Invalid payload proof")]
+    InvalidPayloadProof,
+
+    /// Validator decryption key is not invertible
+    #[error("Invalid validator decryption key")]
+    InvalidValidatorDecryptionKey,
+
+    /// Epoch-gated ciphertext can't be decrypted before its epoch
+    #[error("Epoch {0} not reached, current epoch is {1}")]
+    EpochNotReached(u64, u64),
+
+    /// Plaintext was not found by the bounded discrete logarithm search
+    #[error("Plaintext is out of range, expected at most {0}")]
+    PlaintextOutOfRange(u64),
+
+    /// ElGamal ballot has an invalid proof of knowledge of its randomness
+    #[error("Invalid ElGamal ballot: {0}")]
+    InvalidElGamalBallot(u32),
+
+    /// Access policy must not contain empty `All` or `Any` branches, and must
+    /// match the ciphertext components
+    #[error("Invalid access policy")]
+    InvalidPolicy,
+
+    /// Shared secrets don't satisfy the access policy of the ciphertext
+    #[error("Access policy not satisfied")]
+    PolicyNotSatisfied,
+
+    /// Access policy refers to a ritual whose public key was not provided
+    #[error("Missing public key for ritual {0}")]
+    MissingRitualPublicKey(u32),
+
+    /// Component index is out of range for a multi-key ciphertext
+    #[error("Invalid component index: {0}")]
+    InvalidComponentIndex(u32),
+
+    /// Decryption shares can't be blinded to the identity
+    #[error("Invalid requester public key")]
+    InvalidRequesterPublicKey,
+
+    /// Re-encryption share was masked without a commitment to the tag `H'`
+    #[error("Missing commitment to the re-encryption tag")]
+    MissingReEncryptionTag,
+
+    #[error(transparent)]
+    HashToCurveError(#[from] ark_ec::hashing::HashToCurveError),
+
     #[error(transparent)]
     BincodeError(#[from] bincode::Error),
 
@@ -212,10 +290,7 @@ mod tests {
     use ferveo_common::{FromBytes, ToBytes};
     use rand::seq::IteratorRandom;
 
-    use crate::{
-        api::DecryptionSharePrecomputed,
-        test_common::{create_shared_secret_simple, setup_simple, *},
-    };
+    use crate::test_common::{create_shared_secret_simple, setup_simple, *};
 
     type E = ark_bls12_381::Bls12_381;
     type TargetField = <E as Pairing>::TargetField;
@@ -241,7 +316,7 @@ mod tests {
         assert_eq!(serialized, deserialized.to_bytes().unwrap())
     }
 
-    fn test_ciphertext_validation_fails<E: Pairing>(
+    fn test_ciphertext_validation_fails<E: HashToG2>(
         msg: &[u8],
         aad: &[u8],
         ciphertext: &Ciphertext<E>,
@@ -363,7 +438,7 @@ mod tests {
                     )
                     .unwrap()
             })
-            .collect::<Vec<DecryptionSharePrecomputed>>();
+            .collect::<Vec<DecryptionSharePrecomputed<E>>>();
 
         let shared_secret = share_combine_precomputed::<E>(&decryption_shares);
         test_ciphertext_validation_fails(
@@ -0,0 +1,246 @@
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{PrimeField, UniformRand};
+use ark_serialize::CanonicalSerialize;
+use ferveo_common::serialization;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{digest::Digest, Sha256};
+
+use crate::{
+    dleq::is_in_target_subgroup, PrivateKeyShare, Result, ShareCommitment,
+};
+
+/// Proof that a masked decryption share `e(V, Z_i) * e(P, H)^{-r_i}` was
+/// produced with the key share `Z_i` committed to by `A_i`, and with the same
+/// randomness `r_i` as `[r_i] G` (and `[r_i] H'`, if present).
+///
+/// A Sigma protocol for the homomorphism
+/// `(Z, r) -> (e(V, Z) * e(P, H)^{-r}, e(G, Z), [r] G, [r] H')`,
+/// made non-interactive with Fiat-Shamir.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaskingProof<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub challenge: E::ScalarField,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub key_share_response: E::G2Affine,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub randomness_response: E::ScalarField,
+}
+
+/// Public inputs of a `MaskingProof`
+pub(crate) struct MaskingBases<E: Pairing> {
+    /// `V`, the point paired with the key share
+    pub share_base: E::G1Affine,
+    /// `P`, the public key the share is masked to
+    pub masking_key: E::G1Affine,
+    /// `H'`, if the randomness is also committed to in G2
+    pub tag_base: Option<E::G2Affine>,
+}
+
+pub(crate) struct MaskedShare<E: Pairing> {
+    /// `e(V, Z_i) * e(P, H)^{-r_i}`
+    pub masked_share: E::TargetField,
+    /// `[r_i] G`
+    pub commitment: E::G1Affine,
+    /// `[r_i] H'`
+    pub auth_tag: Option<E::G2Affine>,
+}
+
+impl<E: Pairing> MaskingProof<E> {
+    /// Masks `e(V, Z_i)` with fresh randomness and proves it.
+    /// `context` is bound to the proof, and must be reproduced by the verifier.
+    pub(crate) fn create<R: rand::RngCore>(
+        context: &[u8],
+        bases: &MaskingBases<E>,
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        rng: &mut R,
+    ) -> Result<(MaskedShare<E>, Self)> {
+        let h = E::G2Affine::generator();
+
+        let randomness = E::ScalarField::rand(rng);
+        let share = bases.image(private_key_share.0.into_group(), randomness);
+
+        let key_share_blinding = h * E::ScalarField::rand(rng);
+        let randomness_blinding = E::ScalarField::rand(rng);
+        let blinding = bases.image(key_share_blinding, randomness_blinding);
+
+        let challenge =
+            challenge(context, bases, share_commitment, &share, &blinding)?;
+        let proof = MaskingProof {
+            challenge,
+            key_share_response: (key_share_blinding
+                + private_key_share.0 * challenge)
+                .into_affine(),
+            randomness_response: randomness_blinding + randomness * challenge,
+        };
+        let masked_share = MaskedShare {
+            masked_share: share.masked_share.0,
+            commitment: share.commitment,
+            auth_tag: share.auth_tag,
+        };
+        Ok((masked_share, proof))
+    }
+
+    pub(crate) fn verify(
+        &self,
+        context: &[u8],
+        bases: &MaskingBases<E>,
+        share_commitment: &ShareCommitment<E>,
+        masked_share: &MaskedShare<E>,
+    ) -> Result<bool> {
+        // The proof says nothing about a component of the masked share
+        // outside of GT, so such shares (and zero) are rejected up front
+        if bases.tag_base.is_some() != masked_share.auth_tag.is_some()
+            || !is_in_target_subgroup::<E>(&masked_share.masked_share)
+        {
+            return Ok(false);
+        }
+        let h = E::G2Affine::generator();
+        let share = Image::<E> {
+            masked_share: PairingOutput(masked_share.masked_share),
+            key_share: E::pairing(share_commitment.0, h),
+            commitment: masked_share.commitment,
+            auth_tag: masked_share.auth_tag,
+        };
+
+        // Recover the prover's commitments as φ(s) - c * statement
+        let response = bases.image(
+            self.key_share_response.into_group(),
+            self.randomness_response,
+        );
+        let blinding = Image::<E> {
+            masked_share: response.masked_share
+                - share.masked_share * self.challenge,
+            key_share: response.key_share - share.key_share * self.challenge,
+            commitment: (response.commitment.into_group()
+                - share.commitment * self.challenge)
+                .into_affine(),
+            auth_tag: response.auth_tag.zip(share.auth_tag).map(
+                |(response, auth_tag)| {
+                    (response.into_group() - auth_tag * self.challenge)
+                        .into_affine()
+                },
+            ),
+        };
+
+        let expected_challenge =
+            challenge(context, bases, share_commitment, &share, &blinding)?;
+        Ok(expected_challenge == self.challenge)
+    }
+}
+
+#[cfg(test)]
+impl<E: Pairing> MaskingProof<E> {
+    /// Like `create`, but multiplies the masked share by -1, which has order 2
+    /// and is outside of GT. The proof is made for the modified share, and
+    /// retried until its challenge `c` is even, so that `(-1)^c = 1`.
+    pub(crate) fn create_negated<R: rand::RngCore>(
+        context: &[u8],
+        bases: &MaskingBases<E>,
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        rng: &mut R,
+    ) -> Result<(MaskedShare<E>, Self)> {
+        use ark_ff::BigInteger;
+
+        let h = E::G2Affine::generator();
+        loop {
+            let randomness = E::ScalarField::rand(rng);
+            let mut share =
+                bases.image(private_key_share.0.into_group(), randomness);
+            share.masked_share = PairingOutput(-share.masked_share.0);
+
+            let key_share_blinding = h * E::ScalarField::rand(rng);
+            let randomness_blinding = E::ScalarField::rand(rng);
+            let blinding = bases.image(key_share_blinding, randomness_blinding);
+
+            let challenge =
+                challenge(context, bases, share_commitment, &share, &blinding)?;
+            if challenge.into_bigint().is_odd() {
+                continue;
+            }
+            let proof = MaskingProof {
+                challenge,
+                key_share_response: (key_share_blinding
+                    + private_key_share.0 * challenge)
+                    .into_affine(),
+                randomness_response: randomness_blinding
+                    + randomness * challenge,
+            };
+            let masked_share = MaskedShare {
+                masked_share: share.masked_share.0,
+                commitment: share.commitment,
+                auth_tag: share.auth_tag,
+            };
+            return Ok((masked_share, proof));
+        }
+    }
+}
+
+impl<E: Pairing> MaskingBases<E> {
+    /// φ(Z, r)
+    fn image(&self, key_share: E::G2, randomness: E::ScalarField) -> Image<E> {
+        let g = E::G1Affine::generator();
+        let h = E::G2Affine::generator();
+        let key_share = key_share.into_affine();
+        Image {
+            masked_share: E::multi_pairing(
+                [
+                    self.share_base,
+                    (self.masking_key * -randomness).into_affine(),
+                ],
+                [key_share, h],
+            ),
+            key_share: E::pairing(g, key_share),
+            commitment: (g * randomness).into_affine(),
+            auth_tag: self
+                .tag_base
+                .map(|tag_base| (tag_base * randomness).into_affine()),
+        }
+    }
+}
+
+struct Image<E: Pairing> {
+    masked_share: PairingOutput<E>,
+    key_share: PairingOutput<E>,
+    commitment: E::G1Affine,
+    auth_tag: Option<E::G2Affine>,
+}
+
+impl<E: Pairing> Image<E> {
+    fn serialize_into(&self, hash_input: &mut Vec<u8>) -> Result<()> {
+        self.masked_share.serialize_compressed(&mut *hash_input)?;
+        self.key_share.serialize_compressed(&mut *hash_input)?;
+        self.commitment.serialize_compressed(&mut *hash_input)?;
+        if let Some(auth_tag) = self.auth_tag {
+            auth_tag.serialize_compressed(&mut *hash_input)?;
+        }
+        Ok(())
+    }
+}
+
+fn challenge<E: Pairing>(
+    context: &[u8],
+    bases: &MaskingBases<E>,
+    share_commitment: &ShareCommitment<E>,
+    share: &Image<E>,
+    blinding: &Image<E>,
+) -> Result<E::ScalarField> {
+    let mut hash_input = context.to_vec();
+    bases.share_base.serialize_compressed(&mut hash_input)?;
+    bases.masking_key.serialize_compressed(&mut hash_input)?;
+    if let Some(tag_base) = bases.tag_base {
+        tag_base.serialize_compressed(&mut hash_input)?;
+    }
+    share_commitment.0.serialize_compressed(&mut hash_input)?;
+    share.serialize_into(&mut hash_input)?;
+    blinding.serialize_into(&mut hash_input)?;
+    Ok(E::ScalarField::from_be_bytes_mod_order(&Sha256::digest(
+        &hash_input,
+    )))
+}
@@ -0,0 +1,514 @@
+use std::collections::HashMap;
+
+use ark_ec::pairing::Pairing;
+use chacha20poly1305::{
+    aead::{generic_array::GenericArray, Aead, KeyInit, Payload},
+    ChaCha20Poly1305,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{digest::Digest, Sha256};
+
+use crate::{
+    decrypt_with_shared_secret, encrypt, Ciphertext, CiphertextHeader,
+    DkgPublicKey, Error, HashToG2, Result, SecretBox, SharedSecret,
+};
+
+const COMPONENT_AAD_DST: &[u8] = b"FERVEO_MULTI_KEY_COMPONENT_V1";
+
+type DataKey = SecretBox<[u8; 32]>;
+
+/// Which rituals must cooperate to decrypt a `MultiKeyCiphertext`.
+/// Rituals are identified by the caller, e.g. by their on-chain ritual id.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccessPolicy {
+    Ritual(u32),
+    /// Every sub-policy must be satisfied
+    All(Vec<AccessPolicy>),
+    /// At least one sub-policy must be satisfied
+    Any(Vec<AccessPolicy>),
+}
+
+impl AccessPolicy {
+    /// Rituals in the policy, in the order of the ciphertext components
+    pub fn rituals(&self) -> Vec<u32> {
+        match self {
+            AccessPolicy::Ritual(ritual_id) => vec![*ritual_id],
+            AccessPolicy::All(policies) | AccessPolicy::Any(policies) => {
+                policies.iter().flat_map(|p| p.rituals()).collect()
+            }
+        }
+    }
+
+    fn check(&self) -> Result<()> {
+        match self {
+            AccessPolicy::Ritual(_) => Ok(()),
+            AccessPolicy::All(policies) | AccessPolicy::Any(policies) => {
+                if policies.is_empty() {
+                    return Err(Error::InvalidPolicy);
+                }
+                policies.iter().try_for_each(|p| p.check())
+            }
+        }
+    }
+}
+
+/// The data key wrapped under the public key of one ritual
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyComponent<E: Pairing> {
+    pub ritual_id: u32,
+    #[serde(bound(
+        serialize = "Ciphertext<E>: Serialize",
+        deserialize = "Ciphertext<E>: DeserializeOwned"
+    ))]
+    pub key_ciphertext: Ciphertext<E>,
+}
+
+/// A message encrypted under several `DkgPublicKey`s according to an
+/// `AccessPolicy`.
+///
+/// The data key is XOR-split across the branches of `All` and copied to the
+/// branches of `Any`. Every ritual in the policy gets one component, which is
+/// a regular ciphertext of its part of the key, so validators create
+/// decryption shares for component headers as usual.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiKeyCiphertext<E: Pairing> {
+    pub policy: AccessPolicy,
+    #[serde(bound(
+        serialize = "PolicyComponent<E>: Serialize",
+        deserialize = "PolicyComponent<E>: DeserializeOwned"
+    ))]
+    pub components: Vec<PolicyComponent<E>>,
+    pub nonce: [u8; 12],
+    #[serde(with = "serde_bytes")]
+    pub payload: Vec<u8>,
+}
+
+pub fn encrypt_with_policy<E: HashToG2>(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    policy: &AccessPolicy,
+    public_keys: &HashMap<u32, DkgPublicKey<E>>,
+    rng: &mut impl rand::Rng,
+) -> Result<MultiKeyCiphertext<E>> {
+    policy.check()?;
+    let data_key = random_key(rng);
+    let mut nonce = [0u8; 12];
+    rng.fill_bytes(&mut nonce);
+    let payload =
+        ChaCha20Poly1305::new(GenericArray::from_slice(data_key.as_secret()))
+            .encrypt(
+                GenericArray::from_slice(&nonce),
+                Payload {
+                    msg: message.as_secret(),
+                    aad,
+                },
+            )
+            .map_err(Error::SymmetricEncryptionError)?;
+
+    let mut ciphertext = MultiKeyCiphertext {
+        policy: policy.clone(),
+        components: vec![],
+        nonce,
+        payload,
+    };
+    let component_aad = ciphertext.component_aad(aad)?;
+    let mut key_parts = vec![];
+    split_key(policy, data_key, &mut key_parts, rng)?;
+    ciphertext.components = key_parts
+        .into_iter()
+        .map(|(ritual_id, key_part)| {
+            let public_key = public_keys
+                .get(&ritual_id)
+                .ok_or(Error::MissingRitualPublicKey(ritual_id))?;
+            let key_ciphertext = encrypt(
+                SecretBox::new(key_part.as_secret().to_vec()),
+                &component_aad,
+                public_key,
+                rng,
+            )?;
+            Ok(PolicyComponent {
+                ritual_id,
+                key_ciphertext,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(ciphertext)
+}
+
+impl<E: HashToG2> MultiKeyCiphertext<E> {
+    /// Header to request decryption shares for from the ritual of the
+    /// component at `index`, together with `component_aad`
+    pub fn component_header(
+        &self,
+        index: usize,
+    ) -> Result<CiphertextHeader<E>> {
+        self.components
+            .get(index)
+            .ok_or(Error::InvalidComponentIndex(index as u32))?
+            .key_ciphertext
+            .header()
+    }
+
+    /// The AAD of every component. It binds the components to the policy and
+    /// to the encrypted payload.
+    pub fn component_aad(&self, aad: &[u8]) -> Result<Vec<u8>> {
+        let mut hasher = Sha256::new();
+        hasher.update(bincode::serialize(&self.policy)?);
+        hasher.update(self.nonce);
+        hasher.update(&self.payload);
+        let mut component_aad = COMPONENT_AAD_DST.to_vec();
+        component_aad.extend_from_slice(&hasher.finalize());
+        component_aad.extend_from_slice(aad);
+        Ok(component_aad)
+    }
+
+    /// Decrypt with the shared secrets of the components at the given indices.
+    /// Fails with `Error::InvalidPolicy` if the policy is malformed or doesn't
+    /// match the components, and with `Error::PolicyNotSatisfied` if the
+    /// shared secrets don't satisfy it.
+    pub fn decrypt(
+        &self,
+        aad: &[u8],
+        shared_secrets: &HashMap<usize, SharedSecret<E>>,
+    ) -> Result<Vec<u8>> {
+        self.check_policy()?;
+        let component_aad = self.component_aad(aad)?;
+        let mut next_component = 0;
+        let data_key = self
+            .recover_key(
+                &self.policy,
+                &component_aad,
+                shared_secrets,
+                &mut next_component,
+            )?
+            .ok_or(Error::PolicyNotSatisfied)?;
+        ChaCha20Poly1305::new(GenericArray::from_slice(data_key.as_secret()))
+            .decrypt(
+                GenericArray::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.payload,
+                    aad,
+                },
+            )
+            .map_err(|_| Error::CiphertextVerificationFailed)
+    }
+
+    /// The policy of a decoded ciphertext is not trusted: it must be well
+    /// formed, and its rituals must be those of the components, in order
+    fn check_policy(&self) -> Result<()> {
+        self.policy.check()?;
+        let rituals = self.policy.rituals();
+        if rituals.len() != self.components.len()
+            || rituals
+                .iter()
+                .zip(&self.components)
+                .any(|(ritual_id, component)| *ritual_id != component.ritual_id)
+        {
+            return Err(Error::InvalidPolicy);
+        }
+        Ok(())
+    }
+
+    fn recover_key(
+        &self,
+        policy: &AccessPolicy,
+        component_aad: &[u8],
+        shared_secrets: &HashMap<usize, SharedSecret<E>>,
+        next_component: &mut usize,
+    ) -> Result<Option<DataKey>> {
+        match policy {
+            AccessPolicy::Ritual(_) => {
+                let index = *next_component;
+                *next_component += 1;
+                let shared_secret = match shared_secrets.get(&index) {
+                    Some(shared_secret) => shared_secret,
+                    None => return Ok(None),
+                };
+                let key_part = SecretBox::new(decrypt_with_shared_secret(
+                    &self.components[index].key_ciphertext,
+                    component_aad,
+                    shared_secret,
+                )?);
+                let key_part: [u8; 32] = key_part
+                    .as_secret()
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| Error::CiphertextVerificationFailed)?;
+                Ok(Some(SecretBox::new(key_part)))
+            }
+            AccessPolicy::All(policies) => {
+                let mut data_key = Some(SecretBox::new([0u8; 32]));
+                // Walk every branch to keep the component indices in step
+                for policy in policies {
+                    let key_part = self.recover_key(
+                        policy,
+                        component_aad,
+                        shared_secrets,
+                        next_component,
+                    )?;
+                    data_key = data_key.zip(key_part).map(
+                        |(mut data_key, key_part)| {
+                            xor_into(&mut data_key, &key_part);
+                            data_key
+                        },
+                    );
+                }
+                Ok(data_key)
+            }
+            AccessPolicy::Any(policies) => {
+                let mut data_key = None;
+                for policy in policies {
+                    let key = self.recover_key(
+                        policy,
+                        component_aad,
+                        shared_secrets,
+                        next_component,
+                    )?;
+                    data_key = data_key.or(key);
+                }
+                Ok(data_key)
+            }
+        }
+    }
+}
+
+/// Assign a part of `key` to every ritual in `policy`, in depth-first order
+fn split_key(
+    policy: &AccessPolicy,
+    key: DataKey,
+    key_parts: &mut Vec<(u32, DataKey)>,
+    rng: &mut impl rand::Rng,
+) -> Result<()> {
+    match policy {
+        AccessPolicy::Ritual(ritual_id) => key_parts.push((*ritual_id, key)),
+        AccessPolicy::All(policies) => {
+            let mut last_part = key;
+            let (last, rest) =
+                policies.split_last().ok_or(Error::InvalidPolicy)?;
+            for policy in rest {
+                let part = random_key(rng);
+                xor_into(&mut last_part, &part);
+                split_key(policy, part, key_parts, rng)?;
+            }
+            split_key(last, last_part, key_parts, rng)?;
+        }
+        AccessPolicy::Any(policies) => {
+            for policy in policies {
+                split_key(policy, key.clone(), key_parts, rng)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn random_key(rng: &mut impl rand::Rng) -> DataKey {
+    let mut key = SecretBox::new([0u8; 32]);
+    rng.fill_bytes(key.as_mut_secret());
+    key
+}
+
+fn xor_into(key: &mut DataKey, other: &DataKey) {
+    key.as_mut_secret()
+        .iter_mut()
+        .zip(other.as_secret())
+        .for_each(|(a, b)| *a ^= b);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ark_std::test_rng;
+
+    use crate::{
+        encrypt_with_policy, prepare_combine_simple, share_combine_simple,
+        test_common::*, AccessPolicy, DecryptionShareSimple, DkgPublicKey,
+        Error, MultiKeyCiphertext, PrivateDecryptionContextSimple, SecretBox,
+        SharedSecret,
+    };
+
+    type E = ark_bls12_381::Bls12_381;
+
+    struct Ritual {
+        public_key: DkgPublicKey<E>,
+        contexts: Vec<PrivateDecryptionContextSimple<E>>,
+        threshold: usize,
+    }
+
+    impl Ritual {
+        fn new(
+            shares_num: usize,
+            threshold: usize,
+            rng: &mut impl rand::Rng,
+        ) -> Self {
+            let (public_key, _, contexts) =
+                setup_simple::<E>(shares_num, threshold, rng);
+            Ritual {
+                public_key,
+                contexts,
+                threshold,
+            }
+        }
+
+        /// Unmodified validators decrypting one component header
+        fn decrypt(
+            &self,
+            ciphertext: &MultiKeyCiphertext<E>,
+            index: usize,
+            aad: &[u8],
+        ) -> SharedSecret<E> {
+            let header = ciphertext.component_header(index).unwrap();
+            let component_aad = ciphertext.component_aad(aad).unwrap();
+            let contexts = &self.contexts[..self.threshold];
+            let shares = contexts
+                .iter()
+                .map(|context| {
+                    DecryptionShareSimple::create(
+                        &context.setup_params.b,
+                        &context.private_key_share,
+                        &header,
+                        &component_aad,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let domain_points = contexts
+                .iter()
+                .map(|context| {
+                    context.public_decryption_contexts[context.index].domain
+                })
+                .collect::<Vec<_>>();
+            share_combine_simple::<E>(
+                &shares,
+                &prepare_combine_simple::<E>(&domain_points),
+            )
+        }
+    }
+
+    #[test]
+    fn test_multi_key_policies() {
+        let rng = &mut test_rng();
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let rituals = HashMap::from([
+            (1, Ritual::new(4, 3, rng)),
+            (2, Ritual::new(8, 5, rng)),
+            (3, Ritual::new(4, 2, rng)),
+        ]);
+        let public_keys = rituals
+            .iter()
+            .map(|(ritual_id, ritual)| (*ritual_id, ritual.public_key))
+            .collect::<HashMap<_, _>>();
+
+        // 1 AND (2 OR 3)
+        let policy = AccessPolicy::All(vec![
+            AccessPolicy::Ritual(1),
+            AccessPolicy::Any(vec![
+                AccessPolicy::Ritual(2),
+                AccessPolicy::Ritual(3),
+            ]),
+        ]);
+        assert_eq!(policy.rituals(), vec![1, 2, 3]);
+        let ciphertext = encrypt_with_policy(
+            SecretBox::new(msg.clone()),
+            aad,
+            &policy,
+            &public_keys,
+            rng,
+        )
+        .unwrap();
+
+        let shared_secrets = ciphertext
+            .components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| {
+                let ritual = &rituals[&component.ritual_id];
+                (index, ritual.decrypt(&ciphertext, index, aad))
+            })
+            .collect::<HashMap<_, _>>();
+        let with_components = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|index| (*index, shared_secrets[index].clone()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        for satisfying in [&[0, 1][..], &[0, 2], &[0, 1, 2]] {
+            let plaintext = ciphertext
+                .decrypt(aad, &with_components(satisfying))
+                .unwrap();
+            assert_eq!(plaintext, msg);
+        }
+        for unsatisfying in [&[0][..], &[1, 2], &[]] {
+            assert!(matches!(
+                ciphertext.decrypt(aad, &with_components(unsatisfying)),
+                Err(Error::PolicyNotSatisfied)
+            ));
+        }
+
+        // The policy can't be changed without invalidating the components
+        let mut weakened = ciphertext.clone();
+        weakened.policy = AccessPolicy::Any(vec![
+            AccessPolicy::Ritual(1),
+            AccessPolicy::Ritual(2),
+            AccessPolicy::Ritual(3),
+        ]);
+        assert!(weakened.decrypt(aad, &with_components(&[0])).is_err());
+        assert!(weakened
+            .component_header(0)
+            .unwrap()
+            .check(&weakened.component_aad(aad).unwrap())
+            .is_err());
+
+        // Decoded policies are checked before recovering the key
+        let mut malformed = ciphertext.clone();
+        malformed.policy = AccessPolicy::All(vec![]);
+        malformed.components.clear();
+        assert!(matches!(
+            malformed.decrypt(aad, &HashMap::new()),
+            Err(Error::InvalidPolicy)
+        ));
+        let mut reordered = ciphertext.clone();
+        reordered.components.swap(1, 2);
+        assert!(matches!(
+            reordered.decrypt(aad, &shared_secrets),
+            Err(Error::InvalidPolicy)
+        ));
+    }
+
+    #[test]
+    fn test_multi_key_invalid_policies() {
+        let rng = &mut test_rng();
+        let ritual = Ritual::new(4, 3, rng);
+        let public_keys = HashMap::from([(1, ritual.public_key)]);
+        for policy in [
+            AccessPolicy::All(vec![]),
+            AccessPolicy::Any(vec![
+                AccessPolicy::Ritual(1),
+                AccessPolicy::All(vec![]),
+            ]),
+        ] {
+            assert!(matches!(
+                encrypt_with_policy(
+                    SecretBox::new(vec![]),
+                    &[],
+                    &policy,
+                    &public_keys,
+                    rng
+                ),
+                Err(Error::InvalidPolicy)
+            ));
+        }
+        assert!(matches!(
+            encrypt_with_policy(
+                SecretBox::new(vec![]),
+                &[],
+                &AccessPolicy::Ritual(2),
+                &public_keys,
+                rng
+            ),
+            Err(Error::MissingRitualPublicKey(2))
+        ));
+    }
+}
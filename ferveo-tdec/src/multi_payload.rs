@@ -0,0 +1,420 @@
+use std::ops::Mul;
+
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::UniformRand;
+use ark_serialize::CanonicalSerialize;
+use chacha20poly1305::aead::{Aead, Payload};
+use ferveo_common::serialization;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{digest::Digest, Sha256};
+
+use crate::{
+    construct_tag_hash, shared_secret_to_chacha, CiphertextHeader,
+    DkgPublicKey, Error, HashToG2, Nonce, Result, SecretBox, SharedSecret,
+};
+
+const MERKLE_LEAF_PREFIX: u8 = 0;
+const MERKLE_NODE_PREFIX: u8 = 1;
+
+/// A single payload of a `MultiPayloadCiphertext`.
+/// Each payload is encrypted with its own nonce and AAD.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedPayload {
+    pub index: u32,
+    #[serde(with = "serde_bytes")]
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedPayload {
+    fn leaf_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update([MERKLE_LEAF_PREFIX]);
+        hasher.update(self.index.to_be_bytes());
+        hasher.update(&self.ciphertext);
+        hasher.finalize().into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerklePathNode {
+    pub hash: [u8; 32],
+    /// Whether this node is the left sibling
+    pub is_left: bool,
+}
+
+/// Merkle path from a payload to the root committed to by the ciphertext header
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayloadProof {
+    pub path: Vec<MerklePathNode>,
+}
+
+impl PayloadProof {
+    pub fn verify(&self, payload: &EncryptedPayload, root: &[u8; 32]) -> bool {
+        let computed_root =
+            self.path.iter().fold(payload.leaf_hash(), |acc, node| {
+                if node.is_left {
+                    hash_nodes(&node.hash, &acc)
+                } else {
+                    hash_nodes(&acc, &node.hash)
+                }
+            });
+        computed_root == *root
+    }
+}
+
+/// Many payloads protected by a single threshold header (U, W).
+/// The auth tag is bound to the Merkle root of the payload hashes, so one set
+/// of decryption shares yields a `SharedSecret` that can decrypt every payload.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiPayloadCiphertext<E: Pairing> {
+    // U
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub commitment: E::G1Affine,
+
+    // W
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub auth_tag: E::G2Affine,
+
+    pub payloads: Vec<EncryptedPayload>,
+}
+
+impl<E: HashToG2> MultiPayloadCiphertext<E> {
+    pub fn check(&self, aad: &[u8]) -> Result<bool> {
+        self.header()?.check(aad)
+    }
+
+    /// Merkle root of the payload hashes
+    pub fn payloads_root(&self) -> Result<[u8; 32]> {
+        merkle_root(&self.leaf_hashes())
+    }
+
+    /// The header is the same as for a single-payload `Ciphertext`, with the
+    /// Merkle root in place of the ciphertext hash. Decryption shares are
+    /// created from it as usual.
+    pub fn header(&self) -> Result<CiphertextHeader<E>> {
+        Ok(CiphertextHeader {
+            commitment: self.commitment,
+            auth_tag: self.auth_tag,
+            ciphertext_hash: self.payloads_root()?,
+        })
+    }
+
+    pub fn payload(&self, index: u32) -> Result<&EncryptedPayload> {
+        self.payloads
+            .get(index as usize)
+            .filter(|payload| payload.index == index)
+            .ok_or(Error::InvalidPayloadIndex(index))
+    }
+
+    /// Proof that the payload at `index` is bound to this ciphertext's header
+    pub fn payload_proof(&self, index: u32) -> Result<PayloadProof> {
+        self.payload(index)?;
+        let mut level = self.leaf_hashes();
+        let mut position = index as usize;
+        let mut path = vec![];
+        while level.len() > 1 {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                path.push(MerklePathNode {
+                    hash: level[sibling],
+                    is_left: sibling < position,
+                });
+            }
+            level = next_level(&level);
+            position /= 2;
+        }
+        Ok(PayloadProof { path })
+    }
+
+    /// Decrypt a single payload, checking the header against `aad` first
+    pub fn decrypt_payload(
+        &self,
+        index: u32,
+        aad: &[u8],
+        payload_aad: &[u8],
+        shared_secret: &SharedSecret<E>,
+    ) -> Result<Vec<u8>> {
+        self.check(aad)?;
+        decrypt_payload_unchecked(
+            &self.commitment,
+            self.payload(index)?,
+            payload_aad,
+            shared_secret,
+        )
+    }
+
+    fn leaf_hashes(&self) -> Vec<[u8; 32]> {
+        self.payloads.iter().map(|p| p.leaf_hash()).collect()
+    }
+}
+
+/// Encrypt many payloads under a single threshold header.
+/// `aad` is bound to the header, while each payload has its own AAD.
+pub fn encrypt_payloads<E: HashToG2>(
+    payloads: &[(SecretBox<Vec<u8>>, &[u8])],
+    aad: &[u8],
+    pubkey: &DkgPublicKey<E>,
+    rng: &mut impl rand::Rng,
+) -> Result<MultiPayloadCiphertext<E>> {
+    if payloads.is_empty() {
+        return Err(Error::NoPayloads);
+    }
+    // r
+    let rand_element = E::ScalarField::rand(rng);
+    // s
+    let product = E::pairing(
+        E::G1Prepared::from(pubkey.0.mul(rand_element).into()),
+        E::G2Affine::generator(),
+    )
+    .0;
+    // u
+    let commitment: E::G1Affine =
+        E::G1Affine::generator().mul(rand_element).into();
+
+    let chacha = shared_secret_to_chacha(&SharedSecret::<E>(product))?;
+    let payloads = payloads
+        .iter()
+        .enumerate()
+        .map(|(index, (message, payload_aad))| {
+            let index = index as u32;
+            let nonce =
+                Nonce::from_commitment_and_index::<E>(commitment, index)?;
+            let payload = Payload {
+                msg: message.as_secret().as_ref(),
+                aad: payload_aad,
+            };
+            let ciphertext = chacha
+                .encrypt(&nonce.0, payload)
+                .map_err(Error::SymmetricEncryptionError)?;
+            Ok(EncryptedPayload { index, ciphertext })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let leaf_hashes =
+        payloads.iter().map(|p| p.leaf_hash()).collect::<Vec<_>>();
+    let payloads_root = merkle_root(&leaf_hashes)?;
+
+    // w
+    let auth_tag = construct_tag_hash::<E>(commitment, &payloads_root, aad)?
+        .mul(rand_element)
+        .into();
+
+    Ok(MultiPayloadCiphertext {
+        commitment,
+        auth_tag,
+        payloads,
+    })
+}
+
+/// Decrypt a payload stored apart from the other payloads of its ciphertext.
+/// The payload is checked against the header with its Merkle proof.
+pub fn decrypt_payload_with_proof<E: HashToG2>(
+    ciphertext_header: &CiphertextHeader<E>,
+    aad: &[u8],
+    payload: &EncryptedPayload,
+    proof: &PayloadProof,
+    payload_aad: &[u8],
+    shared_secret: &SharedSecret<E>,
+) -> Result<Vec<u8>> {
+    if !proof.verify(payload, &ciphertext_header.ciphertext_hash) {
+        return Err(Error::InvalidPayloadProof);
+    }
+    ciphertext_header.check(aad)?;
+    decrypt_payload_unchecked(
+        &ciphertext_header.commitment,
+        payload,
+        payload_aad,
+        shared_secret,
+    )
+}
+
+fn decrypt_payload_unchecked<E: Pairing>(
+    commitment: &E::G1Affine,
+    payload: &EncryptedPayload,
+    payload_aad: &[u8],
+    shared_secret: &SharedSecret<E>,
+) -> Result<Vec<u8>> {
+    let nonce =
+        Nonce::from_commitment_and_index::<E>(*commitment, payload.index)?;
+    let payload = Payload {
+        msg: payload.ciphertext.as_ref(),
+        aad: payload_aad,
+    };
+    shared_secret_to_chacha(shared_secret)?
+        .decrypt(&nonce.0, payload)
+        .map_err(|_| Error::CiphertextVerificationFailed)
+}
+
+fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([MERKLE_NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+// An odd node out is promoted to the next level as is
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_nodes(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks of two are never empty"),
+        })
+        .collect()
+}
+
+fn merkle_root(leaves: &[[u8; 32]]) -> Result<[u8; 32]> {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied().ok_or(Error::NoPayloads)
+}
+
+impl Nonce {
+    pub fn from_commitment_and_index<E: Pairing>(
+        commitment: E::G1Affine,
+        index: u32,
+    ) -> Result<Self> {
+        let mut hash_input = Vec::new();
+        commitment.serialize_compressed(&mut hash_input)?;
+        hash_input.extend_from_slice(&index.to_be_bytes());
+        let hash: [u8; 32] = Sha256::digest(&hash_input).into();
+        Ok(Nonce(*chacha20poly1305::Nonce::from_slice(&hash[..12])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+
+    use crate::{test_common::*, *};
+
+    type E = ark_bls12_381::Bls12_381;
+
+    fn make_payloads(n: usize) -> Vec<(SecretBox<Vec<u8>>, Vec<u8>)> {
+        (0..n)
+            .map(|i| {
+                (
+                    SecretBox::new(format!("my-msg-{i}").into_bytes()),
+                    format!("my-payload-aad-{i}").into_bytes(),
+                )
+            })
+            .collect()
+    }
+
+    fn encrypt_test_payloads(
+        payloads: &[(SecretBox<Vec<u8>>, Vec<u8>)],
+        aad: &[u8],
+        pubkey: &DkgPublicKey<E>,
+    ) -> MultiPayloadCiphertext<E> {
+        let rng = &mut test_rng();
+        let payloads = payloads
+            .iter()
+            .map(|(msg, payload_aad)| {
+                (
+                    SecretBox::new(msg.as_secret().clone()),
+                    payload_aad.as_ref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        encrypt_payloads::<E>(&payloads, aad, pubkey, rng).unwrap()
+    }
+
+    #[test]
+    fn multi_payload_threshold_decryption() {
+        let rng = &mut test_rng();
+        let shares_num = 8;
+        let threshold = 5;
+        let aad: &[u8] = "my-aad".as_bytes();
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+
+        for payloads_num in [1, 2, 5, 8] {
+            let payloads = make_payloads(payloads_num);
+            let ciphertext = encrypt_test_payloads(&payloads, aad, &pubkey);
+            let header = ciphertext.header().unwrap();
+
+            // A single decryption round over the shared header
+            let decryption_shares = contexts[..threshold]
+                .iter()
+                .map(|c| c.create_share(&header, aad).unwrap())
+                .collect::<Vec<_>>();
+            let shared_secret = create_shared_secret_simple(
+                &contexts[0].public_decryption_contexts[..threshold],
+                &decryption_shares,
+            );
+
+            for (index, (msg, payload_aad)) in payloads.iter().enumerate() {
+                let index = index as u32;
+                let plaintext = ciphertext
+                    .decrypt_payload(index, aad, payload_aad, &shared_secret)
+                    .unwrap();
+                assert_eq!(&plaintext, msg.as_secret());
+
+                // Payloads can also be stored and decrypted independently
+                let payload = ciphertext.payload(index).unwrap();
+                let proof = ciphertext.payload_proof(index).unwrap();
+                let plaintext = decrypt_payload_with_proof(
+                    &header,
+                    aad,
+                    payload,
+                    &proof,
+                    payload_aad,
+                    &shared_secret,
+                )
+                .unwrap();
+                assert_eq!(&plaintext, msg.as_secret());
+
+                // Payload AAD is bound to the payload
+                assert!(ciphertext
+                    .decrypt_payload(index, aad, b"bad-aad", &shared_secret)
+                    .is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn multi_payload_tag_is_bound_to_all_payloads() {
+        let rng = &mut test_rng();
+        let aad: &[u8] = "my-aad".as_bytes();
+        let (pubkey, _, _) = setup_simple::<E>(4, 3, rng);
+        let payloads = make_payloads(5);
+        let ciphertext = encrypt_test_payloads(&payloads, aad, &pubkey);
+        assert!(ciphertext.check(aad).is_ok());
+        assert!(ciphertext.check(b"bad-aad").is_err());
+
+        // Tampering with any payload invalidates the header
+        let mut tampered = ciphertext.clone();
+        tampered.payloads[3].ciphertext[0] ^= 1;
+        assert!(tampered.check(aad).is_err());
+
+        // Dropping or reordering payloads invalidates the header
+        let mut truncated = ciphertext.clone();
+        truncated.payloads.pop();
+        assert!(truncated.check(aad).is_err());
+        let mut reordered = ciphertext.clone();
+        reordered.payloads.swap(0, 1);
+        assert!(reordered.check(aad).is_err());
+
+        // A proof doesn't verify for a different payload
+        let header = ciphertext.header().unwrap();
+        let proof = ciphertext.payload_proof(0).unwrap();
+        assert!(proof.verify(&ciphertext.payloads[0], &header.ciphertext_hash));
+        assert!(!proof.verify(&ciphertext.payloads[1], &header.ciphertext_hash));
+        assert!(!proof.verify(&tampered.payloads[3], &header.ciphertext_hash));
+
+        assert!(matches!(
+            ciphertext.payload(5),
+            Err(Error::InvalidPayloadIndex(5))
+        ));
+        assert!(matches!(
+            encrypt_payloads::<E>(&[], aad, &pubkey, rng),
+            Err(Error::NoPayloads)
+        ));
+    }
+}
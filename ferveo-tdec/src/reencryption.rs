@@ -0,0 +1,443 @@
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use ferveo_common::serialization;
+use itertools::izip;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    CiphertextHeader, DecryptionShareSimple, DkgPublicKey, Error, HashToG2,
+    MaskedShare, MaskingBases, MaskingProof, PrivateKeyShare, Result,
+    ShareCommitment, SharedSecret,
+};
+
+const REENCRYPTION_TAG_DST: &[u8] = b"FERVEO_REENCRYPTION_TAG";
+const REENCRYPTION_PROOF_DST: &[u8] = b"FERVEO_REENCRYPTION_PROOF";
+
+/// A validator's contribution to re-encrypting a ciphertext header to the
+/// public key `P'` of another ritual.
+///
+/// The decryption share `e(U, Z_i)` is masked with `e(P', H)^{-r_i}`, so that
+/// neither the share nor the combined shared secret is revealed.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReEncryptionShare<E: Pairing> {
+    /// `[r_i] G`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub commitment: E::G1Affine,
+    /// `[r_i] H'`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub auth_tag: E::G2Affine,
+    /// `e(U, Z_i) * e(P', H)^{-r_i}`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub masked_share: E::TargetField,
+    #[serde(bound(
+        serialize = "MaskingProof<E>: Serialize",
+        deserialize = "MaskingProof<E>: DeserializeOwned"
+    ))]
+    pub proof: MaskingProof<E>,
+}
+
+impl<E: HashToG2> ReEncryptionShare<E> {
+    /// Create a re-encryption share of `ciphertext_header` towards
+    /// `target_public_key`. This function checks that the ciphertext is valid.
+    pub fn create<R: rand::RngCore>(
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        private_key_share: &PrivateKeyShare<E>,
+        share_commitment: &ShareCommitment<E>,
+        target_public_key: &DkgPublicKey<E>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        ciphertext_header.check(aad)?;
+        let bases =
+            reencryption_bases(ciphertext_header, aad, target_public_key)?;
+        let (masked_share, proof) = MaskingProof::create(
+            REENCRYPTION_PROOF_DST,
+            &bases,
+            private_key_share,
+            share_commitment,
+            rng,
+        )?;
+        Ok(Self {
+            commitment: masked_share.commitment,
+            auth_tag: masked_share
+                .auth_tag
+                .ok_or(Error::MissingReEncryptionTag)?,
+            masked_share: masked_share.masked_share,
+            proof,
+        })
+    }
+
+    /// Verify the re-encryption share against the share commitment `A_i` of
+    /// the validator that produced it.
+    pub fn verify(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        share_commitment: &ShareCommitment<E>,
+        target_public_key: &DkgPublicKey<E>,
+    ) -> Result<bool> {
+        let bases =
+            reencryption_bases(ciphertext_header, aad, target_public_key)?;
+        let masked_share = MaskedShare {
+            masked_share: self.masked_share,
+            commitment: self.commitment,
+            auth_tag: Some(self.auth_tag),
+        };
+        self.proof.verify(
+            REENCRYPTION_PROOF_DST,
+            &bases,
+            share_commitment,
+            &masked_share,
+        )
+    }
+}
+
+/// A ciphertext header re-encrypted to the public key `P'` of another ritual.
+///
+/// The target ritual decrypts `commitment` as if it were a regular ciphertext
+/// header, and the resulting shared secret is unmasked with `masked_secret`
+/// to recover the shared secret of the original header.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReEncryptedHeader<E: Pairing> {
+    #[serde(bound(
+        serialize = "CiphertextHeader<E>: Serialize",
+        deserialize = "CiphertextHeader<E>: DeserializeOwned"
+    ))]
+    pub header: CiphertextHeader<E>,
+    #[serde(bound(
+        serialize = "DkgPublicKey<E>: Serialize",
+        deserialize = "DkgPublicKey<E>: DeserializeOwned"
+    ))]
+    pub target_public_key: DkgPublicKey<E>,
+    /// `[r'] G`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub commitment: E::G1Affine,
+    /// `[r'] H'`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub auth_tag: E::G2Affine,
+    /// `S * e(P', H)^{-r'}`, where `S` is the original shared secret
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub masked_secret: E::TargetField,
+}
+
+impl<E: HashToG2> ReEncryptedHeader<E> {
+    /// Checks both the original header and the re-encrypted commitment
+    pub fn check(&self, aad: &[u8]) -> Result<bool> {
+        self.header.check(aad)?;
+
+        let tag_hash =
+            reencryption_tag_hash(&self.header, aad, &self.target_public_key)?;
+        // e(U', H') == e(G, W')
+        let g_inv = -E::G1Affine::generator().into_group();
+        let is_valid = E::multi_pairing(
+            [self.commitment, g_inv.into_affine()],
+            [tag_hash, self.auth_tag],
+        )
+        .is_zero();
+        if is_valid {
+            Ok(true)
+        } else {
+            Err(Error::CiphertextVerificationFailed)
+        }
+    }
+
+    /// Make a decryption share (simple variant) with a key share of the
+    /// target ritual
+    pub fn create_decryption_share_simple(
+        &self,
+        aad: &[u8],
+        validator_decryption_key: &E::ScalarField,
+        private_key_share: &PrivateKeyShare<E>,
+    ) -> Result<DecryptionShareSimple<E>> {
+        self.check(aad)?;
+        DecryptionShareSimple::create_unchecked(
+            validator_decryption_key,
+            private_key_share,
+            &self.target_header(),
+        )
+    }
+
+    /// Recovers the shared secret of the original ciphertext from the shared
+    /// secret combined by the target ritual
+    pub fn unmask_shared_secret(
+        &self,
+        target_shared_secret: &SharedSecret<E>,
+    ) -> SharedSecret<E> {
+        SharedSecret(self.masked_secret * target_shared_secret.0)
+    }
+
+    /// The re-encrypted commitment, as a header of the target ritual
+    fn target_header(&self) -> CiphertextHeader<E> {
+        CiphertextHeader {
+            commitment: self.commitment,
+            auth_tag: self.auth_tag,
+            ciphertext_hash: self.header.ciphertext_hash,
+        }
+    }
+}
+
+/// Combine re-encryption shares (simple variant) into a re-encrypted header.
+/// The shares are not verified here; use `ReEncryptionShare::verify` first.
+pub fn combine_reencryption_shares<E: Pairing>(
+    ciphertext_header: &CiphertextHeader<E>,
+    target_public_key: &DkgPublicKey<E>,
+    shares: &[ReEncryptionShare<E>],
+    lagrange_coeffs: &[E::ScalarField],
+) -> ReEncryptedHeader<E> {
+    let mut commitment = E::G1::zero();
+    let mut auth_tag = E::G2::zero();
+    let mut masked_secret = E::TargetField::ONE;
+    for (share, lagrange_coeff) in izip!(shares, lagrange_coeffs) {
+        commitment += share.commitment * lagrange_coeff;
+        auth_tag += share.auth_tag * lagrange_coeff;
+        masked_secret *= share.masked_share.pow(lagrange_coeff.into_bigint());
+    }
+    ReEncryptedHeader {
+        header: ciphertext_header.clone(),
+        target_public_key: *target_public_key,
+        commitment: commitment.into_affine(),
+        auth_tag: auth_tag.into_affine(),
+        masked_secret,
+    }
+}
+
+/// H' = H_G2(U, W, sym_ctxt_digest, aad, P')
+fn reencryption_tag_hash<E: HashToG2>(
+    ciphertext_header: &CiphertextHeader<E>,
+    aad: &[u8],
+    target_public_key: &DkgPublicKey<E>,
+) -> Result<E::G2Affine> {
+    let mut hash_input = REENCRYPTION_TAG_DST.to_vec();
+    ciphertext_header
+        .commitment
+        .serialize_compressed(&mut hash_input)?;
+    ciphertext_header
+        .auth_tag
+        .serialize_compressed(&mut hash_input)?;
+    hash_input.extend_from_slice(&ciphertext_header.ciphertext_hash);
+    hash_input.extend_from_slice(aad);
+    target_public_key.0.serialize_compressed(&mut hash_input)?;
+    E::hash_to_g2(&hash_input)
+}
+
+fn reencryption_bases<E: HashToG2>(
+    ciphertext_header: &CiphertextHeader<E>,
+    aad: &[u8],
+    target_public_key: &DkgPublicKey<E>,
+) -> Result<MaskingBases<E>> {
+    Ok(MaskingBases {
+        share_base: ciphertext_header.commitment,
+        masking_key: target_public_key.0,
+        tag_base: Some(reencryption_tag_hash(
+            ciphertext_header,
+            aad,
+            target_public_key,
+        )?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+
+    use super::{reencryption_bases, REENCRYPTION_PROOF_DST};
+    use crate::{
+        combine_reencryption_shares, decrypt_with_shared_secret, encrypt,
+        prepare_combine_simple, share_combine_simple, test_common::*,
+        MaskingProof, ReEncryptionShare, SecretBox,
+    };
+
+    type E = ark_bls12_381::Bls12_381;
+
+    #[test]
+    fn test_reencryption_to_new_ritual() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (8, 5);
+        let (new_shares_num, new_threshold) = (6, 4);
+        let msg = "my-msg".as_bytes().to_vec();
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let (new_pubkey, _, new_contexts) =
+            setup_simple::<E>(new_shares_num, new_threshold, rng);
+        let ciphertext =
+            encrypt::<E>(SecretBox::new(msg.clone()), aad, &pubkey, rng)
+                .unwrap();
+        let header = ciphertext.header().unwrap();
+
+        // The old committee re-encrypts the header to the new ritual
+        let public_contexts = &contexts[0].public_decryption_contexts;
+        let reencryption_shares = contexts[..threshold]
+            .iter()
+            .map(|context| {
+                let share_commitment =
+                    &public_contexts[context.index].share_commitment;
+                let share = ReEncryptionShare::create(
+                    &header,
+                    aad,
+                    &context.private_key_share,
+                    share_commitment,
+                    &new_pubkey,
+                    rng,
+                )
+                .unwrap();
+                assert!(share
+                    .verify(&header, aad, share_commitment, &new_pubkey)
+                    .unwrap());
+                share
+            })
+            .collect::<Vec<_>>();
+        let domain_points = public_contexts[..threshold]
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let reencrypted_header = combine_reencryption_shares(
+            &header,
+            &new_pubkey,
+            &reencryption_shares,
+            &prepare_combine_simple::<E>(&domain_points),
+        );
+        assert!(reencrypted_header.check(aad).unwrap());
+        assert!(reencrypted_header.check("bad-aad".as_bytes()).is_err());
+
+        // The new committee decrypts the re-encrypted header
+        let decryption_shares = new_contexts[..new_threshold]
+            .iter()
+            .map(|context| {
+                reencrypted_header
+                    .create_decryption_share_simple(
+                        aad,
+                        &context.setup_params.b,
+                        &context.private_key_share,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let new_domain_points = new_contexts[0].public_decryption_contexts
+            [..new_threshold]
+            .iter()
+            .map(|ctxt| ctxt.domain)
+            .collect::<Vec<_>>();
+        let target_shared_secret = share_combine_simple::<E>(
+            &decryption_shares,
+            &prepare_combine_simple::<E>(&new_domain_points),
+        );
+        let shared_secret =
+            reencrypted_header.unmask_shared_secret(&target_shared_secret);
+
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, aad, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, msg);
+    }
+
+    #[test]
+    fn test_reencryption_share_verification() {
+        let rng = &mut test_rng();
+        let (shares_num, threshold) = (4, 3);
+        let aad: &[u8] = "my-aad".as_bytes();
+
+        let (pubkey, _, contexts) =
+            setup_simple::<E>(shares_num, threshold, rng);
+        let (new_pubkey, _, _) = setup_simple::<E>(shares_num, threshold, rng);
+        let ciphertext = encrypt::<E>(
+            SecretBox::new("my-msg".as_bytes().to_vec()),
+            aad,
+            &pubkey,
+            rng,
+        )
+        .unwrap();
+        let header = ciphertext.header().unwrap();
+
+        let public_contexts = &contexts[0].public_decryption_contexts;
+        let share_commitment = &public_contexts[0].share_commitment;
+        let share = ReEncryptionShare::create(
+            &header,
+            aad,
+            &contexts[0].private_key_share,
+            share_commitment,
+            &new_pubkey,
+            rng,
+        )
+        .unwrap();
+
+        // Share commitment of another validator
+        assert!(!share
+            .verify(
+                &header,
+                aad,
+                &public_contexts[1].share_commitment,
+                &new_pubkey
+            )
+            .unwrap());
+        // Different target ritual
+        assert!(!share
+            .verify(&header, aad, share_commitment, &pubkey)
+            .unwrap());
+        // Different aad
+        assert!(!share
+            .verify(
+                &header,
+                "bad-aad".as_bytes(),
+                share_commitment,
+                &new_pubkey
+            )
+            .unwrap());
+
+        // Tampered masked share
+        let mut bad_share = share.clone();
+        bad_share.masked_share *= bad_share.masked_share;
+        assert!(!bad_share
+            .verify(&header, aad, share_commitment, &new_pubkey)
+            .unwrap());
+
+        // Masked share moved out of GT by a -1 factor
+        let (masked_share, proof) = MaskingProof::create_negated(
+            REENCRYPTION_PROOF_DST,
+            &reencryption_bases(&header, aad, &new_pubkey).unwrap(),
+            &contexts[0].private_key_share,
+            share_commitment,
+            rng,
+        )
+        .unwrap();
+        let bad_share = ReEncryptionShare {
+            commitment: masked_share.commitment,
+            auth_tag: masked_share.auth_tag.unwrap(),
+            masked_share: masked_share.masked_share,
+            proof,
+        };
+        assert!(!bad_share
+            .verify(&header, aad, share_commitment, &new_pubkey)
+            .unwrap());
+
+        // Share made with a key share that doesn't match its commitment
+        let bad_share = ReEncryptionShare::create(
+            &header,
+            aad,
+            &contexts[1].private_key_share,
+            share_commitment,
+            &new_pubkey,
+            rng,
+        )
+        .unwrap();
+        assert!(!bad_share
+            .verify(&header, aad, share_commitment, &new_pubkey)
+            .unwrap());
+
+        // Invalid ciphertext headers are rejected
+        assert!(ReEncryptionShare::create(
+            &header,
+            "bad-aad".as_bytes(),
+            &contexts[0].private_key_share,
+            share_commitment,
+            &new_pubkey,
+            rng,
+        )
+        .is_err());
+    }
+}
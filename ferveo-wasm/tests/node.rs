@@ -189,7 +189,7 @@ fn tdec_precomputed() {
                 assert!(server_aggregate
                     .verify(validators_num, &messages_js)
                     .unwrap());
-                server_aggregate
+                let decryption_share = server_aggregate
                     .create_decryption_share_precomputed(
                         &dkg,
                         &ciphertext.header().unwrap(),
@@ -197,7 +197,20 @@ fn tdec_precomputed() {
                         &keypair,
                         &selected_validators_js,
                     )
-                    .unwrap()
+                    .unwrap();
+
+                // The share is the same for an already checked header
+                let verified_header = ciphertext.verify(&aad).unwrap();
+                let verified_share = server_aggregate
+                    .create_decryption_share_precomputed_verified(
+                        &dkg,
+                        &verified_header,
+                        &keypair,
+                        &selected_validators_js,
+                    )
+                    .unwrap();
+                assert!(verified_share.equals(&decryption_share));
+                decryption_share
             })
             // We need `security_threshold` decryption shares to decrypt
             .take(security_threshold as usize)
@@ -215,3 +228,255 @@ fn tdec_precomputed() {
         assert_eq!(msg, plaintext);
     }
 }
+
+#[wasm_bindgen_test]
+fn tdec_simple_with_encryption_key() {
+    let shares_num = 4;
+    let security_threshold = 3;
+    let validators_num = shares_num;
+    let (
+        validator_keypairs,
+        validators,
+        validators_js,
+        messages_js,
+        msg,
+        aad,
+        _,
+    ) = setup_dkg(shares_num, validators_num, security_threshold);
+
+    // The client caches the encryption key and uses it for encryption
+    let client_aggregate = AggregatedTranscript::new(&messages_js).unwrap();
+    let encryption_key = EncryptionKey::new(&client_aggregate.public_key());
+    let encryption_key =
+        EncryptionKey::from_bytes(&encryption_key.to_bytes().unwrap()).unwrap();
+    assert!(encryption_key
+        .public_key()
+        .equals(&client_aggregate.public_key()));
+    let ciphertext =
+        ferveo_encrypt_with_key(&msg, &aad, &encryption_key).unwrap();
+
+    let decryption_shares = zip_eq(validators, validator_keypairs)
+        .map(|(validator, keypair)| {
+            let mut dkg = Dkg::new(
+                TAU,
+                shares_num,
+                security_threshold,
+                &validators_js,
+                &validator,
+            )
+            .unwrap();
+            let aggregate = dkg.aggregate_transcripts(&messages_js).unwrap();
+            aggregate
+                .create_decryption_share_simple(
+                    &dkg,
+                    &ciphertext.header().unwrap(),
+                    &aad,
+                    &keypair,
+                )
+                .unwrap()
+        })
+        .take(security_threshold as usize)
+        .collect::<Vec<DecryptionShareSimple>>();
+    let decryption_shares_js = into_js_array(decryption_shares);
+
+    let shared_secret =
+        combine_decryption_shares_simple(&decryption_shares_js).unwrap();
+    let plaintext =
+        decrypt_with_shared_secret(&ciphertext, &aad, &shared_secret).unwrap();
+    assert_eq!(msg, plaintext);
+}
+
+#[wasm_bindgen_test]
+fn tdec_simple_with_blinded_shares() {
+    let shares_num = 4;
+    let security_threshold = 3;
+    let validators_num = shares_num;
+    let (
+        validator_keypairs,
+        validators,
+        validators_js,
+        messages_js,
+        msg,
+        aad,
+        ciphertext,
+    ) = setup_dkg(shares_num, validators_num, security_threshold);
+    let header = ciphertext.header().unwrap();
+
+    // The requester sends an ephemeral public key with the decryption request
+    let requester_keypair = RequesterKeypair::random();
+    let requester_public_key = requester_keypair.public_key();
+
+    let client_aggregate = AggregatedTranscript::new(&messages_js).unwrap();
+    let decryption_shares = zip_eq(validators, validator_keypairs)
+        .map(|(validator, keypair)| {
+            let mut dkg = Dkg::new(
+                TAU,
+                shares_num,
+                security_threshold,
+                &validators_js,
+                &validator,
+            )
+            .unwrap();
+            let aggregate = dkg.aggregate_transcripts(&messages_js).unwrap();
+            let decryption_share = aggregate
+                .create_blinded_decryption_share_simple(
+                    &dkg,
+                    &header,
+                    &aad,
+                    &keypair,
+                    &requester_public_key,
+                )
+                .unwrap();
+            assert!(client_aggregate
+                .verify_blinded_decryption_share_simple(
+                    &decryption_share,
+                    &header,
+                    &requester_public_key,
+                )
+                .unwrap());
+            decryption_share
+        })
+        .take(security_threshold as usize)
+        .collect::<Vec<BlindedDecryptionShareSimple>>();
+    let decryption_shares_js = into_js_array(decryption_shares);
+
+    let shared_secret = combine_blinded_decryption_shares_simple(
+        &decryption_shares_js,
+        &requester_keypair,
+    )
+    .unwrap();
+    let plaintext =
+        decrypt_with_shared_secret(&ciphertext, &aad, &shared_secret).unwrap();
+    assert_eq!(msg, plaintext);
+}
+
+#[wasm_bindgen_test]
+fn tdec_simple_with_share_proofs() {
+    let shares_num = 4;
+    let security_threshold = 3;
+    let validators_num = shares_num;
+    let (
+        validator_keypairs,
+        validators,
+        validators_js,
+        messages_js,
+        msg,
+        aad,
+        ciphertext,
+    ) = setup_dkg(shares_num, validators_num, security_threshold);
+    let header = ciphertext.header().unwrap();
+
+    // Shares are verified by the client against the aggregated transcript alone
+    let client_aggregate = AggregatedTranscript::new(&messages_js).unwrap();
+    let decryption_shares = zip_eq(validators, validator_keypairs)
+        .map(|(validator, keypair)| {
+            let mut dkg = Dkg::new(
+                TAU,
+                shares_num,
+                security_threshold,
+                &validators_js,
+                &validator,
+            )
+            .unwrap();
+            let aggregate = dkg.aggregate_transcripts(&messages_js).unwrap();
+            let decryption_share = aggregate
+                .create_decryption_share_simple(&dkg, &header, &aad, &keypair)
+                .unwrap();
+            let proof = aggregate
+                .create_decryption_share_proof_simple(
+                    &dkg,
+                    &decryption_share,
+                    &header,
+                    &keypair,
+                )
+                .unwrap();
+            assert!(client_aggregate
+                .verify_decryption_share_simple(
+                    &decryption_share,
+                    &proof,
+                    &header
+                )
+                .unwrap());
+            decryption_share
+        })
+        .take(security_threshold as usize)
+        .collect::<Vec<DecryptionShareSimple>>();
+    let decryption_shares_js = into_js_array(decryption_shares);
+
+    let shared_secret =
+        combine_decryption_shares_simple(&decryption_shares_js).unwrap();
+    let plaintext =
+        decrypt_with_shared_secret(&ciphertext, &aad, &shared_secret).unwrap();
+    assert_eq!(msg, plaintext);
+}
+
+#[wasm_bindgen_test]
+fn tdec_simple_with_refresh() {
+    let shares_num = 4;
+    let security_threshold = 3;
+    let validators_num = shares_num;
+    let (
+        validator_keypairs,
+        validators,
+        validators_js,
+        messages_js,
+        msg,
+        aad,
+        ciphertext,
+    ) = setup_dkg(shares_num, validators_num, security_threshold);
+
+    // Every validator deals a refresh transcript to the whole cohort
+    let refresh_messages = validators
+        .iter()
+        .map(|validator| {
+            let dkg = Dkg::new(
+                TAU,
+                shares_num,
+                security_threshold,
+                &validators_js,
+                validator,
+            )
+            .unwrap();
+            let refresh_transcript = dkg.generate_refresh_transcript().unwrap();
+            RefreshMessage::new(validator, &refresh_transcript).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let refresh_messages_js = into_js_array(refresh_messages);
+
+    // Refreshed shares still decrypt the ciphertext
+    let decryption_shares = zip_eq(validators, validator_keypairs)
+        .map(|(validator, keypair)| {
+            let mut dkg = Dkg::new(
+                TAU,
+                shares_num,
+                security_threshold,
+                &validators_js,
+                &validator,
+            )
+            .unwrap();
+            let aggregate = dkg.aggregate_transcripts(&messages_js).unwrap();
+            let refreshed_aggregate = aggregate
+                .refresh(&refresh_messages_js, &validators_js)
+                .unwrap();
+            assert!(refreshed_aggregate
+                .is_share_current(validator.share_index())
+                .unwrap());
+            refreshed_aggregate
+                .create_decryption_share_simple(
+                    &dkg,
+                    &ciphertext.header().unwrap(),
+                    &aad,
+                    &keypair,
+                )
+                .unwrap()
+        })
+        .take(security_threshold as usize)
+        .collect::<Vec<DecryptionShareSimple>>();
+    let decryption_shares_js = into_js_array(decryption_shares);
+
+    let shared_secret =
+        combine_decryption_shares_simple(&decryption_shares_js).unwrap();
+    let plaintext =
+        decrypt_with_shared_secret(&ciphertext, &aad, &shared_secret).unwrap();
+    assert_eq!(msg, plaintext);
+}
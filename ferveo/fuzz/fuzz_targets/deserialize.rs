@@ -0,0 +1,35 @@
+//! Decodes arbitrary bytes as each of the messages exchanged by validators and
+//! clients, and checks that whatever decodes also encodes again.
+#![no_main]
+
+use ferveo::api::*;
+use ferveo_common::{FromBytes, ToBytes};
+use libfuzzer_sys::fuzz_target;
+
+fn round_trip<T: FromBytes + ToBytes>(bytes: &[u8]) {
+    if let Ok(decoded) = T::from_bytes(bytes) {
+        decoded.to_bytes().unwrap();
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, bytes)) = data.split_first() else {
+        return;
+    };
+    match selector % 14 {
+        0 => round_trip::<Transcript>(bytes),
+        1 => round_trip::<AggregatedTranscript>(bytes),
+        2 => round_trip::<RefreshTranscript>(bytes),
+        3 => round_trip::<HandoverTranscript>(bytes),
+        4 => round_trip::<HandoverResult>(bytes),
+        5 => round_trip::<RecoveryShare>(bytes),
+        6 => round_trip::<ResharingTranscript>(bytes),
+        7 => round_trip::<KeyRotation>(bytes),
+        8 => round_trip::<Ciphertext>(bytes),
+        9 => round_trip::<CiphertextHeader>(bytes),
+        10 => round_trip::<DecryptionShareSimple>(bytes),
+        11 => round_trip::<DecryptionSharePrecomputed>(bytes),
+        12 => round_trip::<DecryptionShareProof>(bytes),
+        _ => round_trip::<ValidatorPublicKey>(bytes),
+    }
+});
@@ -0,0 +1,234 @@
+//! Feeds arbitrary bytes, decoded as each of the artifacts a validator receives
+//! from its peers, to the functions that verify and apply them. Any of them may
+//! reject the input, but none of them may panic.
+#![no_main]
+
+use std::{collections::HashMap, str::FromStr, sync::OnceLock};
+
+use ferveo::api::*;
+use ferveo_common::FromBytes;
+use libfuzzer_sys::fuzz_target;
+use rand::{rngs::StdRng, SeedableRng};
+
+const TAU: u32 = 0;
+const SHARES_NUM: u32 = 4;
+const THRESHOLD: u32 = 3;
+const RECOVERY_INDEX: u32 = 3;
+const AAD: &[u8] = b"fuzz-aad";
+
+struct Setup {
+    keypairs: Vec<ValidatorKeypair>,
+    dkgs: Vec<Dkg>,
+    messages: Vec<ValidatorMessage>,
+    aggregate: AggregatedTranscript,
+    validator_keys: HashMap<u32, ValidatorPublicKey>,
+    refresh_transcripts: HashMap<u32, RefreshTranscript>,
+    recovery_transcripts: HashMap<u32, RecoveryTranscript>,
+    ciphertext_header: CiphertextHeader,
+    decryption_shares: Vec<DecryptionShareSimple>,
+}
+
+fn setup() -> &'static Setup {
+    static SETUP: OnceLock<Setup> = OnceLock::new();
+    SETUP.get_or_init(|| {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let keypairs = (0..SHARES_NUM)
+            .map(|_| ValidatorKeypair::new(rng))
+            .collect::<Vec<_>>();
+        let validators = keypairs
+            .iter()
+            .enumerate()
+            .map(|(i, keypair)| Validator {
+                address: EthereumAddress::from_str(&format!("0x{i:040}"))
+                    .unwrap(),
+                public_key: keypair.public_key(),
+                share_index: i as u32,
+            })
+            .collect::<Vec<_>>();
+        let mut dkgs = validators
+            .iter()
+            .map(|me| {
+                Dkg::new(TAU, SHARES_NUM, THRESHOLD, &validators, me).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let messages = validators
+            .iter()
+            .zip(dkgs.iter_mut())
+            .map(|(validator, dkg)| {
+                (validator.clone(), dkg.generate_transcript(rng).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+
+        let validator_keys = validators
+            .iter()
+            .map(|validator| (validator.share_index, validator.public_key))
+            .collect();
+        let refresh_transcripts = validators
+            .iter()
+            .zip(dkgs.iter())
+            .map(|(validator, dkg)| {
+                (
+                    validator.share_index,
+                    dkg.generate_refresh_transcript(rng).unwrap(),
+                )
+            })
+            .collect();
+        let helper_indices = (0..THRESHOLD).collect::<Vec<_>>();
+        let recovery_transcripts = helper_indices
+            .iter()
+            .map(|&helper_index| {
+                let transcript = dkgs[helper_index as usize]
+                    .generate_recovery_transcript(
+                        &helper_indices,
+                        RECOVERY_INDEX,
+                        rng,
+                    )
+                    .unwrap();
+                (helper_index, transcript)
+            })
+            .collect();
+
+        let ciphertext = encrypt(
+            SecretBox::new(b"fuzz-message".to_vec()),
+            AAD,
+            &aggregate.public_key(),
+        )
+        .unwrap();
+        let ciphertext_header = ciphertext.header().unwrap();
+        let decryption_shares = dkgs
+            .iter()
+            .zip(keypairs.iter())
+            .take(THRESHOLD as usize)
+            .map(|(dkg, keypair)| {
+                aggregate
+                    .create_decryption_share_simple(
+                        dkg,
+                        &ciphertext_header,
+                        AAD,
+                        keypair,
+                    )
+                    .unwrap()
+            })
+            .collect();
+
+        Setup {
+            keypairs,
+            dkgs,
+            messages,
+            aggregate,
+            validator_keys,
+            refresh_transcripts,
+            recovery_transcripts,
+            ciphertext_header,
+            decryption_shares,
+        }
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, bytes)) = data.split_first() else {
+        return;
+    };
+    let setup = setup();
+    let rng = &mut StdRng::seed_from_u64(0);
+    match selector % 10 {
+        0 => {
+            let Ok(transcript) = Transcript::from_bytes(bytes) else {
+                return;
+            };
+            let mut messages = setup.messages.clone();
+            messages[0].1 = transcript;
+            if let Ok(aggregate) =
+                setup.dkgs[0].aggregate_transcripts(&messages)
+            {
+                let _ = aggregate.verify(SHARES_NUM, &messages);
+            }
+        }
+        1 => {
+            let Ok(aggregate) = AggregatedTranscript::from_bytes(bytes) else {
+                return;
+            };
+            let _ = aggregate.verify(SHARES_NUM, &setup.messages);
+            let _ = aggregate.create_decryption_share_simple(
+                &setup.dkgs[0],
+                &setup.ciphertext_header,
+                AAD,
+                &setup.keypairs[0],
+            );
+        }
+        2 => {
+            let Ok(transcript) = RefreshTranscript::from_bytes(bytes) else {
+                return;
+            };
+            let mut refresh_transcripts = setup.refresh_transcripts.clone();
+            refresh_transcripts.insert(0, transcript);
+            let _ = setup
+                .aggregate
+                .refresh(&refresh_transcripts, &setup.validator_keys);
+        }
+        3 => {
+            let Ok(transcript) = HandoverTranscript::from_bytes(bytes) else {
+                return;
+            };
+            let _ = setup
+                .aggregate
+                .finalize_handover(&transcript, &setup.keypairs[0]);
+            let _ = setup.aggregate.create_handover_result(
+                &transcript,
+                &setup.keypairs[0],
+                rng,
+            );
+        }
+        4 => {
+            let Ok(result) = HandoverResult::from_bytes(bytes) else {
+                return;
+            };
+            let _ = setup.aggregate.apply_handover_result(&result);
+        }
+        5 => {
+            let Ok(rotation) = KeyRotation::from_bytes(bytes) else {
+                return;
+            };
+            let _ = setup.aggregate.verify_key_rotation(&rotation);
+            let _ = setup.aggregate.apply_key_rotation(&rotation);
+        }
+        6 => {
+            let Ok(share) = RecoveryShare::from_bytes(bytes) else {
+                return;
+            };
+            let _ = setup.aggregate.recover_share(
+                &setup.recovery_transcripts,
+                &setup.validator_keys,
+                RECOVERY_INDEX,
+                &[share],
+            );
+        }
+        7 => {
+            let Ok(transcript) = ResharingTranscript::from_bytes(bytes) else {
+                return;
+            };
+            let transcripts = vec![transcript; THRESHOLD as usize];
+            let _ = setup.aggregate.reshare(&setup.dkgs[0], &transcripts);
+        }
+        8 => {
+            let Ok(share) = DecryptionShareSimple::from_bytes(bytes) else {
+                return;
+            };
+            let mut shares = setup.decryption_shares.clone();
+            shares[0] = share;
+            let _ = combine_shares_simple(&shares);
+        }
+        _ => {
+            let Ok(header) = CiphertextHeader::from_bytes(bytes) else {
+                return;
+            };
+            let _ = setup.aggregate.create_decryption_share_simple(
+                &setup.dkgs[0],
+                &header,
+                AAD,
+                &setup.keypairs[0],
+            );
+        }
+    }
+});
@@ -1,19 +1,15 @@
-use std::{collections::HashMap, fmt, io};
+use std::{collections::HashMap, fmt, io, ops::Add};
 
+use ark_ec::AffineRepr;
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ferveo_common::serialization;
 pub use ferveo_tdec::{
     api::{
         prepare_combine_simple, share_combine_precomputed,
-        share_combine_simple, DecryptionSharePrecomputed, Fr, G1Affine,
-        G1Prepared, G2Affine, SecretBox, E,
+        share_combine_simple, Fr, G1Affine, G1Prepared, G2Affine, SecretBox, E,
     },
-    DomainPoint,
-};
-use generic_array::{
-    typenum::{Unsigned, U48},
-    GenericArray,
+    AccessPolicy, DiscreteLogTable, DomainPoint, EpochClock, SystemEpochClock,
 };
 use rand::{thread_rng, RngCore};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -23,17 +19,18 @@ use serde_with::serde_as;
 use crate::bindings_python;
 #[cfg(feature = "bindings-wasm")]
 use crate::bindings_wasm;
-pub use crate::EthereumAddress;
 use crate::{
     do_verify_aggregation, Error, PubliclyVerifiableSS, Result,
     UpdateTranscript,
 };
+pub use crate::{EthereumAddress, ShareGeneration};
 
 pub type ValidatorPublicKey = ferveo_common::PublicKey<E>;
 pub type ValidatorKeypair = ferveo_common::Keypair<E>;
 pub type Validator = crate::Validator<E>;
 pub type Transcript = PubliclyVerifiableSS<E>;
 pub type RefreshTranscript = UpdateTranscript<E>; // TODO: Consider renaming to UpdateTranscript when dealing with #193
+pub type RecoveryTranscript = UpdateTranscript<E>;
 pub type ValidatorMessage = (Validator, Transcript);
 
 // Normally, we would use a custom trait for this, but we can't because
@@ -62,6 +59,78 @@ pub fn encrypt(
     Ok(Ciphertext(ciphertext))
 }
 
+/// Same as `encrypt`, but avoids computing a pairing for every message
+pub fn encrypt_with_key(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    encryption_key: &EncryptionKey,
+) -> Result<Ciphertext> {
+    let mut rng = thread_rng();
+    let ciphertext = ferveo_tdec::encrypt_with_key(
+        message,
+        aad,
+        &encryption_key.0,
+        &mut rng,
+    )?;
+    Ok(Ciphertext(ciphertext))
+}
+
+/// Encrypt many payloads under one threshold header, so that a single
+/// decryption round unlocks all of them
+pub fn encrypt_payloads(
+    payloads: &[(SecretBox<Vec<u8>>, &[u8])],
+    aad: &[u8],
+    public_key: &DkgPublicKey,
+) -> Result<MultiPayloadCiphertext> {
+    let mut rng = thread_rng();
+    let ciphertext =
+        ferveo_tdec::encrypt_payloads(payloads, aad, &public_key.0, &mut rng)?;
+    Ok(MultiPayloadCiphertext(ciphertext))
+}
+
+/// Encrypt a message under the public keys of several rituals, so that it can
+/// only be decrypted with the cooperation of rituals satisfying `policy`
+pub fn encrypt_with_policy(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    policy: &AccessPolicy,
+    public_keys: &HashMap<u32, DkgPublicKey>,
+) -> Result<MultiKeyCiphertext> {
+    let mut rng = thread_rng();
+    let public_keys = public_keys
+        .iter()
+        .map(|(ritual_id, public_key)| (*ritual_id, public_key.0))
+        .collect();
+    let ciphertext = ferveo_tdec::encrypt_with_policy(
+        message,
+        aad,
+        policy,
+        &public_keys,
+        &mut rng,
+    )?;
+    Ok(MultiKeyCiphertext(ciphertext))
+}
+
+/// Encrypt a message that the validators only decrypt once `epoch` is reached.
+/// Release is enforced by the validators' own clocks, not cryptographically:
+/// see [`ferveo_tdec::epoch_gate`] for the trust model.
+pub fn encrypt_to_epoch(
+    message: SecretBox<Vec<u8>>,
+    aad: &[u8],
+    epoch: u64,
+    public_key: &DkgPublicKey,
+) -> Result<EpochGatedCiphertext> {
+    let mut rng = thread_rng();
+    let ciphertext = ferveo_tdec::encrypt_to_epoch(
+        message,
+        aad,
+        epoch,
+        &public_key.0,
+        &mut rng,
+    )?;
+    Ok(EpochGatedCiphertext(ciphertext))
+}
+
 pub fn decrypt_with_shared_secret(
     ciphertext: &Ciphertext,
     aad: &[u8],
@@ -75,6 +144,21 @@ pub fn decrypt_with_shared_secret(
     .map_err(Error::from)
 }
 
+/// Decrypt a ciphertext with a header checked by [`Ciphertext::verify`] or
+/// [`CiphertextHeader::verify`], without checking it again
+pub fn decrypt_with_verified_header(
+    ciphertext: &Ciphertext,
+    verified_header: &VerifiedCiphertextHeader,
+    shared_secret: &SharedSecret,
+) -> Result<Vec<u8>> {
+    ferveo_tdec::api::decrypt_with_verified_header(
+        &ciphertext.0,
+        &verified_header.0,
+        &shared_secret.0,
+    )
+    .map_err(Error::from)
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
 pub struct Ciphertext(ferveo_tdec::api::Ciphertext);
 
@@ -83,6 +167,11 @@ impl Ciphertext {
         Ok(CiphertextHeader(self.0.header()?))
     }
 
+    /// Check the ciphertext once, for use without checking it again
+    pub fn verify(&self, aad: &[u8]) -> Result<VerifiedCiphertextHeader> {
+        Ok(VerifiedCiphertextHeader(self.0.verify(aad)?))
+    }
+
     pub fn payload(&self) -> Vec<u8> {
         self.0.payload()
     }
@@ -91,6 +180,129 @@ impl Ciphertext {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CiphertextHeader(ferveo_tdec::api::CiphertextHeader);
 
+impl CiphertextHeader {
+    /// Check the header once, for use without checking it again
+    pub fn verify(&self, aad: &[u8]) -> Result<VerifiedCiphertextHeader> {
+        Ok(VerifiedCiphertextHeader(self.0.verify(aad)?))
+    }
+}
+
+/// A ciphertext header that passed the ciphertext validity check for an aad
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedCiphertextHeader(ferveo_tdec::api::VerifiedCiphertextHeader);
+
+impl VerifiedCiphertextHeader {
+    pub fn header(&self) -> CiphertextHeader {
+        CiphertextHeader(self.0.header().clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
+pub struct MultiPayloadCiphertext(ferveo_tdec::MultiPayloadCiphertext<E>);
+
+impl MultiPayloadCiphertext {
+    /// Decryption shares for every payload are created from this header
+    pub fn header(&self) -> Result<CiphertextHeader> {
+        Ok(CiphertextHeader(self.0.header()?))
+    }
+
+    pub fn payloads_num(&self) -> u32 {
+        self.0.payloads.len() as u32
+    }
+
+    pub fn decrypt_payload(
+        &self,
+        index: u32,
+        aad: &[u8],
+        payload_aad: &[u8],
+        shared_secret: &SharedSecret,
+    ) -> Result<Vec<u8>> {
+        Ok(self
+            .0
+            .decrypt_payload(index, aad, payload_aad, &shared_secret.0)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
+pub struct MultiKeyCiphertext(ferveo_tdec::MultiKeyCiphertext<E>);
+
+impl MultiKeyCiphertext {
+    pub fn policy(&self) -> &AccessPolicy {
+        &self.0.policy
+    }
+
+    pub fn components_num(&self) -> u32 {
+        self.0.components.len() as u32
+    }
+
+    /// Ritual to request decryption shares from for the component at `index`
+    pub fn component_ritual(&self, index: u32) -> Result<u32> {
+        self.0
+            .components
+            .get(index as usize)
+            .map(|component| component.ritual_id)
+            .ok_or(ferveo_tdec::Error::InvalidComponentIndex(index).into())
+    }
+
+    /// Decryption shares for the component at `index` are created from this
+    /// header and `component_aad`, as for any other ciphertext
+    pub fn component_header(&self, index: u32) -> Result<CiphertextHeader> {
+        Ok(CiphertextHeader(self.0.component_header(index as usize)?))
+    }
+
+    pub fn component_aad(&self, aad: &[u8]) -> Result<Vec<u8>> {
+        Ok(self.0.component_aad(aad)?)
+    }
+
+    /// Decrypt with the shared secrets combined for some of the components,
+    /// keyed by component index
+    pub fn decrypt(
+        &self,
+        aad: &[u8],
+        shared_secrets: &HashMap<u32, SharedSecret>,
+    ) -> Result<Vec<u8>> {
+        let shared_secrets = shared_secrets
+            .iter()
+            .map(|(index, shared_secret)| {
+                (*index as usize, shared_secret.0.clone())
+            })
+            .collect();
+        Ok(self.0.decrypt(aad, &shared_secrets)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
+pub struct EpochGatedCiphertext(ferveo_tdec::EpochGatedCiphertext<E>);
+
+impl EpochGatedCiphertext {
+    pub fn header(&self) -> Result<EpochGatedCiphertextHeader> {
+        Ok(EpochGatedCiphertextHeader(self.0.header()?))
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.0.epoch
+    }
+
+    pub fn decrypt_with_shared_secret(
+        &self,
+        aad: &[u8],
+        shared_secret: &SharedSecret,
+    ) -> Result<Vec<u8>> {
+        Ok(self.0.decrypt_with_shared_secret(aad, &shared_secret.0)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
+pub struct EpochGatedCiphertextHeader(
+    ferveo_tdec::EpochGatedCiphertextHeader<E>,
+);
+
+impl EpochGatedCiphertextHeader {
+    pub fn epoch(&self) -> u64 {
+        self.0.epoch
+    }
+}
+
 /// The ferveo variant to use for the decryption share derivation.
 #[derive(
     PartialEq, Eq, Debug, Serialize, Deserialize, Copy, Clone, PartialOrd,
@@ -150,26 +362,45 @@ pub struct DkgPublicKey(
 
 // TODO: Consider moving these implementation details to ferveo_tdec::DkgPublicKey - #197
 impl DkgPublicKey {
-    pub fn to_bytes(&self) -> Result<GenericArray<u8, U48>> {
-        let as_bytes = to_bytes(&self.0 .0)?;
-        Ok(GenericArray::<u8, U48>::from_slice(&as_bytes).to_owned())
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        to_bytes(&self.0 .0)
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<DkgPublicKey> {
-        let bytes =
-            GenericArray::<u8, U48>::from_exact_iter(bytes.iter().cloned())
-                .ok_or_else(|| {
-                    Error::InvalidByteLength(
-                        Self::serialized_size(),
-                        bytes.len(),
-                    )
-                })?;
-        let pk: G1Affine = from_bytes(&bytes)?;
+        if bytes.len() != Self::serialized_size() {
+            return Err(Error::InvalidByteLength(
+                Self::serialized_size(),
+                bytes.len(),
+            ));
+        }
+        let pk: G1Affine = from_bytes(bytes)?;
         Ok(DkgPublicKey(ferveo_tdec::DkgPublicKey(pk)))
     }
 
+    /// The size of a compressed G1 point of the selected curve.
     pub fn serialized_size() -> usize {
-        U48::to_usize()
+        G1Affine::generator().compressed_size()
+    }
+}
+
+/// A `DkgPublicKey` with precomputed values for faster encryption.
+/// Clients encrypting many messages to the same ritual may cache it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptionKey(
+    #[serde(bound(
+        serialize = "ferveo_tdec::EncryptionKey<E>: Serialize",
+        deserialize = "ferveo_tdec::EncryptionKey<E>: DeserializeOwned"
+    ))]
+    ferveo_tdec::EncryptionKey<E>,
+);
+
+impl EncryptionKey {
+    pub fn new(dkg_public_key: &DkgPublicKey) -> Self {
+        EncryptionKey(ferveo_tdec::EncryptionKey::new(&dkg_public_key.0))
+    }
+
+    pub fn public_key(&self) -> DkgPublicKey {
+        DkgPublicKey(*self.0.public_key())
     }
 }
 
@@ -232,6 +463,15 @@ impl Dkg {
         self.0.generate_refresh_transcript(rng)
     }
 
+    /// Refresh transcript dealing updates to the given validators only
+    pub fn generate_refresh_transcript_for<R: RngCore>(
+        &self,
+        share_indices: &[u32],
+        rng: &mut R,
+    ) -> Result<RefreshTranscript> {
+        self.0.generate_refresh_transcript_for(share_indices, rng)
+    }
+
     pub fn generate_handover_transcript<R: RngCore>(
         &self,
         aggregate: &AggregatedTranscript,
@@ -249,6 +489,54 @@ impl Dkg {
             .map(HandoverTranscript)
     }
 
+    /// Handover transcripts of several slots at once. Slots and incoming
+    /// validators must be distinct, and new to the cohort.
+    pub fn generate_handover_transcripts<R: RngCore>(
+        &self,
+        aggregate: &AggregatedTranscript,
+        handovers: &[(u32, &ValidatorKeypair)],
+        rng: &mut R,
+    ) -> Result<Vec<HandoverTranscript>> {
+        let transcripts = self.0.generate_handover_transcripts(
+            &aggregate.0,
+            handovers,
+            rng,
+        )?;
+        Ok(transcripts.into_iter().map(HandoverTranscript).collect())
+    }
+
+    /// Recovery transcript for the share at `recovery_index`, dealt by one of
+    /// the helpers to all of them
+    pub fn generate_recovery_transcript<R: RngCore>(
+        &self,
+        helper_indices: &[u32],
+        recovery_index: u32,
+        rng: &mut R,
+    ) -> Result<RecoveryTranscript> {
+        self.0
+            .generate_recovery_transcript(helper_indices, recovery_index, rng)
+    }
+
+    /// Handover transcript the recovering validator sends to a helper
+    pub fn generate_recovery_handover_transcript<R: RngCore>(
+        &self,
+        aggregate: &AggregatedTranscript,
+        recovery_transcripts: &HashMap<u32, RecoveryTranscript>,
+        helper_index: u32,
+        recovering_validator_keypair: &ValidatorKeypair,
+        rng: &mut R,
+    ) -> Result<HandoverTranscript> {
+        self.0
+            .generate_recovery_handover_transcript(
+                &aggregate.0,
+                recovery_transcripts,
+                helper_index,
+                recovering_validator_keypair,
+                rng,
+            )
+            .map(HandoverTranscript)
+    }
+
     pub fn me(&self) -> &Validator {
         &self.0.me
     }
@@ -264,6 +552,18 @@ pub struct AggregatedTranscript(crate::AggregatedTranscript<E>);
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HandoverTranscript(crate::HandoverTranscript<E>);
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandoverResult(crate::HandoverResult<E>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryShare(crate::RecoveryShare<E>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation(crate::KeyRotation<E>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResharingTranscript(crate::ResharingTranscript<E>);
+
 impl AggregatedTranscript {
     pub fn new(messages: &[ValidatorMessage]) -> Result<Self> {
         let transcripts: Vec<_> = messages
@@ -275,6 +575,13 @@ impl AggregatedTranscript {
         Ok(AggregatedTranscript(aggregated_transcript))
     }
 
+    /// Decode an aggregate, including one encoded before refresh rounds were
+    /// tracked. See [`crate::AggregatedTranscript::from_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        crate::AggregatedTranscript::<E>::from_bytes(bytes)
+            .map(AggregatedTranscript)
+    }
+
     pub fn verify(
         &self,
         validators_num: u32,
@@ -286,10 +593,20 @@ impl AggregatedTranscript {
                 messages.len() as u32,
             ));
         }
+        // The threshold isn't known here, so the dealt transcripts are checked
+        // against the aggregate
+        let security_threshold = self.0.aggregate.coeffs.len() as u32;
+        self.0
+            .aggregate
+            .check_structure(security_threshold, validators_num)?;
+        for (_, transcript) in messages {
+            transcript.check_structure(security_threshold, validators_num)?;
+        }
 
         let domain =
-            GeneralEvaluationDomain::<Fr>::new(validators_num as usize)
-                .expect("Unable to construct an evaluation domain");
+            GeneralEvaluationDomain::<Fr>::new(validators_num as usize).ok_or(
+                Error::InvalidDkgParameters(validators_num, security_threshold),
+            )?;
         let is_valid_optimistic = self.0.aggregate.verify_optimistic();
         if !is_valid_optimistic {
             return Err(Error::InvalidTranscriptAggregate);
@@ -323,22 +640,18 @@ impl AggregatedTranscript {
         validator_keypair: &ValidatorKeypair,
         selected_validators: &[Validator],
     ) -> Result<DecryptionSharePrecomputed> {
-        let selected_domain_points = selected_validators
-            .iter()
-            .filter_map(|v| {
-                dkg.0
-                    .get_domain_point(v.share_index)
-                    .ok()
-                    .map(|domain_point| (v.share_index, domain_point))
-            })
-            .collect::<HashMap<u32, ferveo_tdec::DomainPoint<E>>>();
-        self.0.aggregate.create_decryption_share_precomputed(
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self.0.aggregate.create_decryption_share_precomputed(
             &ciphertext_header.0,
             aad,
             validator_keypair,
             dkg.0.me.share_index,
-            &selected_domain_points,
-        )
+            &selected_domain_points(dkg, selected_validators),
+        )?;
+        Ok(DecryptionSharePrecomputed {
+            share,
+            generation: self.0.generation,
+        })
     }
 
     pub fn create_decryption_share_simple(
@@ -348,6 +661,7 @@ impl AggregatedTranscript {
         aad: &[u8],
         validator_keypair: &ValidatorKeypair,
     ) -> Result<DecryptionShareSimple> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
         let share = self.0.aggregate.create_decryption_share_simple(
             &ciphertext_header.0,
             aad,
@@ -358,73 +672,1053 @@ impl AggregatedTranscript {
         Ok(DecryptionShareSimple {
             share,
             domain_point,
+            generation: self.0.generation,
         })
     }
 
-    pub fn public_key(&self) -> DkgPublicKey {
-        DkgPublicKey(self.0.public_key)
-    }
-
-    pub fn refresh(
+    /// Make a decryption share (precomputed variant) for a header checked by
+    /// [`Ciphertext::verify`] or [`CiphertextHeader::verify`]
+    pub fn create_decryption_share_precomputed_verified(
         &self,
-        update_transcripts: &HashMap<u32, RefreshTranscript>,
-        validator_keys_map: &HashMap<u32, ValidatorPublicKey>,
-    ) -> Result<Self> {
-        // TODO: Aggregates structs should be refactored, this is a bit of a mess - #162
-        let updated_aggregate = self
+        dkg: &Dkg,
+        ciphertext_header: &VerifiedCiphertextHeader,
+        validator_keypair: &ValidatorKeypair,
+        selected_validators: &[Validator],
+    ) -> Result<DecryptionSharePrecomputed> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self
             .0
             .aggregate
-            .refresh(update_transcripts, validator_keys_map)
-            .unwrap();
-        let eeww =
-            crate::AggregatedTranscript::<E>::from_aggregate(updated_aggregate)
-                .unwrap();
-        Ok(AggregatedTranscript(eeww))
+            .create_decryption_share_precomputed_verified(
+                &ciphertext_header.0,
+                validator_keypair,
+                dkg.0.me.share_index,
+                &selected_domain_points(dkg, selected_validators),
+            )?;
+        Ok(DecryptionSharePrecomputed {
+            share,
+            generation: self.0.generation,
+        })
     }
 
-    pub fn finalize_handover(
+    /// Make a decryption share (simple variant) for a header checked by
+    /// [`Ciphertext::verify`] or [`CiphertextHeader::verify`]
+    pub fn create_decryption_share_simple_verified(
         &self,
-        handover_transcript: &HandoverTranscript,
+        dkg: &Dkg,
+        ciphertext_header: &VerifiedCiphertextHeader,
         validator_keypair: &ValidatorKeypair,
-    ) -> Result<Self> {
-        let new_aggregate = self
-            .0
-            .aggregate
-            .finalize_handover(&handover_transcript.0, validator_keypair)
-            .unwrap();
-        // TODO: Aggregates structs should be refactored, this is a bit of a mess - #162
-        let eeww =
-            crate::AggregatedTranscript::<E>::from_aggregate(new_aggregate)
-                .unwrap();
-        Ok(AggregatedTranscript(eeww))
+    ) -> Result<DecryptionShareSimple> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self.0.aggregate.create_decryption_share_simple_verified(
+            &ciphertext_header.0,
+            validator_keypair,
+            dkg.0.me.share_index,
+        )?;
+        let domain_point = dkg.0.get_domain_point(dkg.0.me.share_index)?;
+        Ok(DecryptionShareSimple {
+            share,
+            domain_point,
+            generation: self.0.generation,
+        })
     }
-}
-
-#[serde_as]
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DecryptionShareSimple {
-    share: ferveo_tdec::api::DecryptionShareSimple,
-    #[serde_as(as = "serialization::SerdeAs")]
-    domain_point: DomainPoint<E>,
-}
-
-pub fn combine_shares_simple(shares: &[DecryptionShareSimple]) -> SharedSecret {
-    let domain_points: Vec<_> = shares.iter().map(|s| s.domain_point).collect();
-    let lagrange_coefficients = prepare_combine_simple::<E>(&domain_points);
 
-    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
-    let shared_secret =
-        share_combine_simple(&shares, &lagrange_coefficients[..]);
-    SharedSecret(shared_secret)
-}
+    /// Make a decryption share (simple variant) for an epoch-gated ciphertext.
+    /// Fails until `clock` reaches the epoch of the ciphertext.
+    pub fn create_epoch_gated_decryption_share_simple(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &EpochGatedCiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &ValidatorKeypair,
+        clock: &impl EpochClock,
+    ) -> Result<DecryptionShareSimple> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        ciphertext_header.0.check_unlocked(clock)?;
+        let share = self.0.aggregate.create_decryption_share_simple(
+            &ciphertext_header.0.header,
+            &ciphertext_header.0.header_aad(aad),
+            validator_keypair,
+            dkg.0.me.share_index,
+        )?;
+        let domain_point = dkg.0.get_domain_point(dkg.0.me.share_index)?;
+        Ok(DecryptionShareSimple {
+            share,
+            domain_point,
+            generation: self.0.generation,
+        })
+    }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct SharedSecret(pub ferveo_tdec::api::SharedSecret<E>);
+    /// Make a long-lived decryption context for this validator, to be reused
+    /// for every decryption request in this ritual
+    pub fn create_decryption_service(
+        &self,
+        dkg: &Dkg,
+        validator_keypair: &ValidatorKeypair,
+    ) -> Result<DecryptionService> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share_index = dkg.0.me.share_index;
+        let service = self.0.aggregate.create_decryption_service(
+            validator_keypair,
+            share_index,
+            dkg.0.domain_point_map(),
+            ferveo_tdec::DEFAULT_LAGRANGE_CACHE_CAPACITY,
+        )?;
+        let domain_point = dkg.0.get_domain_point(share_index)?;
+        Ok(DecryptionService {
+            service,
+            domain_point,
+            generation: self.0.generation,
+        })
+    }
 
-#[cfg(test)]
-mod test_ferveo_api {
+    /// Make a decryption share (simple variant) blinded to the requester's
+    /// ephemeral public key, so that only the requester can combine it
+    pub fn create_blinded_decryption_share_simple(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &ValidatorKeypair,
+        requester_public_key: &RequesterPublicKey,
+    ) -> Result<BlindedDecryptionShareSimple> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self.0.aggregate.create_blinded_decryption_share_simple(
+            &ciphertext_header.0,
+            aad,
+            validator_keypair,
+            dkg.0.me.share_index,
+            &requester_public_key.0,
+            &mut thread_rng(),
+        )?;
+        let domain_point = dkg.0.get_domain_point(dkg.0.me.share_index)?;
+        Ok(BlindedDecryptionShareSimple {
+            share,
+            domain_point,
+            generation: self.0.generation,
+        })
+    }
 
-    use ark_std::{iterable::Iterable, UniformRand};
+    /// Make a decryption share (precomputed variant) blinded to the requester's
+    /// ephemeral public key, so that only the requester can combine it
+    pub fn create_blinded_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &ValidatorKeypair,
+        selected_validators: &[Validator],
+        requester_public_key: &RequesterPublicKey,
+    ) -> Result<BlindedDecryptionSharePrecomputed> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self
+            .0
+            .aggregate
+            .create_blinded_decryption_share_precomputed(
+                &ciphertext_header.0,
+                aad,
+                validator_keypair,
+                dkg.0.me.share_index,
+                &selected_domain_points(dkg, selected_validators),
+                &requester_public_key.0,
+                &mut thread_rng(),
+            )?;
+        Ok(BlindedDecryptionSharePrecomputed {
+            share,
+            generation: self.0.generation,
+        })
+    }
+
+    /// Verify a blinded decryption share (simple variant) against the share
+    /// commitments of this ritual
+    pub fn verify_blinded_decryption_share_simple(
+        &self,
+        share: &BlindedDecryptionShareSimple,
+        ciphertext_header: &CiphertextHeader,
+        requester_public_key: &RequesterPublicKey,
+    ) -> Result<bool> {
+        let share_index = match self.share_index_of(&share.domain_point)? {
+            Some(share_index) => share_index,
+            None => return Ok(false),
+        };
+        self.0.aggregate.verify_blinded_decryption_share_simple(
+            &share.share,
+            &ciphertext_header.0,
+            share_index,
+            &requester_public_key.0,
+        )
+    }
+
+    /// Verify a blinded decryption share (precomputed variant) against the
+    /// share commitments of this ritual
+    pub fn verify_blinded_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        share: &BlindedDecryptionSharePrecomputed,
+        ciphertext_header: &CiphertextHeader,
+        selected_validators: &[Validator],
+        requester_public_key: &RequesterPublicKey,
+    ) -> Result<bool> {
+        self.0
+            .aggregate
+            .verify_blinded_decryption_share_precomputed(
+                &share.share,
+                &ciphertext_header.0,
+                &selected_domain_points(dkg, selected_validators),
+                &requester_public_key.0,
+            )
+    }
+
+    /// Make a decryption share of a verified tally of ElGamal ballots, with a
+    /// proof of correctness
+    pub fn create_elgamal_decryption_share(
+        &self,
+        dkg: &Dkg,
+        tally: &VerifiedElGamalTally,
+        validator_keypair: &ValidatorKeypair,
+    ) -> Result<ElGamalDecryptionShare> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self.0.aggregate.create_elgamal_decryption_share(
+            &tally.0,
+            validator_keypair,
+            dkg.0.me.share_index,
+            &mut thread_rng(),
+        )?;
+        let domain_point = dkg.0.get_domain_point(dkg.0.me.share_index)?;
+        Ok(ElGamalDecryptionShare {
+            share,
+            domain_point,
+            generation: self.0.generation,
+        })
+    }
+
+    /// Verify a decryption share of a homomorphic ElGamal ciphertext against
+    /// the share commitments of this ritual
+    pub fn verify_elgamal_decryption_share(
+        &self,
+        share: &ElGamalDecryptionShare,
+        ciphertext: &ElGamalCiphertext,
+    ) -> Result<bool> {
+        let share_index = match self.share_index_of(&share.domain_point)? {
+            Some(share_index) => share_index,
+            None => return Ok(false),
+        };
+        self.0.aggregate.verify_elgamal_decryption_share(
+            &share.share,
+            &ciphertext.0,
+            share_index,
+        )
+    }
+
+    /// Prove that a decryption share (simple variant) made by this validator
+    /// is correct. The proof can be verified with the aggregated transcript
+    /// alone, without the blinded key share or the validator public key.
+    pub fn create_decryption_share_proof_simple(
+        &self,
+        dkg: &Dkg,
+        share: &DecryptionShareSimple,
+        ciphertext_header: &CiphertextHeader,
+        validator_keypair: &ValidatorKeypair,
+    ) -> Result<DecryptionShareProof> {
+        self.0
+            .aggregate
+            .create_decryption_share_proof_simple(
+                &share.share,
+                &ciphertext_header.0,
+                validator_keypair,
+                dkg.0.me.share_index,
+                &mut thread_rng(),
+            )
+            .map(DecryptionShareProof)
+    }
+
+    /// Prove that a decryption share (precomputed variant) made by this
+    /// validator is correct
+    pub fn create_decryption_share_proof_precomputed(
+        &self,
+        dkg: &Dkg,
+        share: &DecryptionSharePrecomputed,
+        ciphertext_header: &CiphertextHeader,
+        validator_keypair: &ValidatorKeypair,
+        selected_validators: &[Validator],
+    ) -> Result<DecryptionShareProof> {
+        self.0
+            .aggregate
+            .create_decryption_share_proof_precomputed(
+                &share.share,
+                &ciphertext_header.0,
+                validator_keypair,
+                &selected_domain_points(dkg, selected_validators),
+                &mut thread_rng(),
+            )
+            .map(DecryptionShareProof)
+    }
+
+    /// Verify a decryption share (simple variant) and its proof against the
+    /// share commitments of this ritual
+    pub fn verify_decryption_share_simple(
+        &self,
+        share: &DecryptionShareSimple,
+        proof: &DecryptionShareProof,
+        ciphertext_header: &CiphertextHeader,
+    ) -> Result<bool> {
+        let share_index = match self.share_index_of(&share.domain_point)? {
+            Some(share_index) => share_index,
+            None => return Ok(false),
+        };
+        self.0.aggregate.verify_decryption_share_proof_simple(
+            &share.share,
+            &proof.0,
+            &ciphertext_header.0,
+            share_index,
+        )
+    }
+
+    /// Verify a decryption share (precomputed variant) and its proof against
+    /// the share commitments of this ritual
+    pub fn verify_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        share: &DecryptionSharePrecomputed,
+        proof: &DecryptionShareProof,
+        ciphertext_header: &CiphertextHeader,
+        selected_validators: &[Validator],
+    ) -> Result<bool> {
+        self.0.aggregate.verify_decryption_share_proof_precomputed(
+            &share.share,
+            &proof.0,
+            &ciphertext_header.0,
+            &selected_domain_points(dkg, selected_validators),
+        )
+    }
+
+    /// Make this validator's share of the re-encryption of a ciphertext to
+    /// the ritual with the public key `target_public_key`
+    pub fn create_reencryption_share(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &ValidatorKeypair,
+        target_public_key: &DkgPublicKey,
+    ) -> Result<ReEncryptionShare> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let share = self.0.aggregate.create_reencryption_share(
+            &ciphertext_header.0,
+            aad,
+            validator_keypair,
+            dkg.0.me.share_index,
+            &target_public_key.0,
+            &mut thread_rng(),
+        )?;
+        let domain_point = dkg.0.get_domain_point(dkg.0.me.share_index)?;
+        Ok(ReEncryptionShare {
+            share,
+            domain_point,
+            generation: self.0.generation,
+        })
+    }
+
+    /// Verify a re-encryption share against the share commitments of this
+    /// ritual. Anyone holding the aggregated transcript can run this check.
+    pub fn verify_reencryption_share(
+        &self,
+        share: &ReEncryptionShare,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        target_public_key: &DkgPublicKey,
+    ) -> Result<bool> {
+        let share_index = match self.share_index_of(&share.domain_point)? {
+            Some(share_index) => share_index,
+            None => return Ok(false),
+        };
+        self.0.aggregate.verify_reencryption_share(
+            &share.share,
+            &ciphertext_header.0,
+            aad,
+            share_index,
+            &target_public_key.0,
+        )
+    }
+
+    /// Make a decryption share (simple variant) for a header that was
+    /// re-encrypted to this ritual
+    pub fn create_decryption_share_simple_for_reencrypted(
+        &self,
+        dkg: &Dkg,
+        reencrypted_header: &ReEncryptedHeader,
+        aad: &[u8],
+        validator_keypair: &ValidatorKeypair,
+    ) -> Result<DecryptionShareSimple> {
+        self.0.check_share_is_current(dkg.0.me.share_index)?;
+        let private_key_share = self
+            .0
+            .aggregate
+            .get_share_for_index_and_pubkey(
+                dkg.0.me.share_index,
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        let share = reencrypted_header.0.create_decryption_share_simple(
+            aad,
+            &validator_keypair.decryption_key,
+            &private_key_share,
+        )?;
+        let domain_point = dkg.0.get_domain_point(dkg.0.me.share_index)?;
+        Ok(DecryptionShareSimple {
+            share,
+            domain_point,
+            generation: self.0.generation,
+        })
+    }
+
+    pub fn public_key(&self) -> DkgPublicKey {
+        DkgPublicKey(self.0.public_key)
+    }
+
+    /// Share generation of this aggregate. Decryption shares can only be
+    /// combined with shares of the same generation.
+    pub fn generation(&self) -> ShareGeneration {
+        self.0.generation
+    }
+
+    /// Share index of a domain point of this ritual
+    fn share_index_of(
+        &self,
+        domain_point: &DomainPoint<E>,
+    ) -> Result<Option<u32>> {
+        let domain =
+            crate::evaluation_domain::<Fr>(self.0.aggregate.shares.len())?;
+        Ok(domain
+            .elements()
+            .position(|point| point == *domain_point)
+            .map(|share_index| share_index as u32))
+    }
+
+    /// Whether the share took part in the latest refresh round of this
+    /// aggregate. Stale shares can't create decryption shares.
+    pub fn is_share_current(&self, share_index: u32) -> Result<bool> {
+        self.0.is_share_current(share_index)
+    }
+
+    /// Refresh the shares of the validators in `validator_keys_map`, which may
+    /// be a subset of the ritual, such as the validators currently online
+    pub fn refresh(
+        &self,
+        update_transcripts: &HashMap<u32, RefreshTranscript>,
+        validator_keys_map: &HashMap<u32, ValidatorPublicKey>,
+    ) -> Result<Self> {
+        let refreshed =
+            self.0.refresh(update_transcripts, validator_keys_map)?;
+        Ok(AggregatedTranscript(refreshed))
+    }
+
+    /// Make this helper's share of the recovery of the share at `recovery_index`
+    pub fn create_recovery_share(
+        &self,
+        recovery_transcripts: &HashMap<u32, RecoveryTranscript>,
+        helper_keys_map: &HashMap<u32, ValidatorPublicKey>,
+        recovery_index: u32,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &ValidatorKeypair,
+    ) -> Result<RecoveryShare> {
+        self.0
+            .create_recovery_share(
+                recovery_transcripts,
+                helper_keys_map,
+                recovery_index,
+                &handover_transcript.0,
+                validator_keypair,
+            )
+            .map(RecoveryShare)
+    }
+
+    /// Replace the share at `recovery_index` with the one recovered from the
+    /// helpers' recovery shares, blinded to the recovering validator's key
+    pub fn recover_share(
+        &self,
+        recovery_transcripts: &HashMap<u32, RecoveryTranscript>,
+        helper_keys_map: &HashMap<u32, ValidatorPublicKey>,
+        recovery_index: u32,
+        recovery_shares: &[RecoveryShare],
+    ) -> Result<Self> {
+        let recovery_shares = recovery_shares
+            .iter()
+            .map(|share| share.0.clone())
+            .collect::<Vec<_>>();
+        let recovered = self.0.recover_share(
+            recovery_transcripts,
+            helper_keys_map,
+            recovery_index,
+            &recovery_shares,
+        )?;
+        Ok(AggregatedTranscript(recovered))
+    }
+
+    /// Deal this validator's share to the new committee of `new_dkg`, given a
+    /// handover transcript from each of its validators
+    pub fn create_resharing_transcript<R: RngCore>(
+        &self,
+        dealer_index: u32,
+        validator_keypair: &ValidatorKeypair,
+        handover_transcripts: &HashMap<u32, HandoverTranscript>,
+        new_dkg: &Dkg,
+        rng: &mut R,
+    ) -> Result<ResharingTranscript> {
+        let handover_transcripts = handover_transcripts
+            .iter()
+            .map(|(share_index, transcript)| {
+                (*share_index, transcript.0.clone())
+            })
+            .collect::<HashMap<_, _>>();
+        self.0
+            .create_resharing_transcript(
+                dealer_index,
+                validator_keypair,
+                &handover_transcripts,
+                &new_dkg.0,
+                rng,
+            )
+            .map(ResharingTranscript)
+    }
+
+    /// Aggregate for the new committee of `new_dkg` from the resharing
+    /// transcripts of at least `threshold` validators. The public key is kept.
+    pub fn reshare(
+        &self,
+        new_dkg: &Dkg,
+        resharing_transcripts: &[ResharingTranscript],
+    ) -> Result<Self> {
+        let resharing_transcripts = resharing_transcripts
+            .iter()
+            .map(|transcript| transcript.0.clone())
+            .collect::<Vec<_>>();
+        let reshared = self.0.reshare(&new_dkg.0, &resharing_transcripts)?;
+        Ok(AggregatedTranscript(reshared))
+    }
+
+    pub fn finalize_handover(
+        &self,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &ValidatorKeypair,
+    ) -> Result<Self> {
+        let new_aggregate = self
+            .0
+            .finalize_handover(&handover_transcript.0, validator_keypair)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+
+    /// Finalize a handover of this validator's share as a result that any
+    /// party can verify and apply to its own copy of the aggregate
+    pub fn create_handover_result<R: RngCore>(
+        &self,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &ValidatorKeypair,
+        rng: &mut R,
+    ) -> Result<HandoverResult> {
+        self.0
+            .create_handover_result(
+                &handover_transcript.0,
+                validator_keypair,
+                rng,
+            )
+            .map(HandoverResult)
+    }
+
+    pub fn apply_handover_result(
+        &self,
+        handover_result: &HandoverResult,
+    ) -> Result<Self> {
+        let new_aggregate = self.0.apply_handover_result(&handover_result.0)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+
+    /// Rotate the key of the validator at `share_index` to `new_keypair`,
+    /// keeping its share and share index
+    pub fn create_key_rotation<R: RngCore>(
+        &self,
+        share_index: u32,
+        validator_keypair: &ValidatorKeypair,
+        new_validator_keypair: &ValidatorKeypair,
+        rng: &mut R,
+    ) -> Result<KeyRotation> {
+        self.0
+            .create_key_rotation(
+                share_index,
+                validator_keypair,
+                new_validator_keypair,
+                rng,
+            )
+            .map(KeyRotation)
+    }
+
+    pub fn verify_key_rotation(
+        &self,
+        key_rotation: &KeyRotation,
+    ) -> Result<bool> {
+        self.0.aggregate.verify_key_rotation(&key_rotation.0)
+    }
+
+    pub fn apply_key_rotation(
+        &self,
+        key_rotation: &KeyRotation,
+    ) -> Result<Self> {
+        let new_aggregate = self.0.apply_key_rotation(&key_rotation.0)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+
+    /// Finalize the handovers of several slots of the cohort of `dkg` at once
+    pub fn finalize_handovers(
+        &self,
+        dkg: &Dkg,
+        handovers: &[(&HandoverTranscript, &ValidatorKeypair)],
+    ) -> Result<Self> {
+        let handovers = handovers
+            .iter()
+            .map(|(transcript, keypair)| (&transcript.0, *keypair))
+            .collect::<Vec<_>>();
+        let new_aggregate = self.0.finalize_handovers(&dkg.0, &handovers)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+
+    /// Apply the handover results of several slots of the cohort of `dkg` at
+    /// once
+    pub fn apply_handover_results(
+        &self,
+        dkg: &Dkg,
+        handover_results: &[HandoverResult],
+    ) -> Result<Self> {
+        let handover_results = handover_results
+            .iter()
+            .map(|result| result.0.clone())
+            .collect::<Vec<_>>();
+        let new_aggregate =
+            self.0.apply_handover_results(&dkg.0, &handover_results)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+}
+
+fn selected_domain_points(
+    dkg: &Dkg,
+    selected_validators: &[Validator],
+) -> HashMap<u32, DomainPoint<E>> {
+    selected_validators
+        .iter()
+        .filter_map(|v| {
+            dkg.0
+                .get_domain_point(v.share_index)
+                .ok()
+                .map(|domain_point| (v.share_index, domain_point))
+        })
+        .collect()
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShareSimple {
+    share: ferveo_tdec::api::DecryptionShareSimple,
+    #[serde_as(as = "serialization::SerdeAs")]
+    domain_point: DomainPoint<E>,
+    generation: ShareGeneration,
+}
+
+impl DecryptionShareSimple {
+    /// Share generation of the aggregate this share was made with
+    pub fn generation(&self) -> ShareGeneration {
+        self.generation
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionSharePrecomputed {
+    share: ferveo_tdec::api::DecryptionSharePrecomputed,
+    generation: ShareGeneration,
+}
+
+impl DecryptionSharePrecomputed {
+    /// Share generation of the aggregate this share was made with
+    pub fn generation(&self) -> ShareGeneration {
+        self.generation
+    }
+}
+
+/// Proof that a decryption share was computed with the key share committed to
+/// in the aggregated transcript
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShareProof(ferveo_tdec::DecryptionShareProof<E>);
+
+/// Validator-side decryption context, holding the unblinded private key share
+pub struct DecryptionService {
+    service: ferveo_tdec::DecryptionService<E>,
+    domain_point: DomainPoint<E>,
+    generation: ShareGeneration,
+}
+
+impl DecryptionService {
+    pub fn create_decryption_share_simple(
+        &self,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+    ) -> Result<DecryptionShareSimple> {
+        let share = self
+            .service
+            .create_decryption_share_simple(&ciphertext_header.0, aad)?;
+        Ok(DecryptionShareSimple {
+            share,
+            domain_point: self.domain_point,
+            generation: self.generation,
+        })
+    }
+
+    pub fn create_decryption_share_precomputed(
+        &self,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        selected_validators: &[Validator],
+    ) -> Result<DecryptionSharePrecomputed> {
+        let selected_share_indices = selected_validators
+            .iter()
+            .map(|v| v.share_index)
+            .collect::<Vec<_>>();
+        let share = self.service.create_decryption_share_precomputed(
+            &ciphertext_header.0,
+            aad,
+            &selected_share_indices,
+        )?;
+        Ok(DecryptionSharePrecomputed {
+            share,
+            generation: self.generation,
+        })
+    }
+
+    /// Make a decryption share (simple variant) for an already checked header
+    pub fn create_decryption_share_simple_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader,
+    ) -> DecryptionShareSimple {
+        let share = self
+            .service
+            .create_decryption_share_simple_verified(&ciphertext_header.0);
+        DecryptionShareSimple {
+            share,
+            domain_point: self.domain_point,
+            generation: self.generation,
+        }
+    }
+
+    /// Make a decryption share (precomputed variant) for an already checked
+    /// header
+    pub fn create_decryption_share_precomputed_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader,
+        selected_validators: &[Validator],
+    ) -> Result<DecryptionSharePrecomputed> {
+        let selected_share_indices = selected_validators
+            .iter()
+            .map(|v| v.share_index)
+            .collect::<Vec<_>>();
+        let share = self.service.create_decryption_share_precomputed_verified(
+            &ciphertext_header.0,
+            &selected_share_indices,
+        )?;
+        Ok(DecryptionSharePrecomputed {
+            share,
+            generation: self.generation,
+        })
+    }
+}
+
+/// Ephemeral keypair of a decryption requester, used to receive blinded
+/// decryption shares
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RequesterKeypair(ferveo_tdec::RequesterKeypair<E>);
+
+impl RequesterKeypair {
+    pub fn random() -> Self {
+        Self(ferveo_tdec::RequesterKeypair::random(&mut thread_rng()))
+    }
+
+    pub fn public_key(&self) -> RequesterPublicKey {
+        RequesterPublicKey(self.0.public_key())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequesterPublicKey(ferveo_tdec::RequesterPublicKey<E>);
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindedDecryptionShareSimple {
+    share: ferveo_tdec::BlindedDecryptionShareSimple<E>,
+    #[serde_as(as = "serialization::SerdeAs")]
+    domain_point: DomainPoint<E>,
+    generation: ShareGeneration,
+}
+
+impl BlindedDecryptionShareSimple {
+    /// Share generation of the aggregate this share was made with
+    pub fn generation(&self) -> ShareGeneration {
+        self.generation
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindedDecryptionSharePrecomputed {
+    share: ferveo_tdec::BlindedDecryptionSharePrecomputed<E>,
+    generation: ShareGeneration,
+}
+
+impl BlindedDecryptionSharePrecomputed {
+    /// Share generation of the aggregate this share was made with
+    pub fn generation(&self) -> ShareGeneration {
+        self.generation
+    }
+}
+
+/// Additively homomorphic encryption of a small integer, e.g. a vote.
+/// Ciphertexts can be summed, and only the sum is decrypted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalCiphertext(ferveo_tdec::ElGamalCiphertext<E>);
+
+impl Add for ElGamalCiphertext {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl std::iter::Sum for ElGamalCiphertext {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|ciphertext| ciphertext.0).sum())
+    }
+}
+
+/// An `ElGamalCiphertext` of one input to a tally, with a proof of knowledge
+/// of its randomness bound to the tally `context`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalBallot(ferveo_tdec::ElGamalBallot<E>);
+
+impl ElGamalBallot {
+    pub fn encrypt(
+        value: u64,
+        public_key: &DkgPublicKey,
+        context: &[u8],
+    ) -> Result<Self> {
+        Ok(Self(ferveo_tdec::ElGamalBallot::encrypt(
+            value,
+            &public_key.0,
+            context,
+            &mut thread_rng(),
+        )?))
+    }
+
+    pub fn ciphertext(&self) -> ElGamalCiphertext {
+        ElGamalCiphertext(self.0.ciphertext)
+    }
+}
+
+/// The sum of ElGamal ballots whose proofs were checked for a tally context.
+/// Validators only make decryption shares of verified tallies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedElGamalTally(ferveo_tdec::VerifiedElGamalTally<E>);
+
+impl VerifiedElGamalTally {
+    /// Fails with `ferveo_tdec::Error::InvalidElGamalBallot` if the proof of
+    /// any ballot doesn't verify for `context`
+    pub fn new(ballots: &[ElGamalBallot], context: &[u8]) -> Result<Self> {
+        let ballots: Vec<_> = ballots.iter().map(|ballot| ballot.0).collect();
+        Ok(Self(ferveo_tdec::VerifiedElGamalTally::new(
+            &ballots, context,
+        )?))
+    }
+
+    pub fn ciphertext(&self) -> ElGamalCiphertext {
+        ElGamalCiphertext(*self.0.ciphertext())
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalDecryptionShare {
+    share: ferveo_tdec::ElGamalDecryptionShare<E>,
+    #[serde_as(as = "serialization::SerdeAs")]
+    domain_point: DomainPoint<E>,
+    generation: ShareGeneration,
+}
+
+impl ElGamalDecryptionShare {
+    /// Share generation of the aggregate this share was made with
+    pub fn generation(&self) -> ShareGeneration {
+        self.generation
+    }
+}
+
+/// Fails with `Error::ShareGenerationMismatch` unless all shares were made
+/// with aggregates of the same share generation
+fn check_share_generations(
+    generations: impl IntoIterator<Item = ShareGeneration>,
+) -> Result<()> {
+    let mut generations = generations.into_iter();
+    if let Some(first) = generations.next() {
+        if let Some(other) = generations.find(|g| *g != first) {
+            return Err(Error::ShareGenerationMismatch(
+                first.number,
+                other.number,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Recover the plaintext of a homomorphic ElGamal ciphertext, which must not
+/// exceed `table.max_value()`.
+/// Fails with `Error::ShareGenerationMismatch` if the shares were made with
+/// aggregates of different share generations.
+pub fn combine_elgamal_shares(
+    ciphertext: &ElGamalCiphertext,
+    shares: &[ElGamalDecryptionShare],
+    table: &DiscreteLogTable<E>,
+) -> Result<u64> {
+    check_share_generations(shares.iter().map(|s| s.generation))?;
+    let domain_points: Vec<_> = shares.iter().map(|s| s.domain_point).collect();
+    let lagrange_coefficients = prepare_combine_simple::<E>(&domain_points);
+
+    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
+    Ok(ferveo_tdec::combine_elgamal_shares(
+        &ciphertext.0,
+        &shares,
+        &lagrange_coefficients,
+        table,
+    )?)
+}
+
+/// Fails with `Error::ShareGenerationMismatch` if the shares were made with
+/// aggregates of different share generations
+pub fn combine_blinded_shares_simple(
+    shares: &[BlindedDecryptionShareSimple],
+    requester_keypair: &RequesterKeypair,
+) -> Result<SharedSecret> {
+    check_share_generations(shares.iter().map(|s| s.generation))?;
+    let domain_points: Vec<_> = shares.iter().map(|s| s.domain_point).collect();
+    let lagrange_coefficients = prepare_combine_simple::<E>(&domain_points);
+
+    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
+    Ok(SharedSecret(ferveo_tdec::share_combine_blinded_simple(
+        &shares,
+        &lagrange_coefficients,
+        &requester_keypair.0,
+    )))
+}
+
+/// Fails with `Error::ShareGenerationMismatch` if the shares were made with
+/// aggregates of different share generations
+pub fn combine_blinded_shares_precomputed(
+    shares: &[BlindedDecryptionSharePrecomputed],
+    requester_keypair: &RequesterKeypair,
+) -> Result<SharedSecret> {
+    check_share_generations(shares.iter().map(|s| s.generation))?;
+    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
+    Ok(SharedSecret(
+        ferveo_tdec::share_combine_blinded_precomputed(
+            &shares,
+            &requester_keypair.0,
+        ),
+    ))
+}
+
+/// Fails with `Error::ShareGenerationMismatch` if the shares were made with
+/// aggregates of different share generations
+pub fn combine_shares_simple(
+    shares: &[DecryptionShareSimple],
+) -> Result<SharedSecret> {
+    check_share_generations(shares.iter().map(|s| s.generation))?;
+    let domain_points: Vec<_> = shares.iter().map(|s| s.domain_point).collect();
+    let lagrange_coefficients = prepare_combine_simple::<E>(&domain_points);
+
+    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
+    let shared_secret =
+        share_combine_simple(&shares, &lagrange_coefficients[..]);
+    Ok(SharedSecret(shared_secret))
+}
+
+/// Fails with `Error::ShareGenerationMismatch` if the shares were made with
+/// aggregates of different share generations
+pub fn combine_shares_precomputed(
+    shares: &[DecryptionSharePrecomputed],
+) -> Result<SharedSecret> {
+    check_share_generations(shares.iter().map(|s| s.generation))?;
+    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
+    Ok(SharedSecret(share_combine_precomputed(&shares)))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedSecret(pub ferveo_tdec::api::SharedSecret<E>);
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReEncryptionShare {
+    share: ferveo_tdec::ReEncryptionShare<E>,
+    #[serde_as(as = "serialization::SerdeAs")]
+    domain_point: DomainPoint<E>,
+    generation: ShareGeneration,
+}
+
+impl ReEncryptionShare {
+    /// Share generation of the aggregate this share was made with
+    pub fn generation(&self) -> ShareGeneration {
+        self.generation
+    }
+}
+
+/// A ciphertext header re-encrypted to the public key of another ritual
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReEncryptedHeader(ferveo_tdec::ReEncryptedHeader<E>);
+
+impl ReEncryptedHeader {
+    pub fn check(&self, aad: &[u8]) -> Result<bool> {
+        Ok(self.0.check(aad)?)
+    }
+
+    /// Recovers the shared secret of the original ciphertext from the shared
+    /// secret combined by the target ritual
+    pub fn unmask_shared_secret(
+        &self,
+        target_shared_secret: &SharedSecret,
+    ) -> SharedSecret {
+        SharedSecret(self.0.unmask_shared_secret(&target_shared_secret.0))
+    }
+}
+
+/// Combine re-encryption shares into a header that the ritual with the
+/// public key `target_public_key` can decrypt.
+/// Shares should be checked with `verify_reencryption_share` first.
+/// Fails with `Error::ShareGenerationMismatch` if the shares were made with
+/// aggregates of different share generations.
+pub fn combine_reencryption_shares(
+    ciphertext_header: &CiphertextHeader,
+    target_public_key: &DkgPublicKey,
+    shares: &[ReEncryptionShare],
+) -> Result<ReEncryptedHeader> {
+    check_share_generations(shares.iter().map(|s| s.generation))?;
+    let domain_points: Vec<_> = shares.iter().map(|s| s.domain_point).collect();
+    let lagrange_coefficients = prepare_combine_simple::<E>(&domain_points);
+
+    let shares: Vec<_> = shares.iter().cloned().map(|s| s.share).collect();
+    Ok(ReEncryptedHeader(ferveo_tdec::combine_reencryption_shares(
+        &ciphertext_header.0,
+        &target_public_key.0,
+        &shares,
+        &lagrange_coefficients,
+    )))
+}
+
+#[cfg(test)]
+mod test_ferveo_api {
+
+    use ark_ec::CurveGroup;
+    use ark_std::{iterable::Iterable, UniformRand};
+    use ferveo_common::{FromBytes, ToBytes};
     use ferveo_tdec::SecretBox;
     use itertools::{izip, Itertools};
     use rand::{
@@ -433,120 +1727,835 @@ mod test_ferveo_api {
     };
     use test_case::test_case;
 
-    use crate::{
-        api::*,
-        test_common::{gen_address, gen_keypairs, AAD, MSG, TAU},
-    };
+    use crate::{
+        api::*,
+        test_common::{gen_address, gen_keypairs, AAD, MSG, TAU},
+    };
+
+    type TestInputs =
+        (Vec<ValidatorMessage>, Vec<Validator>, Vec<ValidatorKeypair>);
+
+    const TALLY_CONTEXT: &[u8] = b"tally-context";
+
+    // TODO: validators_num - #197
+    fn make_test_inputs(
+        rng: &mut StdRng,
+        tau: u32,
+        security_threshold: u32,
+        shares_num: u32,
+        validators_num: u32,
+    ) -> TestInputs {
+        let validator_keypairs = gen_keypairs(validators_num);
+        let validators = validator_keypairs
+            .iter()
+            .enumerate()
+            .map(|(i, keypair)| Validator {
+                address: gen_address(i),
+                public_key: keypair.public_key(),
+                share_index: i as u32,
+            })
+            .collect::<Vec<_>>();
+
+        // Each validator holds their own DKG instance and generates a transcript every
+        // validator, including themselves
+        let mut messages: Vec<_> = validators
+            .iter()
+            .map(|sender| {
+                let dkg = Dkg::new(
+                    tau,
+                    shares_num,
+                    security_threshold,
+                    &validators,
+                    sender,
+                )
+                .unwrap();
+                (sender.clone(), dkg.0.generate_transcript(rng).unwrap())
+            })
+            .collect();
+        messages.shuffle(rng);
+        (messages, validators, validator_keypairs)
+    }
+
+    fn random_dkg_public_key() -> DkgPublicKey {
+        let mut rng = thread_rng();
+        let g1 = G1Affine::rand(&mut rng);
+        DkgPublicKey(ferveo_tdec::DkgPublicKey(g1))
+    }
+
+    #[test]
+    fn test_encrypt_with_key() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (messages, _, _) = make_test_inputs(rng, TAU, 3, 4, 4);
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+        let dkg_public_key = aggregate.public_key();
+
+        let encryption_key = EncryptionKey::new(&dkg_public_key);
+        let encryption_key =
+            EncryptionKey::from_bytes(&encryption_key.to_bytes().unwrap())
+                .unwrap();
+        assert_eq!(encryption_key.public_key(), dkg_public_key);
+
+        let ciphertext = encrypt_with_key(
+            SecretBox::new(MSG.to_vec()),
+            AAD,
+            &encryption_key,
+        )
+        .unwrap();
+        assert!(ciphertext.header().unwrap().0.check(AAD).is_ok());
+    }
+
+    #[test]
+    fn test_encrypt_payloads() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (shares_num, security_threshold) = (4, 3);
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            shares_num,
+        );
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+
+        let payload_aads: [&[u8]; 3] = [b"aad-0", b"aad-1", b"aad-2"];
+        let payloads = payload_aads
+            .into_iter()
+            .map(|payload_aad| (SecretBox::new(MSG.to_vec()), payload_aad))
+            .collect::<Vec<_>>();
+        let ciphertext =
+            encrypt_payloads(&payloads, AAD, &aggregate.public_key()).unwrap();
+        assert_eq!(ciphertext.payloads_num(), 3);
+
+        // One set of decryption shares unlocks every payload
+        let decryption_shares = izip!(&validators, &validator_keypairs)
+            .take(security_threshold as usize)
+            .map(|(validator, validator_keypair)| {
+                let dkg = Dkg::new(
+                    TAU,
+                    shares_num,
+                    security_threshold,
+                    &validators,
+                    validator,
+                )
+                .unwrap();
+                aggregate
+                    .create_decryption_share_simple(
+                        &dkg,
+                        &ciphertext.header().unwrap(),
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let shared_secret = combine_shares_simple(&decryption_shares).unwrap();
+        for (index, payload_aad) in payload_aads.into_iter().enumerate() {
+            let plaintext = ciphertext
+                .decrypt_payload(index as u32, AAD, payload_aad, &shared_secret)
+                .unwrap();
+            assert_eq!(plaintext, MSG);
+        }
+    }
+
+    #[test]
+    fn test_dkg_pk_serialization() {
+        let dkg_pk = random_dkg_public_key();
+        let serialized = dkg_pk.to_bytes().unwrap();
+        let deserialized = DkgPublicKey::from_bytes(&serialized).unwrap();
+        assert_eq!(serialized.len(), DkgPublicKey::serialized_size());
+        assert_eq!(dkg_pk, deserialized);
+    }
+
+    #[test_case(4, 3; "N is a power of 2, t is 1 + 50%")]
+    #[test_case(4, 4; "N is a power of 2, t=N")]
+    #[test_case(30, 16; "N is not a power of 2, t is 1 + 50%")]
+    #[test_case(30, 30; "N is not a power of 2, t=N")]
+    fn test_server_api_tdec_precomputed(
+        shares_num: u32,
+        security_threshold: u32,
+    ) {
+        let validators_num = shares_num; // TODO: #197
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            validators_num,
+        );
+        // We only need `shares_num` transcripts to aggregate
+        let messages = &messages[..shares_num as usize];
+
+        // Every validator can aggregate the transcripts
+        let me = validators[0].clone();
+        let dkg =
+            Dkg::new(TAU, shares_num, security_threshold, &validators, &me)
+                .unwrap();
+        let local_aggregate = dkg.aggregate_transcripts(messages).unwrap();
+        assert!(local_aggregate.verify(validators_num, messages).unwrap());
+
+        // At this point, any given validator should be able to provide a DKG public key
+        let dkg_public_key = local_aggregate.public_key();
+
+        // In the meantime, the client creates a ciphertext and decryption request
+        let ciphertext =
+            encrypt(SecretBox::new(MSG.to_vec()), AAD, &dkg_public_key)
+                .unwrap();
+
+        // In precomputed variant, client selects a specific subset of validators to create
+        // decryption shares
+        let selected_validators: Vec<_> = validators
+            .choose_multiple(rng, security_threshold as usize)
+            .cloned()
+            .collect();
+
+        // Having aggregated the transcripts, the validators can now create decryption shares
+        let mut decryption_shares = selected_validators
+            .iter()
+            .map(|validator| {
+                let validator_keypair = validator_keypairs
+                    .iter()
+                    .find(|kp| kp.public_key() == validator.public_key)
+                    .unwrap();
+                // Each validator holds their own instance of DKG and creates their own aggregate
+                let dkg = Dkg::new(
+                    TAU,
+                    shares_num,
+                    security_threshold,
+                    &validators,
+                    validator,
+                )
+                .unwrap();
+                let server_aggregate =
+                    dkg.aggregate_transcripts(messages).unwrap();
+                assert!(server_aggregate
+                    .verify(validators_num, messages)
+                    .unwrap());
+
+                // And then each validator creates their own decryption share
+                server_aggregate
+                    .create_decryption_share_precomputed(
+                        &dkg,
+                        &ciphertext.header().unwrap(),
+                        AAD,
+                        validator_keypair,
+                        &selected_validators,
+                    )
+                    .unwrap()
+            })
+            // We only need `security_threshold` shares to be able to decrypt
+            .take(security_threshold as usize)
+            .collect::<Vec<DecryptionSharePrecomputed>>();
+        decryption_shares.shuffle(rng);
+
+        // Now, the decryption share can be used to decrypt the ciphertext
+        // This part is part of the client API
+        let shared_secret =
+            combine_shares_precomputed(&decryption_shares).unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+
+        // We need `security_threshold` shares to be able to decrypt
+        // So if we remove one share, we should not be able to decrypt
+        let decryption_shares = decryption_shares
+            .iter()
+            .take(security_threshold as usize - 1)
+            .cloned()
+            .collect::<Vec<_>>();
+        let shared_secret =
+            combine_shares_precomputed(&decryption_shares).unwrap();
+        let result =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret);
+        assert!(result.is_err());
+    }
+
+    #[test_case(4, 3; "N is a power of 2, t is 1 + 50%")]
+    #[test_case(4, 4; "N is a power of 2, t=N")]
+    #[test_case(30, 16; "N is not a power of 2, t is 1 + 50%")]
+    #[test_case(30, 30; "N is not a power of 2, t=N")]
+    fn test_server_api_tdec_simple(shares_num: u32, security_threshold: u32) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let validators_num: u32 = shares_num; // TODO: #197
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            validators_num,
+        );
+        // We only need `shares_num` transcripts to aggregate
+        let messages = &messages[..shares_num as usize];
+
+        // Now that every validator holds a dkg instance and a transcript for every other validator,
+        // every validator can aggregate the transcripts
+        let local_aggregate = AggregatedTranscript::new(messages).unwrap();
+        assert!(local_aggregate.verify(validators_num, messages).unwrap());
+
+        // At this point, any given validator should be able to provide a DKG public key
+        let public_key = local_aggregate.public_key();
+
+        // In the meantime, the client creates a ciphertext and decryption request
+        let ciphertext =
+            encrypt(SecretBox::new(MSG.to_vec()), AAD, &public_key).unwrap();
+
+        // Having aggregated the transcripts, the validators can now create decryption shares
+        let mut decryption_shares: Vec<_> =
+            izip!(&validators, &validator_keypairs)
+                .map(|(validator, validator_keypair)| {
+                    // Each validator holds their own instance of DKG and creates their own aggregate
+                    let dkg = Dkg::new(
+                        TAU,
+                        shares_num,
+                        security_threshold,
+                        &validators,
+                        validator,
+                    )
+                    .unwrap();
+                    let server_aggregate =
+                        dkg.aggregate_transcripts(messages).unwrap();
+                    assert!(server_aggregate
+                        .verify(validators_num, messages)
+                        .unwrap());
+                    server_aggregate
+                        .create_decryption_share_simple(
+                            &dkg,
+                            &ciphertext.header().unwrap(),
+                            AAD,
+                            validator_keypair,
+                        )
+                        .unwrap()
+                })
+                // We only need `security_threshold` shares to be able to decrypt
+                .take(security_threshold as usize)
+                .collect();
+        decryption_shares.shuffle(rng);
+
+        // Now, the decryption share can be used to decrypt the ciphertext
+        // This part is part of the client API
+        let decryption_shares =
+            decryption_shares[..security_threshold as usize].to_vec();
 
-    type TestInputs =
-        (Vec<ValidatorMessage>, Vec<Validator>, Vec<ValidatorKeypair>);
+        let shared_secret = combine_shares_simple(&decryption_shares).unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
 
-    // TODO: validators_num - #197
-    fn make_test_inputs(
-        rng: &mut StdRng,
-        tau: u32,
-        security_threshold: u32,
+        // We need `security_threshold` shares to be able to decrypt
+        // So if we remove one share, we should not be able to decrypt
+        let decryption_shares =
+            decryption_shares[..security_threshold as usize - 1].to_vec();
+
+        let shared_secret = combine_shares_simple(&decryption_shares).unwrap();
+        let result =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret);
+        assert!(result.is_err());
+    }
+
+    #[test_case(4, 3; "N is a power of 2, t is 1 + 50%")]
+    #[test_case(30, 16; "N is not a power of 2, t is 1 + 50%")]
+    fn test_server_api_decryption_service(
         shares_num: u32,
-        validators_num: u32,
-    ) -> TestInputs {
-        let validator_keypairs = gen_keypairs(validators_num);
-        let validators = validator_keypairs
-            .iter()
-            .enumerate()
-            .map(|(i, keypair)| Validator {
-                address: gen_address(i),
-                public_key: keypair.public_key(),
-                share_index: i as u32,
-            })
-            .collect::<Vec<_>>();
+        security_threshold: u32,
+    ) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let validators_num = shares_num;
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            validators_num,
+        );
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+        let ciphertext =
+            encrypt(SecretBox::new(MSG.to_vec()), AAD, &aggregate.public_key())
+                .unwrap();
+        let ciphertext_header = ciphertext.header().unwrap();
+        let verified_header = ciphertext.verify(AAD).unwrap();
+        assert_eq!(verified_header.header(), ciphertext_header);
+        let selected_validators: Vec<_> = validators
+            .choose_multiple(rng, security_threshold as usize)
+            .cloned()
+            .collect();
 
-        // Each validator holds their own DKG instance and generates a transcript every
-        // validator, including themselves
-        let mut messages: Vec<_> = validators
-            .iter()
-            .map(|sender| {
+        let (simple_shares, precomputed_shares): (Vec<_>, Vec<_>) =
+            selected_validators
+                .iter()
+                .map(|validator| {
+                    let validator_keypair = validator_keypairs
+                        .iter()
+                        .find(|kp| kp.public_key() == validator.public_key)
+                        .unwrap();
+                    let dkg = Dkg::new(
+                        TAU,
+                        shares_num,
+                        security_threshold,
+                        &validators,
+                        validator,
+                    )
+                    .unwrap();
+                    let service = aggregate
+                        .create_decryption_service(&dkg, validator_keypair)
+                        .unwrap();
+
+                    // Shares are the same as the ones created without a service
+                    let simple = service
+                        .create_decryption_share_simple(&ciphertext_header, AAD)
+                        .unwrap();
+                    assert_eq!(
+                        simple,
+                        aggregate
+                            .create_decryption_share_simple(
+                                &dkg,
+                                &ciphertext_header,
+                                AAD,
+                                validator_keypair,
+                            )
+                            .unwrap()
+                    );
+                    // The second call hits the cache of lagrange coefficients
+                    let precomputed = (0..2)
+                        .map(|_| {
+                            service
+                                .create_decryption_share_precomputed(
+                                    &ciphertext_header,
+                                    AAD,
+                                    &selected_validators,
+                                )
+                                .unwrap()
+                        })
+                        .collect::<Vec<_>>();
+                    assert_eq!(precomputed[0], precomputed[1]);
+                    assert_eq!(
+                        precomputed[0],
+                        aggregate
+                            .create_decryption_share_precomputed(
+                                &dkg,
+                                &ciphertext_header,
+                                AAD,
+                                validator_keypair,
+                                &selected_validators,
+                            )
+                            .unwrap()
+                    );
+
+                    // And so are the ones for an already checked header
+                    assert_eq!(
+                        service.create_decryption_share_simple_verified(
+                            &verified_header
+                        ),
+                        simple
+                    );
+                    assert_eq!(
+                        service
+                            .create_decryption_share_precomputed_verified(
+                                &verified_header,
+                                &selected_validators,
+                            )
+                            .unwrap(),
+                        precomputed[0]
+                    );
+                    assert_eq!(
+                        aggregate
+                            .create_decryption_share_simple_verified(
+                                &dkg,
+                                &verified_header,
+                                validator_keypair,
+                            )
+                            .unwrap(),
+                        simple
+                    );
+                    assert_eq!(
+                        aggregate
+                            .create_decryption_share_precomputed_verified(
+                                &dkg,
+                                &verified_header,
+                                validator_keypair,
+                                &selected_validators,
+                            )
+                            .unwrap(),
+                        precomputed[0]
+                    );
+                    (simple, precomputed[0].clone())
+                })
+                .unzip();
+
+        let shared_secret = combine_shares_simple(&simple_shares).unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+
+        let shared_secret =
+            combine_shares_precomputed(&precomputed_shares).unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+        let plaintext = decrypt_with_verified_header(
+            &ciphertext,
+            &verified_header,
+            &shared_secret,
+        )
+        .unwrap();
+        assert_eq!(plaintext, MSG);
+    }
+
+    #[test]
+    fn test_reencryption_to_new_ritual() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (shares_num, security_threshold) = (4, 3);
+        let (new_shares_num, new_security_threshold) = (5, 3);
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            shares_num,
+        );
+        let (new_messages, new_validators, new_validator_keypairs) =
+            make_test_inputs(
+                rng,
+                TAU + 1,
+                new_security_threshold,
+                new_shares_num,
+                new_shares_num,
+            );
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+        let new_aggregate = AggregatedTranscript::new(&new_messages).unwrap();
+        let new_public_key = new_aggregate.public_key();
+
+        let ciphertext =
+            encrypt(SecretBox::new(MSG.to_vec()), AAD, &aggregate.public_key())
+                .unwrap();
+        let ciphertext_header = ciphertext.header().unwrap();
+
+        // The old committee re-encrypts the header, and anyone can verify
+        // their shares against the old ritual's transcript
+        let reencryption_shares = izip!(&validators, &validator_keypairs)
+            .take(security_threshold as usize)
+            .map(|(validator, validator_keypair)| {
                 let dkg = Dkg::new(
-                    tau,
+                    TAU,
                     shares_num,
                     security_threshold,
                     &validators,
-                    sender,
+                    validator,
                 )
                 .unwrap();
-                (sender.clone(), dkg.0.generate_transcript(rng).unwrap())
+                let share = aggregate
+                    .create_reencryption_share(
+                        &dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                        &new_public_key,
+                    )
+                    .unwrap();
+                assert!(aggregate
+                    .verify_reencryption_share(
+                        &share,
+                        &ciphertext_header,
+                        AAD,
+                        &new_public_key,
+                    )
+                    .unwrap());
+                share
+            })
+            .collect::<Vec<_>>();
+        // A share is only valid for the target ritual it was made for
+        assert!(!aggregate
+            .verify_reencryption_share(
+                &reencryption_shares[0],
+                &ciphertext_header,
+                AAD,
+                &aggregate.public_key(),
+            )
+            .unwrap());
+
+        let reencrypted_header = combine_reencryption_shares(
+            &ciphertext_header,
+            &new_public_key,
+            &reencryption_shares,
+        )
+        .unwrap();
+        assert!(reencrypted_header.check(AAD).unwrap());
+
+        // The new committee decrypts the re-encrypted header
+        let decryption_shares = izip!(&new_validators, &new_validator_keypairs)
+            .take(new_security_threshold as usize)
+            .map(|(validator, validator_keypair)| {
+                let dkg = Dkg::new(
+                    TAU + 1,
+                    new_shares_num,
+                    new_security_threshold,
+                    &new_validators,
+                    validator,
+                )
+                .unwrap();
+                new_aggregate
+                    .create_decryption_share_simple_for_reencrypted(
+                        &dkg,
+                        &reencrypted_header,
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let shared_secret = reencrypted_header.unmask_shared_secret(
+            &combine_shares_simple(&decryption_shares).unwrap(),
+        );
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+    }
+
+    #[test_case(4, 4; "N is a power of 2")]
+    #[test_case(7, 7; "N is not a power of 2")]
+    fn test_blinded_decryption_shares(
+        shares_num: u32,
+        security_threshold: u32,
+    ) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            shares_num,
+        );
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+        let ciphertext =
+            encrypt(SecretBox::new(MSG.to_vec()), AAD, &aggregate.public_key())
+                .unwrap();
+        let ciphertext_header = ciphertext.header().unwrap();
+        let requester_keypair = RequesterKeypair::random();
+        let requester_public_key = requester_keypair.public_key();
+        let other_requester_keypair = RequesterKeypair::random();
+
+        let (simple_shares, precomputed_shares): (Vec<_>, Vec<_>) =
+            izip!(&validators, &validator_keypairs)
+                .map(|(validator, validator_keypair)| {
+                    let dkg = Dkg::new(
+                        TAU,
+                        shares_num,
+                        security_threshold,
+                        &validators,
+                        validator,
+                    )
+                    .unwrap();
+                    let simple = aggregate
+                        .create_blinded_decryption_share_simple(
+                            &dkg,
+                            &ciphertext_header,
+                            AAD,
+                            validator_keypair,
+                            &requester_public_key,
+                        )
+                        .unwrap();
+                    assert!(aggregate
+                        .verify_blinded_decryption_share_simple(
+                            &simple,
+                            &ciphertext_header,
+                            &requester_public_key,
+                        )
+                        .unwrap());
+                    assert!(!aggregate
+                        .verify_blinded_decryption_share_simple(
+                            &simple,
+                            &ciphertext_header,
+                            &other_requester_keypair.public_key(),
+                        )
+                        .unwrap());
+
+                    let precomputed = aggregate
+                        .create_blinded_decryption_share_precomputed(
+                            &dkg,
+                            &ciphertext_header,
+                            AAD,
+                            validator_keypair,
+                            &validators,
+                            &requester_public_key,
+                        )
+                        .unwrap();
+                    assert!(aggregate
+                        .verify_blinded_decryption_share_precomputed(
+                            &dkg,
+                            &precomputed,
+                            &ciphertext_header,
+                            &validators,
+                            &requester_public_key,
+                        )
+                        .unwrap());
+                    (simple, precomputed)
+                })
+                .unzip();
+
+        let shared_secret =
+            combine_blinded_shares_simple(&simple_shares, &requester_keypair)
+                .unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+
+        let shared_secret = combine_blinded_shares_precomputed(
+            &precomputed_shares,
+            &requester_keypair,
+        )
+        .unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+
+        // Only the requester can combine the shares
+        let shared_secret = combine_blinded_shares_simple(
+            &simple_shares,
+            &other_requester_keypair,
+        )
+        .unwrap();
+        assert!(decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+            .is_err());
+    }
+
+    #[test]
+    fn test_multi_ritual_policy() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (shares_num, security_threshold) = (4, 3);
+        let rituals = (0..2)
+            .map(|_| {
+                make_test_inputs(
+                    rng,
+                    TAU,
+                    security_threshold,
+                    shares_num,
+                    shares_num,
+                )
+            })
+            .collect::<Vec<_>>();
+        let aggregates = rituals
+            .iter()
+            .map(|(messages, _, _)| {
+                AggregatedTranscript::new(messages).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let public_keys = aggregates
+            .iter()
+            .enumerate()
+            .map(|(ritual_id, aggregate)| {
+                (ritual_id as u32, aggregate.public_key())
             })
-            .collect();
-        messages.shuffle(rng);
-        (messages, validators, validator_keypairs)
-    }
+            .collect::<HashMap<_, _>>();
 
-    fn random_dkg_public_key() -> DkgPublicKey {
-        let mut rng = thread_rng();
-        let g1 = G1Affine::rand(&mut rng);
-        DkgPublicKey(ferveo_tdec::DkgPublicKey(g1))
-    }
+        // Each ritual decrypts its component like any other ciphertext
+        let decrypt_component = |ciphertext: &MultiKeyCiphertext,
+                                 index: u32| {
+            let ritual_id =
+                ciphertext.component_ritual(index).unwrap() as usize;
+            let (_, validators, validator_keypairs) = &rituals[ritual_id];
+            let header = ciphertext.component_header(index).unwrap();
+            let aad = ciphertext.component_aad(AAD).unwrap();
+            let shares = izip!(validators, validator_keypairs)
+                .take(security_threshold as usize)
+                .map(|(validator, validator_keypair)| {
+                    let dkg = Dkg::new(
+                        TAU,
+                        shares_num,
+                        security_threshold,
+                        validators,
+                        validator,
+                    )
+                    .unwrap();
+                    aggregates[ritual_id]
+                        .create_decryption_share_simple(
+                            &dkg,
+                            &header,
+                            &aad,
+                            validator_keypair,
+                        )
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            (index, combine_shares_simple(&shares).unwrap())
+        };
 
-    #[test]
-    fn test_dkg_pk_serialization() {
-        let dkg_pk = random_dkg_public_key();
-        let serialized = dkg_pk.to_bytes().unwrap();
-        let deserialized = DkgPublicKey::from_bytes(&serialized).unwrap();
-        assert_eq!(serialized.len(), 48_usize);
-        assert_eq!(dkg_pk, deserialized);
+        let both = AccessPolicy::All(vec![
+            AccessPolicy::Ritual(0),
+            AccessPolicy::Ritual(1),
+        ]);
+        let either = AccessPolicy::Any(vec![
+            AccessPolicy::Ritual(0),
+            AccessPolicy::Ritual(1),
+        ]);
+        for policy in [both, either] {
+            let ciphertext = encrypt_with_policy(
+                SecretBox::new(MSG.to_vec()),
+                AAD,
+                &policy,
+                &public_keys,
+            )
+            .unwrap();
+            assert_eq!(ciphertext.components_num(), 2);
+            let first = HashMap::from([decrypt_component(&ciphertext, 0)]);
+            let all = HashMap::from([
+                decrypt_component(&ciphertext, 0),
+                decrypt_component(&ciphertext, 1),
+            ]);
+
+            assert_eq!(ciphertext.decrypt(AAD, &all).unwrap(), MSG);
+            let result = ciphertext.decrypt(AAD, &first);
+            match ciphertext.policy() {
+                AccessPolicy::All(_) => assert!(result.is_err()),
+                _ => assert_eq!(result.unwrap(), MSG),
+            }
+        }
     }
 
-    #[test_case(4, 3; "N is a power of 2, t is 1 + 50%")]
-    #[test_case(4, 4; "N is a power of 2, t=N")]
-    #[test_case(30, 16; "N is not a power of 2, t is 1 + 50%")]
-    #[test_case(30, 30; "N is not a power of 2, t=N")]
-    fn test_server_api_tdec_precomputed(
-        shares_num: u32,
-        security_threshold: u32,
-    ) {
-        let validators_num = shares_num; // TODO: #197
+    #[test_case(4, 3; "N is a power of 2")]
+    #[test_case(7, 5; "N is not a power of 2")]
+    fn test_elgamal_tally(shares_num: u32, security_threshold: u32) {
         let rng = &mut StdRng::seed_from_u64(0);
         let (messages, validators, validator_keypairs) = make_test_inputs(
             rng,
             TAU,
             security_threshold,
             shares_num,
-            validators_num,
+            shares_num,
         );
-        // We only need `shares_num` transcripts to aggregate
-        let messages = &messages[..shares_num as usize];
-
-        // Every validator can aggregate the transcripts
-        let me = validators[0].clone();
-        let dkg =
-            Dkg::new(TAU, shares_num, security_threshold, &validators, &me)
-                .unwrap();
-        let local_aggregate = dkg.aggregate_transcripts(messages).unwrap();
-        assert!(local_aggregate.verify(validators_num, messages).unwrap());
-
-        // At this point, any given validator should be able to provide a DKG public key
-        let dkg_public_key = local_aggregate.public_key();
-
-        // In the meantime, the client creates a ciphertext and decryption request
-        let ciphertext =
-            encrypt(SecretBox::new(MSG.to_vec()), AAD, &dkg_public_key)
-                .unwrap();
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
 
-        // In precomputed variant, client selects a specific subset of validators to create
-        // decryption shares
-        let selected_validators: Vec<_> = validators
-            .choose_multiple(rng, security_threshold as usize)
-            .cloned()
-            .collect();
+        let votes = [1u64, 0, 1, 1, 0, 1];
+        let ballots = votes
+            .into_iter()
+            .map(|vote| {
+                ElGamalBallot::encrypt(
+                    vote,
+                    &aggregate.public_key(),
+                    TALLY_CONTEXT,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let verified_tally =
+            VerifiedElGamalTally::new(&ballots, TALLY_CONTEXT).unwrap();
+        let tally = verified_tally.ciphertext();
+        assert_eq!(
+            tally,
+            ballots
+                .iter()
+                .map(ElGamalBallot::ciphertext)
+                .sum::<ElGamalCiphertext>()
+        );
 
-        // Having aggregated the transcripts, the validators can now create decryption shares
-        let mut decryption_shares = selected_validators
-            .iter()
-            .map(|validator| {
-                let validator_keypair = validator_keypairs
-                    .iter()
-                    .find(|kp| kp.public_key() == validator.public_key)
-                    .unwrap();
-                // Each validator holds their own instance of DKG and creates their own aggregate
+        let shares = izip!(&validators, &validator_keypairs)
+            .take(security_threshold as usize)
+            .map(|(validator, validator_keypair)| {
                 let dkg = Dkg::new(
                     TAU,
                     shares_num,
@@ -555,89 +2564,75 @@ mod test_ferveo_api {
                     validator,
                 )
                 .unwrap();
-                let server_aggregate =
-                    dkg.aggregate_transcripts(messages).unwrap();
-                assert!(server_aggregate
-                    .verify(validators_num, messages)
-                    .unwrap());
-
-                // And then each validator creates their own decryption share
-                server_aggregate
-                    .create_decryption_share_precomputed(
+                let share = aggregate
+                    .create_elgamal_decryption_share(
                         &dkg,
-                        &ciphertext.header().unwrap(),
-                        AAD,
+                        &verified_tally,
                         validator_keypair,
-                        &selected_validators,
                     )
-                    .unwrap()
+                    .unwrap();
+                assert!(aggregate
+                    .verify_elgamal_decryption_share(&share, &tally)
+                    .unwrap());
+                share
             })
-            // We only need `security_threshold` shares to be able to decrypt
-            .take(security_threshold as usize)
-            .collect::<Vec<DecryptionSharePrecomputed>>();
-        decryption_shares.shuffle(rng);
+            .collect::<Vec<_>>();
 
-        // Now, the decryption share can be used to decrypt the ciphertext
-        // This part is part of the client API
-        let shared_secret = share_combine_precomputed(&decryption_shares);
-        let plaintext = decrypt_with_shared_secret(
-            &ciphertext,
-            AAD,
-            &SharedSecret(shared_secret),
-        )
-        .unwrap();
-        assert_eq!(plaintext, MSG);
+        // Shares of the tally don't verify for an individual vote
+        let vote = ballots[0].ciphertext();
+        assert!(!aggregate
+            .verify_elgamal_decryption_share(&shares[0], &vote)
+            .unwrap());
 
-        // We need `security_threshold` shares to be able to decrypt
-        // So if we remove one share, we should not be able to decrypt
-        let decryption_shares = decryption_shares
-            .iter()
-            .take(security_threshold as usize - 1)
-            .cloned()
-            .collect::<Vec<_>>();
-        let shared_secret = share_combine_precomputed(&decryption_shares);
-        let result = decrypt_with_shared_secret(
-            &ciphertext,
-            AAD,
-            &SharedSecret(shared_secret),
-        );
-        assert!(result.is_err());
+        let table = DiscreteLogTable::new(votes.len() as u64);
+        let result = combine_elgamal_shares(&tally, &shares, &table).unwrap();
+        assert_eq!(result, votes.iter().sum::<u64>());
+
+        // Ballots made for another tally are rejected
+        assert!(matches!(
+            VerifiedElGamalTally::new(&ballots, b"other-tally"),
+            Err(Error::ThresholdEncryptionError(
+                ferveo_tdec::Error::InvalidElGamalBallot(0)
+            ))
+        ));
     }
 
-    #[test_case(4, 3; "N is a power of 2, t is 1 + 50%")]
-    #[test_case(4, 4; "N is a power of 2, t=N")]
-    #[test_case(30, 16; "N is not a power of 2, t is 1 + 50%")]
-    #[test_case(30, 30; "N is not a power of 2, t=N")]
-    fn test_server_api_tdec_simple(shares_num: u32, security_threshold: u32) {
+    struct SimulatedClock(std::cell::Cell<u64>);
+
+    impl EpochClock for SimulatedClock {
+        fn current_epoch(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn test_epoch_gated_encryption() {
         let rng = &mut StdRng::seed_from_u64(0);
-        let validators_num: u32 = shares_num; // TODO: #197
+        let (shares_num, security_threshold) = (4, 3);
         let (messages, validators, validator_keypairs) = make_test_inputs(
             rng,
             TAU,
             security_threshold,
             shares_num,
-            validators_num,
+            shares_num,
         );
-        // We only need `shares_num` transcripts to aggregate
-        let messages = &messages[..shares_num as usize];
-
-        // Now that every validator holds a dkg instance and a transcript for every other validator,
-        // every validator can aggregate the transcripts
-        let local_aggregate = AggregatedTranscript::new(messages).unwrap();
-        assert!(local_aggregate.verify(validators_num, messages).unwrap());
-
-        // At this point, any given validator should be able to provide a DKG public key
-        let public_key = local_aggregate.public_key();
-
-        // In the meantime, the client creates a ciphertext and decryption request
-        let ciphertext =
-            encrypt(SecretBox::new(MSG.to_vec()), AAD, &public_key).unwrap();
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+        let epoch = 100;
+        let ciphertext = encrypt_to_epoch(
+            SecretBox::new(MSG.to_vec()),
+            AAD,
+            epoch,
+            &aggregate.public_key(),
+        )
+        .unwrap();
+        let ciphertext_header = ciphertext.header().unwrap();
+        assert_eq!(ciphertext_header.epoch(), epoch);
 
-        // Having aggregated the transcripts, the validators can now create decryption shares
-        let mut decryption_shares: Vec<_> =
+        let clock = SimulatedClock(std::cell::Cell::new(0));
+        let create_shares = |clock: &SimulatedClock| {
             izip!(&validators, &validator_keypairs)
+                .take(security_threshold as usize)
                 .map(|(validator, validator_keypair)| {
-                    // Each validator holds their own instance of DKG and creates their own aggregate
                     let dkg = Dkg::new(
                         TAU,
                         shares_num,
@@ -646,45 +2641,165 @@ mod test_ferveo_api {
                         validator,
                     )
                     .unwrap();
-                    let server_aggregate =
-                        dkg.aggregate_transcripts(messages).unwrap();
-                    assert!(server_aggregate
-                        .verify(validators_num, messages)
-                        .unwrap());
-                    server_aggregate
-                        .create_decryption_share_simple(
-                            &dkg,
-                            &ciphertext.header().unwrap(),
-                            AAD,
-                            validator_keypair,
-                        )
-                        .unwrap()
+                    aggregate.create_epoch_gated_decryption_share_simple(
+                        &dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                        clock,
+                    )
                 })
-                // We only need `security_threshold` shares to be able to decrypt
-                .take(security_threshold as usize)
-                .collect();
-        decryption_shares.shuffle(rng);
+                .collect::<Result<Vec<_>>>()
+        };
 
-        // Now, the decryption share can be used to decrypt the ciphertext
-        // This part is part of the client API
-        let decryption_shares =
-            decryption_shares[..security_threshold as usize].to_vec();
+        // Before the epoch, validators don't release their shares
+        for current_epoch in [0, epoch - 1] {
+            clock.0.set(current_epoch);
+            assert!(matches!(
+                create_shares(&clock),
+                Err(Error::ThresholdEncryptionError(
+                    ferveo_tdec::Error::EpochNotReached(..)
+                ))
+            ));
+        }
 
-        let shared_secret = combine_shares_simple(&decryption_shares);
-        let plaintext =
-            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
-                .unwrap();
+        clock.0.set(epoch);
+        let decryption_shares = create_shares(&clock).unwrap();
+        let shared_secret = combine_shares_simple(&decryption_shares).unwrap();
+        let plaintext = ciphertext
+            .decrypt_with_shared_secret(AAD, &shared_secret)
+            .unwrap();
         assert_eq!(plaintext, MSG);
+    }
 
-        // We need `security_threshold` shares to be able to decrypt
-        // So if we remove one share, we should not be able to decrypt
-        let decryption_shares =
-            decryption_shares[..security_threshold as usize - 1].to_vec();
+    #[test_case(4, 3; "N is a power of 2")]
+    #[test_case(7, 5; "N is not a power of 2")]
+    fn test_decryption_share_proofs(shares_num: u32, security_threshold: u32) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (messages, validators, validator_keypairs) = make_test_inputs(
+            rng,
+            TAU,
+            security_threshold,
+            shares_num,
+            shares_num,
+        );
+        let aggregate = AggregatedTranscript::new(&messages).unwrap();
+        let ciphertext =
+            encrypt(SecretBox::new(MSG.to_vec()), AAD, &aggregate.public_key())
+                .unwrap();
+        let ciphertext_header = ciphertext.header().unwrap();
+        let selected_validators = &validators[..security_threshold as usize];
+
+        let simple_shares = izip!(selected_validators, &validator_keypairs)
+            .map(|(validator, validator_keypair)| {
+                let dkg = Dkg::new(
+                    TAU,
+                    shares_num,
+                    security_threshold,
+                    &validators,
+                    validator,
+                )
+                .unwrap();
+                let share = aggregate
+                    .create_decryption_share_simple(
+                        &dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap();
+                let proof = aggregate
+                    .create_decryption_share_proof_simple(
+                        &dkg,
+                        &share,
+                        &ciphertext_header,
+                        validator_keypair,
+                    )
+                    .unwrap();
+                assert!(aggregate
+                    .verify_decryption_share_simple(
+                        &share,
+                        &proof,
+                        &ciphertext_header,
+                    )
+                    .unwrap());
+
+                let precomputed = aggregate
+                    .create_decryption_share_precomputed(
+                        &dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                        selected_validators,
+                    )
+                    .unwrap();
+                let precomputed_proof = aggregate
+                    .create_decryption_share_proof_precomputed(
+                        &dkg,
+                        &precomputed,
+                        &ciphertext_header,
+                        validator_keypair,
+                        selected_validators,
+                    )
+                    .unwrap();
+                assert!(aggregate
+                    .verify_decryption_share_precomputed(
+                        &dkg,
+                        &precomputed,
+                        &precomputed_proof,
+                        &ciphertext_header,
+                        selected_validators,
+                    )
+                    .unwrap());
+                // The proof is bound to the set of selected validators
+                assert!(!aggregate
+                    .verify_decryption_share_precomputed(
+                        &dkg,
+                        &precomputed,
+                        &precomputed_proof,
+                        &ciphertext_header,
+                        &validators,
+                    )
+                    .unwrap());
+                share
+            })
+            .collect::<Vec<_>>();
+
+        // A proof doesn't verify for another validator's share
+        let dkg = Dkg::new(
+            TAU,
+            shares_num,
+            security_threshold,
+            &validators,
+            &validators[0],
+        )
+        .unwrap();
+        let proof = aggregate
+            .create_decryption_share_proof_simple(
+                &dkg,
+                &simple_shares[0],
+                &ciphertext_header,
+                &validator_keypairs[0],
+            )
+            .unwrap();
+        assert!(!aggregate
+            .verify_decryption_share_simple(
+                &simple_shares[1],
+                &proof,
+                &ciphertext_header,
+            )
+            .unwrap());
 
-        let shared_secret = combine_shares_simple(&decryption_shares);
-        let result =
-            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret);
-        assert!(result.is_err());
+        // Proofs survive a serialization roundtrip
+        let bytes = bincode::serialize(&proof).unwrap();
+        let proof: DecryptionShareProof = bincode::deserialize(&bytes).unwrap();
+        assert!(aggregate
+            .verify_decryption_share_simple(
+                &simple_shares[0],
+                &proof,
+                &ciphertext_header,
+            )
+            .unwrap());
     }
 
     /// Note that the server and client code are using the same underlying
@@ -949,191 +3064,626 @@ mod test_ferveo_api {
         )
     }
 
-    // FIXME: This test is currently broken, and adjusted to allow compilation
-    // Also, see test cases in other tests that include threshold as a parameter
-    #[ignore = "Re-introduce recovery tests - #193"]
-    #[test_case(4, 4, true; "number of shares (validators) is a power of 2")]
-    #[test_case(7, 7, true; "number of shares (validators) is not a power of 2")]
-    #[test_case(4, 6, true; "number of validators greater than the number of shares")]
-    #[test_case(4, 6, false; "recovery at a specific point")]
-    fn test_dkg_simple_tdec_share_recovery(
+    #[test_case(4, 3; "N is a power of 2")]
+    #[test_case(7, 5; "N is not a power of 2")]
+    fn test_dkg_simple_tdec_share_recovery(
+        shares_num: u32,
+        security_threshold: u32,
+    ) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let validators_num = shares_num;
+        let (
+            messages,
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            old_shared_secret,
+        ) = make_share_update_test_inputs(
+            shares_num,
+            validators_num,
+            rng,
+            security_threshold,
+        );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+
+        // The last validator lost its key share, and recovers it under a new key
+        let recovery_index = validators.last().unwrap().share_index;
+        let recovering_keypair = ValidatorKeypair::new(rng);
+        let helpers = &dkgs[..security_threshold as usize];
+        let helper_indices = helpers
+            .iter()
+            .map(|dkg| dkg.me().share_index)
+            .collect::<Vec<_>>();
+        let helper_keys_map = helper_indices
+            .iter()
+            .map(|share_index| {
+                let keypair = &validator_keypairs[*share_index as usize];
+                (*share_index, keypair.public_key())
+            })
+            .collect::<HashMap<_, _>>();
+
+        // Each helper deals a recovery transcript to all helpers
+        let recovery_transcripts = helpers
+            .iter()
+            .map(|dkg| {
+                let transcript = dkg
+                    .generate_recovery_transcript(
+                        &helper_indices,
+                        recovery_index,
+                        rng,
+                    )
+                    .unwrap();
+                (dkg.me().share_index, transcript)
+            })
+            .collect::<HashMap<_, _>>();
+
+        // The recovering validator sends a handover transcript to each helper,
+        // who answers with a recovery share
+        let recovery_shares = helper_indices
+            .iter()
+            .map(|&helper_index| {
+                let handover_transcript = dkgs[0]
+                    .generate_recovery_handover_transcript(
+                        &aggregate,
+                        &recovery_transcripts,
+                        helper_index,
+                        &recovering_keypair,
+                        rng,
+                    )
+                    .unwrap();
+                aggregate
+                    .create_recovery_share(
+                        &recovery_transcripts,
+                        &helper_keys_map,
+                        recovery_index,
+                        &handover_transcript,
+                        &validator_keypairs[helper_index as usize],
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Helpers refuse handovers of their unmasked shares
+        let handover_transcript = dkgs[0]
+            .generate_handover_transcript(
+                &aggregate,
+                helper_indices[0],
+                &recovering_keypair,
+                rng,
+            )
+            .unwrap();
+        assert!(aggregate
+            .create_recovery_share(
+                &recovery_transcripts,
+                &helper_keys_map,
+                recovery_index,
+                &handover_transcript,
+                &validator_keypairs[helper_indices[0] as usize],
+            )
+            .is_err());
+
+        // Anyone can combine the recovery shares, which are checked against
+        // the commitments of the aggregate
+        let recovered = aggregate
+            .recover_share(
+                &recovery_transcripts,
+                &helper_keys_map,
+                recovery_index,
+                &recovery_shares,
+            )
+            .unwrap();
+        assert_eq!(recovered.public_key(), aggregate.public_key());
+        assert!(matches!(
+            aggregate.recover_share(
+                &recovery_transcripts,
+                &helper_keys_map,
+                recovery_index,
+                &recovery_shares[1..],
+            ),
+            Err(Error::InsufficientRecoveryHelpers(..))
+        ));
+
+        // The recovering validator decrypts together with the other validators
+        let mut new_validators = validators.clone();
+        new_validators.last_mut().unwrap().public_key =
+            recovering_keypair.public_key();
+        let recovering_dkg = Dkg::new(
+            TAU,
+            shares_num,
+            security_threshold,
+            &new_validators,
+            new_validators.last().unwrap(),
+        )
+        .unwrap();
+        let mut decryption_shares = izip!(&dkgs, &validator_keypairs)
+            .take(security_threshold as usize - 1)
+            .map(|(dkg, validator_keypair)| {
+                recovered
+                    .create_decryption_share_simple(
+                        dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        decryption_shares.push(
+            recovered
+                .create_decryption_share_simple(
+                    &recovering_dkg,
+                    &ciphertext_header,
+                    AAD,
+                    &recovering_keypair,
+                )
+                .unwrap(),
+        );
+        decryption_shares.shuffle(rng);
+
+        let new_shared_secret =
+            combine_shares_simple(&decryption_shares).unwrap();
+        assert_eq!(
+            old_shared_secret, new_shared_secret,
+            "Shared secret reconstruction failed"
+        );
+    }
+
+    #[test]
+    fn test_dkg_simple_tdec_verifiable_handover() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (shares_num, security_threshold) = (4, 3);
+        let (
+            messages,
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            old_shared_secret,
+        ) = make_share_update_test_inputs(
+            shares_num,
+            shares_num,
+            rng,
+            security_threshold,
+        );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+
+        // The last validator hands over its share to an incoming validator
+        let handover_index = validators.last().unwrap().share_index;
+        let departing_keypair = &validator_keypairs[handover_index as usize];
+        let incoming_keypair = ValidatorKeypair::new(rng);
+        let handover_transcript = dkgs[0]
+            .generate_handover_transcript(
+                &aggregate,
+                handover_index,
+                &incoming_keypair,
+                rng,
+            )
+            .unwrap();
+
+        // Only the departing validator can produce the result
+        assert!(aggregate
+            .create_handover_result(
+                &handover_transcript,
+                &validator_keypairs[0],
+                rng
+            )
+            .is_err());
+        let handover_result = aggregate
+            .create_handover_result(
+                &handover_transcript,
+                departing_keypair,
+                rng,
+            )
+            .unwrap();
+
+        // Any party applies it to its own copy of the aggregate, with the same
+        // outcome as the departing validator's finalization
+        let new_aggregate = dkgs[1]
+            .aggregate_transcripts(&messages)
+            .unwrap()
+            .apply_handover_result(&handover_result)
+            .unwrap();
+        assert_eq!(
+            new_aggregate,
+            aggregate
+                .finalize_handover(&handover_transcript, departing_keypair)
+                .unwrap()
+        );
+
+        // A result can't be applied once the share was handed over, nor
+        // tampered with
+        assert!(matches!(
+            new_aggregate.apply_handover_result(&handover_result),
+            Err(Error::InvalidHandoverResult(..))
+        ));
+        let mut forged_result = handover_result.clone();
+        forged_result.0.blinded_key_share =
+            (forged_result.0.blinded_key_share * Fr::from(2u64)).into_affine();
+        assert!(matches!(
+            aggregate.apply_handover_result(&forged_result),
+            Err(Error::InvalidHandoverResult(..))
+        ));
+
+        // The incoming validator decrypts together with the other validators
+        let mut new_validators = validators.clone();
+        new_validators.last_mut().unwrap().public_key =
+            incoming_keypair.public_key();
+        let incoming_dkg = Dkg::new(
+            TAU,
+            shares_num,
+            security_threshold,
+            &new_validators,
+            new_validators.last().unwrap(),
+        )
+        .unwrap();
+        let mut decryption_shares = izip!(&dkgs, &validator_keypairs)
+            .take(security_threshold as usize - 1)
+            .map(|(dkg, validator_keypair)| {
+                new_aggregate
+                    .create_decryption_share_simple(
+                        dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        decryption_shares.push(
+            new_aggregate
+                .create_decryption_share_simple(
+                    &incoming_dkg,
+                    &ciphertext_header,
+                    AAD,
+                    &incoming_keypair,
+                )
+                .unwrap(),
+        );
+        decryption_shares.shuffle(rng);
+
+        let new_shared_secret =
+            combine_shares_simple(&decryption_shares).unwrap();
+        assert_eq!(
+            old_shared_secret, new_shared_secret,
+            "Shared secret reconstruction failed"
+        );
+    }
+
+    #[test]
+    fn test_dkg_simple_tdec_key_rotation() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (shares_num, security_threshold) = (4, 3);
+        let (
+            messages,
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            old_shared_secret,
+        ) = make_share_update_test_inputs(
+            shares_num,
+            shares_num,
+            rng,
+            security_threshold,
+        );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+
+        // The first validator rotates its key, and anyone can apply it
+        let share_index = validators[0].share_index;
+        let new_keypair = ValidatorKeypair::new(rng);
+        let key_rotation = aggregate
+            .create_key_rotation(
+                share_index,
+                &validator_keypairs[0],
+                &new_keypair,
+                rng,
+            )
+            .unwrap();
+        assert!(aggregate.verify_key_rotation(&key_rotation).unwrap());
+        let new_aggregate =
+            aggregate.apply_key_rotation(&key_rotation).unwrap();
+        assert_eq!(new_aggregate.public_key(), aggregate.public_key());
+
+        // Only the holder of the share can rotate it, and only once
+        assert!(matches!(
+            aggregate.create_key_rotation(
+                share_index,
+                &validator_keypairs[1],
+                &new_keypair,
+                rng,
+            ),
+            Err(Error::ValidatorPublicKeyMismatch)
+        ));
+        assert!(matches!(
+            new_aggregate.apply_key_rotation(&key_rotation),
+            Err(Error::InvalidKeyRotation(..))
+        ));
+
+        // The validator decrypts with its new key, in the same slot
+        let mut new_validators = validators.clone();
+        new_validators[0].public_key = new_keypair.public_key();
+        let mut new_validator_keypairs = validator_keypairs.clone();
+        new_validator_keypairs[0] = new_keypair;
+        let mut decryption_shares =
+            izip!(&new_validators, &new_validator_keypairs)
+                .take(security_threshold as usize)
+                .map(|(validator, validator_keypair)| {
+                    let dkg = Dkg::new(
+                        TAU,
+                        shares_num,
+                        security_threshold,
+                        &new_validators,
+                        validator,
+                    )
+                    .unwrap();
+                    new_aggregate
+                        .create_decryption_share_simple(
+                            &dkg,
+                            &ciphertext_header,
+                            AAD,
+                            validator_keypair,
+                        )
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+        decryption_shares.shuffle(rng);
+
+        let new_shared_secret =
+            combine_shares_simple(&decryption_shares).unwrap();
+        assert_eq!(
+            old_shared_secret, new_shared_secret,
+            "Shared secret reconstruction failed"
+        );
+    }
+
+    #[test_case(4, 3; "N is a power of 2, t is 1 + 50%")]
+    #[test_case(7, 5; "N is not a power of 2, t is 1 + 50%")]
+    fn test_dkg_simple_tdec_batch_handover(
+        shares_num: u32,
+        security_threshold: u32,
+    ) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let (
+            messages,
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            old_shared_secret,
+        ) = make_share_update_test_inputs(
+            shares_num,
+            shares_num,
+            rng,
+            security_threshold,
+        );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+
+        // The first validators migrate to new keys in a single batch
+        let handover_indices = (0..security_threshold - 1).collect::<Vec<_>>();
+        let incoming_keypairs = handover_indices
+            .iter()
+            .map(|_| ValidatorKeypair::new(rng))
+            .collect::<Vec<_>>();
+        let handovers = izip!(&handover_indices, &incoming_keypairs)
+            .map(|(share_index, keypair)| (*share_index, keypair))
+            .collect::<Vec<_>>();
+        let handover_transcripts = dkgs[0]
+            .generate_handover_transcripts(&aggregate, &handovers, rng)
+            .unwrap();
+
+        // Slots and incoming validators must be distinct and new to the cohort
+        let duplicated_slot =
+            [handovers[0], (handovers[0].0, &validator_keypairs[0])];
+        assert!(matches!(
+            dkgs[0].generate_handover_transcripts(
+                &aggregate,
+                &duplicated_slot,
+                rng
+            ),
+            Err(Error::DuplicatedShareIndex(..))
+        ));
+        let duplicated_key = [handovers[0], (handovers[1].0, handovers[0].1)];
+        assert!(matches!(
+            dkgs[0].generate_handover_transcripts(
+                &aggregate,
+                &duplicated_key,
+                rng
+            ),
+            Err(Error::DuplicatedValidatorPublicKey(..))
+        ));
+        let cohort_key = [(handovers[0].0, &validator_keypairs[1])];
+        assert!(matches!(
+            dkgs[0].generate_handover_transcripts(&aggregate, &cohort_key, rng),
+            Err(Error::DuplicatedValidatorPublicKey(..))
+        ));
+
+        // The departing validators finalize the batch at once, or publish
+        // results that anyone applies in a single pass
+        let finalized_handovers =
+            izip!(&handover_transcripts, &handover_indices)
+                .map(|(transcript, share_index)| {
+                    (transcript, &validator_keypairs[*share_index as usize])
+                })
+                .collect::<Vec<_>>();
+        let new_aggregate = aggregate
+            .finalize_handovers(&dkgs[0], &finalized_handovers)
+            .unwrap();
+        let handover_results = finalized_handovers
+            .iter()
+            .map(|(transcript, keypair)| {
+                aggregate
+                    .create_handover_result(transcript, keypair, rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            aggregate
+                .apply_handover_results(&dkgs[1], &handover_results)
+                .unwrap(),
+            new_aggregate
+        );
+        let duplicated_results =
+            [handover_results[0].clone(), handover_results[0].clone()];
+        assert!(matches!(
+            aggregate.apply_handover_results(&dkgs[1], &duplicated_results),
+            Err(Error::DuplicatedShareIndex(..))
+        ));
+
+        // The incoming validators decrypt together with the remaining ones
+        let mut new_validators = validators.clone();
+        let mut new_validator_keypairs = validator_keypairs.clone();
+        for (share_index, keypair) in &handovers {
+            new_validators[*share_index as usize].public_key =
+                keypair.public_key();
+            new_validator_keypairs[*share_index as usize] = **keypair;
+        }
+        let mut decryption_shares =
+            izip!(&new_validators, &new_validator_keypairs)
+                .take(security_threshold as usize)
+                .map(|(validator, validator_keypair)| {
+                    let dkg = Dkg::new(
+                        TAU,
+                        shares_num,
+                        security_threshold,
+                        &new_validators,
+                        validator,
+                    )
+                    .unwrap();
+                    new_aggregate
+                        .create_decryption_share_simple(
+                            &dkg,
+                            &ciphertext_header,
+                            AAD,
+                            validator_keypair,
+                        )
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+        decryption_shares.shuffle(rng);
+
+        let new_shared_secret =
+            combine_shares_simple(&decryption_shares).unwrap();
+        assert_eq!(
+            old_shared_secret, new_shared_secret,
+            "Shared secret reconstruction failed"
+        );
+    }
+
+    #[test_case(4, 3, 7, 5; "to a larger committee")]
+    #[test_case(7, 5, 4, 2; "to a smaller committee")]
+    fn test_dkg_simple_tdec_resharing(
         shares_num: u32,
-        validators_num: u32,
-        _recover_at_random_point: bool,
+        security_threshold: u32,
+        new_shares_num: u32,
+        new_security_threshold: u32,
     ) {
         let rng = &mut StdRng::seed_from_u64(0);
-        let security_threshold = shares_num / 2 + 1;
         let (
-            mut messages,
-            mut validators,
-            mut validator_keypairs,
-            mut dkgs,
+            messages,
+            _validators,
+            validator_keypairs,
+            dkgs,
             ciphertext_header,
             old_shared_secret,
         ) = make_share_update_test_inputs(
             shares_num,
-            validators_num,
+            shares_num,
             rng,
             security_threshold,
         );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
 
-        // We assume that all participants have the same aggregate, and that participants created
-        // their own aggregates before the off-boarding of the validator
-        // If we didn't create this aggregate here, we risk having a "dangling validator message"
-        // later when we off-board the validator
-        let aggregated_transcript = dkgs[0]
-            .clone()
-            .aggregate_transcripts(messages.as_slice())
-            .unwrap();
-        assert!(aggregated_transcript
-            .verify(validators_num, messages.as_slice())
-            .unwrap());
+        // The new committee has its own validators, size and threshold
+        let new_validator_keypairs = (0..new_shares_num)
+            .map(|_| ValidatorKeypair::new(rng))
+            .collect::<Vec<_>>();
+        let new_validators = new_validator_keypairs
+            .iter()
+            .enumerate()
+            .map(|(i, keypair)| Validator {
+                address: gen_address(shares_num as usize + i),
+                public_key: keypair.public_key(),
+                share_index: i as u32,
+            })
+            .collect::<Vec<_>>();
+        let new_dkgs = new_validators
+            .iter()
+            .map(|validator| {
+                Dkg::new(
+                    TAU,
+                    new_shares_num,
+                    new_security_threshold,
+                    &new_validators,
+                    validator,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
 
-        // We need to save this domain point to be user in the recovery testing scenario
-        let mut domain_points = dkgs[0].0.domain_point_map();
-        let _removed_domain_point = domain_points
-            .remove(&validators.last().unwrap().share_index)
-            .unwrap();
+        // Each new validator sends a handover transcript to each dealer, who
+        // deals its share to the new committee
+        let dealers = &dkgs[..security_threshold as usize];
+        let resharing_transcripts = dealers
+            .iter()
+            .map(|dkg| {
+                let dealer_index = dkg.me().share_index;
+                let handover_transcripts = new_validator_keypairs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, keypair)| {
+                        let transcript = dkg
+                            .generate_handover_transcript(
+                                &aggregate,
+                                dealer_index,
+                                keypair,
+                                rng,
+                            )
+                            .unwrap();
+                        (i as u32, transcript)
+                    })
+                    .collect::<HashMap<_, _>>();
+                aggregate
+                    .create_resharing_transcript(
+                        dealer_index,
+                        &validator_keypairs[dealer_index as usize],
+                        &handover_transcripts,
+                        &new_dkgs[0],
+                        rng,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
 
-        // Remove one participant from the contexts and all nested structure
-        // to simulate off-boarding a validator
-        messages.pop().unwrap();
-        dkgs.pop();
-        validator_keypairs.pop().unwrap();
-        let _removed_validator = validators.pop().unwrap();
-
-        // Now, we're going to recover a new share at a random point or at a specific point
-        // and check that the shared secret is still the same.
-        // let _x_r = if recover_at_random_point {
-        //     // Onboarding a validator with a completely new private key share
-        //     DomainPoint<E>::rand(rng)
-        // } else {
-        //     // Onboarding a validator with a private key share recovered from the removed validator
-        //     removed_domain_point
-        // };
-
-        // Each participant prepares an update for each other participant
-        // let share_updates = dkgs
-        //     .iter()
-        //     .map(|validator_dkg| {
-        //         let share_update =
-        //             ShareRecoveryUpdate::create_recovery_updates(
-        //                 validator_dkg,
-        //                 &x_r,
-        //             )
-        //             .unwrap();
-        //         (validator_dkg.me().address.clone(), share_update)
-        //     })
-        //     .collect::<HashMap<_, _>>();
-
-        // Participants share updates and update their shares
-
-        // Now, every participant separately:
-        // let updated_shares: HashMap<u32, _> = dkgs
-        //     .iter()
-        //     .map(|validator_dkg| {
-        //         // Current participant receives updates from other participants
-        //         let updates_for_participant: Vec<_> = share_updates
-        //             .values()
-        //             .map(|updates| {
-        //                 updates.get(&validator_dkg.me().share_index).unwrap()
-        //             })
-        //             .cloned()
-        //             .collect();
-
-        //         // Each validator uses their decryption key to update their share
-        //         let validator_keypair = validator_keypairs
-        //             .get(validator_dkg.me().share_index as usize)
-        //             .unwrap();
-
-        //         // And creates updated private key shares
-        //         let updated_key_share = aggregated_transcript
-        //             .get_private_key_share(
-        //                 validator_keypair,
-        //                 validator_dkg.me().share_index,
-        //             )
-        //             .unwrap()
-        //             .create_updated_private_key_share_for_recovery(
-        //                 &updates_for_participant,
-        //             )
-        //             .unwrap();
-        //         (validator_dkg.me().share_index, updated_key_share)
-        //     })
-        //     .collect();
-
-        // Now, we have to combine new share fragments into a new share
-        // let recovered_key_share =
-        // PrivateKeyShare::recover_share_from_updated_private_shares(
-        //     &x_r,
-        //     &domain_points,
-        //     &updated_shares,
-        // )
-        // .unwrap();
-
-        // Get decryption shares from remaining participants
-        let mut decryption_shares: Vec<DecryptionShareSimple> =
-            validator_keypairs
-                .iter()
-                .zip_eq(dkgs.iter())
-                .map(|(validator_keypair, validator_dkg)| {
-                    aggregated_transcript
-                        .create_decryption_share_simple(
-                            validator_dkg,
-                            &ciphertext_header,
-                            AAD,
-                            validator_keypair,
-                        )
-                        .unwrap()
-                })
-                .collect();
-        decryption_shares.shuffle(rng);
+        // Anyone can aggregate the resharing transcripts of enough dealers
+        let reshared = aggregate
+            .reshare(&new_dkgs[0], &resharing_transcripts)
+            .unwrap();
+        assert_eq!(reshared.public_key(), aggregate.public_key());
+        assert!(matches!(
+            aggregate.reshare(&new_dkgs[0], &resharing_transcripts[1..]),
+            Err(Error::InsufficientResharingDealers(..))
+        ));
 
-        // In order to test the recovery, we need to create a new decryption share from the recovered
-        // private key share. To do that, we need a new validator
-
-        // Let's create and onboard a new validator
-        // TODO: Add test scenarios for onboarding and offboarding validators
-        // let new_validator_keypair = Keypair::random();
-        // Normally, we would get these from the Coordinator:
-        // let new_validator_share_index = removed_validator.share_index;
-        // let new_validator = Validator {
-        //     address: gen_address(new_validator_share_index as usize),
-        //     public_key: new_validator_keypair.public_key(),
-        //     share_index: new_validator_share_index,
-        // };
-        // validators.push(new_validator.clone());
-        // let new_validator_dkg = Dkg::new(
-        //     TAU,
-        //     shares_num,
-        //     security_threshold,
-        //     &validators,
-        //     &new_validator,
-        // )
-        // .unwrap();
-
-        // let new_decryption_share = recovered_key_share
-        //     .create_decryption_share_simple(
-        //         &new_validator_dkg,
-        //         &ciphertext_header,
-        //         &new_validator_keypair,
-        //         AAD,
-        //     )
-        //     .unwrap();
-        // decryption_shares.push(new_decryption_share);
-        // domain_points.insert(new_validator_share_index, x_r);
-
-        let domain_points = domain_points
-            .values()
-            .take(security_threshold as usize)
-            .cloned()
+        // The new committee decrypts ciphertexts made before the resharing
+        let mut decryption_shares = izip!(&new_dkgs, &new_validator_keypairs)
+            .take(new_security_threshold as usize)
+            .map(|(dkg, validator_keypair)| {
+                reshared
+                    .create_decryption_share_simple(
+                        dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
             .collect::<Vec<_>>();
-        let decryption_shares =
-            &decryption_shares[..security_threshold as usize];
-        assert_eq!(domain_points.len(), security_threshold as usize);
-        assert_eq!(decryption_shares.len(), security_threshold as usize);
+        decryption_shares.shuffle(rng);
 
-        let new_shared_secret = combine_shares_simple(decryption_shares);
-        assert_ne!(
+        let new_shared_secret =
+            combine_shares_simple(&decryption_shares).unwrap();
+        assert_eq!(
             old_shared_secret, new_shared_secret,
             "Shared secret reconstruction failed"
         );
@@ -1211,7 +3761,10 @@ mod test_ferveo_api {
             })
             .collect();
 
-        // TODO: test that refreshed aggregates are all the same
+        // All participants obtain the same refreshed aggregate
+        assert!(refreshed_aggregates
+            .iter()
+            .all(|aggregate| aggregate == &refreshed_aggregates[0]));
 
         // Participants create decryption shares
         let mut decryption_shares: Vec<DecryptionShareSimple> =
@@ -1243,10 +3796,443 @@ mod test_ferveo_api {
             &decryption_shares[..security_threshold as usize];
         assert_eq!(decryption_shares.len(), security_threshold as usize);
 
-        let new_shared_secret = combine_shares_simple(decryption_shares);
+        let new_shared_secret =
+            combine_shares_simple(decryption_shares).unwrap();
         assert_eq!(
             old_shared_secret, new_shared_secret,
             "Shared secret reconstruction failed"
         );
     }
+
+    /// An aggregate before and after a refresh round, with what's needed to
+    /// make decryption shares with either of them
+    struct RefreshedAggregateTestInputs {
+        messages: Vec<ValidatorMessage>,
+        validators: Vec<Validator>,
+        validator_keypairs: Vec<ValidatorKeypair>,
+        dkgs: Vec<Dkg>,
+        ciphertext_header: CiphertextHeader,
+        shared_secret: SharedSecret,
+        aggregate: AggregatedTranscript,
+        refreshed_aggregate: AggregatedTranscript,
+    }
+
+    const REFRESHED_SECURITY_THRESHOLD: u32 = 3;
+
+    fn make_refreshed_aggregate_test_inputs(
+        rng: &mut StdRng,
+    ) -> RefreshedAggregateTestInputs {
+        let shares_num = 4;
+        let (
+            messages,
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            shared_secret,
+        ) = make_share_update_test_inputs(
+            shares_num,
+            shares_num,
+            rng,
+            REFRESHED_SECURITY_THRESHOLD,
+        );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+        let update_transcripts = dkgs
+            .iter()
+            .map(|dkg| {
+                (
+                    dkg.me().share_index,
+                    dkg.generate_refresh_transcript(rng).unwrap(),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let validator_map = validators
+            .iter()
+            .map(|validator| (validator.share_index, validator.public_key))
+            .collect::<HashMap<_, _>>();
+        let refreshed_aggregate = aggregate
+            .refresh(&update_transcripts, &validator_map)
+            .unwrap();
+        RefreshedAggregateTestInputs {
+            messages,
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            shared_secret,
+            aggregate,
+            refreshed_aggregate,
+        }
+    }
+
+    #[test]
+    fn test_dkg_simple_tdec_rejects_mixed_share_generations() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let RefreshedAggregateTestInputs {
+            messages,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            shared_secret,
+            aggregate,
+            refreshed_aggregate,
+            ..
+        } = make_refreshed_aggregate_test_inputs(rng);
+        assert_eq!(aggregate.generation().number, 0);
+        assert_eq!(
+            aggregate.generation(),
+            dkgs[1]
+                .aggregate_transcripts(&messages)
+                .unwrap()
+                .generation()
+        );
+        let generation = refreshed_aggregate.generation();
+        assert_eq!(generation.number, 1);
+        assert_ne!(generation.lineage, aggregate.generation().lineage);
+
+        let decryption_share = |aggregate: &AggregatedTranscript, i: usize| {
+            aggregate
+                .create_decryption_share_simple(
+                    &dkgs[i],
+                    &ciphertext_header,
+                    AAD,
+                    &validator_keypairs[i],
+                )
+                .unwrap()
+        };
+        let mut decryption_shares = (0..REFRESHED_SECURITY_THRESHOLD as usize)
+            .map(|i| decryption_share(&refreshed_aggregate, i))
+            .collect::<Vec<_>>();
+        assert!(decryption_shares
+            .iter()
+            .all(|share| share.generation() == generation));
+        assert_eq!(
+            combine_shares_simple(&decryption_shares).unwrap(),
+            shared_secret
+        );
+
+        // A share made with the stale aggregate can't be mixed in
+        decryption_shares[0] = decryption_share(&aggregate, 0);
+        assert!(matches!(
+            combine_shares_simple(&decryption_shares),
+            Err(Error::ShareGenerationMismatch(0, 1))
+        ));
+    }
+
+    #[test]
+    fn test_dkg_precomputed_tdec_rejects_mixed_share_generations() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let RefreshedAggregateTestInputs {
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            shared_secret,
+            aggregate,
+            refreshed_aggregate,
+            ..
+        } = make_refreshed_aggregate_test_inputs(rng);
+        let selected_validators =
+            &validators[..REFRESHED_SECURITY_THRESHOLD as usize];
+
+        let decryption_share = |aggregate: &AggregatedTranscript, i: usize| {
+            aggregate
+                .create_decryption_share_precomputed(
+                    &dkgs[i],
+                    &ciphertext_header,
+                    AAD,
+                    &validator_keypairs[i],
+                    selected_validators,
+                )
+                .unwrap()
+        };
+        let mut decryption_shares = (0..selected_validators.len())
+            .map(|i| decryption_share(&refreshed_aggregate, i))
+            .collect::<Vec<_>>();
+        assert!(decryption_shares.iter().all(
+            |share| share.generation() == refreshed_aggregate.generation()
+        ));
+        assert_eq!(
+            combine_shares_precomputed(&decryption_shares).unwrap(),
+            shared_secret
+        );
+
+        decryption_shares[0] = decryption_share(&aggregate, 0);
+        assert!(matches!(
+            combine_shares_precomputed(&decryption_shares),
+            Err(Error::ShareGenerationMismatch(0, 1))
+        ));
+    }
+
+    #[test]
+    fn test_blinded_shares_reject_mixed_share_generations() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let RefreshedAggregateTestInputs {
+            validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            shared_secret,
+            aggregate,
+            refreshed_aggregate,
+            ..
+        } = make_refreshed_aggregate_test_inputs(rng);
+        let selected_validators =
+            &validators[..REFRESHED_SECURITY_THRESHOLD as usize];
+        let requester_keypair = RequesterKeypair::random();
+
+        let simple_share = |aggregate: &AggregatedTranscript, i: usize| {
+            aggregate
+                .create_blinded_decryption_share_simple(
+                    &dkgs[i],
+                    &ciphertext_header,
+                    AAD,
+                    &validator_keypairs[i],
+                    &requester_keypair.public_key(),
+                )
+                .unwrap()
+        };
+        let mut simple_shares = (0..selected_validators.len())
+            .map(|i| simple_share(&refreshed_aggregate, i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            combine_blinded_shares_simple(&simple_shares, &requester_keypair)
+                .unwrap(),
+            shared_secret
+        );
+        simple_shares[0] = simple_share(&aggregate, 0);
+        assert!(matches!(
+            combine_blinded_shares_simple(&simple_shares, &requester_keypair),
+            Err(Error::ShareGenerationMismatch(0, 1))
+        ));
+
+        let precomputed_share = |aggregate: &AggregatedTranscript, i: usize| {
+            aggregate
+                .create_blinded_decryption_share_precomputed(
+                    &dkgs[i],
+                    &ciphertext_header,
+                    AAD,
+                    &validator_keypairs[i],
+                    selected_validators,
+                    &requester_keypair.public_key(),
+                )
+                .unwrap()
+        };
+        let mut precomputed_shares = (0..selected_validators.len())
+            .map(|i| precomputed_share(&refreshed_aggregate, i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            combine_blinded_shares_precomputed(
+                &precomputed_shares,
+                &requester_keypair
+            )
+            .unwrap(),
+            shared_secret
+        );
+        precomputed_shares[0] = precomputed_share(&aggregate, 0);
+        assert!(matches!(
+            combine_blinded_shares_precomputed(
+                &precomputed_shares,
+                &requester_keypair
+            ),
+            Err(Error::ShareGenerationMismatch(0, 1))
+        ));
+    }
+
+    #[test]
+    fn test_elgamal_shares_reject_mixed_share_generations() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let RefreshedAggregateTestInputs {
+            validator_keypairs,
+            dkgs,
+            aggregate,
+            refreshed_aggregate,
+            ..
+        } = make_refreshed_aggregate_test_inputs(rng);
+        let ballot =
+            ElGamalBallot::encrypt(3, &aggregate.public_key(), TALLY_CONTEXT)
+                .unwrap();
+        let tally =
+            VerifiedElGamalTally::new(&[ballot], TALLY_CONTEXT).unwrap();
+        let ciphertext = tally.ciphertext();
+        let table = DiscreteLogTable::new(3);
+
+        let decryption_share = |aggregate: &AggregatedTranscript, i: usize| {
+            aggregate
+                .create_elgamal_decryption_share(
+                    &dkgs[i],
+                    &tally,
+                    &validator_keypairs[i],
+                )
+                .unwrap()
+        };
+        let mut decryption_shares = (0..REFRESHED_SECURITY_THRESHOLD as usize)
+            .map(|i| decryption_share(&refreshed_aggregate, i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            combine_elgamal_shares(&ciphertext, &decryption_shares, &table)
+                .unwrap(),
+            3
+        );
+
+        decryption_shares[0] = decryption_share(&aggregate, 0);
+        assert!(matches!(
+            combine_elgamal_shares(&ciphertext, &decryption_shares, &table),
+            Err(Error::ShareGenerationMismatch(0, 1))
+        ));
+    }
+
+    #[test]
+    fn test_reencryption_shares_reject_mixed_share_generations() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let RefreshedAggregateTestInputs {
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            aggregate,
+            refreshed_aggregate,
+            ..
+        } = make_refreshed_aggregate_test_inputs(rng);
+        // Re-encrypting to the same ritual is enough to combine the shares
+        let target_public_key = aggregate.public_key();
+
+        let reencryption_share = |aggregate: &AggregatedTranscript,
+                                  i: usize| {
+            aggregate
+                .create_reencryption_share(
+                    &dkgs[i],
+                    &ciphertext_header,
+                    AAD,
+                    &validator_keypairs[i],
+                    &target_public_key,
+                )
+                .unwrap()
+        };
+        let mut reencryption_shares = (0..REFRESHED_SECURITY_THRESHOLD
+            as usize)
+            .map(|i| reencryption_share(&refreshed_aggregate, i))
+            .collect::<Vec<_>>();
+        let reencrypted_header = combine_reencryption_shares(
+            &ciphertext_header,
+            &target_public_key,
+            &reencryption_shares,
+        )
+        .unwrap();
+        assert!(reencrypted_header.check(AAD).unwrap());
+
+        reencryption_shares[0] = reencryption_share(&aggregate, 0);
+        assert!(matches!(
+            combine_reencryption_shares(
+                &ciphertext_header,
+                &target_public_key,
+                &reencryption_shares,
+            ),
+            Err(Error::ShareGenerationMismatch(0, 1))
+        ));
+    }
+
+    #[test_case(4, 3; "N is a power of 2")]
+    #[test_case(7, 5; "N is not a power of 2")]
+    fn test_dkg_simple_tdec_share_refreshing_subset(
+        shares_num: u32,
+        security_threshold: u32,
+    ) {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let validators_num: u32 = shares_num;
+        let (
+            messages,
+            _validators,
+            validator_keypairs,
+            dkgs,
+            ciphertext_header,
+            old_shared_secret,
+        ) = make_share_update_test_inputs(
+            shares_num,
+            validators_num,
+            rng,
+            security_threshold,
+        );
+        let aggregate = dkgs[0].aggregate_transcripts(&messages).unwrap();
+
+        // Only the first `security_threshold` validators are online
+        let online = &dkgs[..security_threshold as usize];
+        let online_indices = online
+            .iter()
+            .map(|dkg| dkg.me().share_index)
+            .collect::<Vec<_>>();
+        let update_transcripts = online
+            .iter()
+            .map(|dkg| {
+                let transcript = dkg
+                    .generate_refresh_transcript_for(&online_indices, rng)
+                    .unwrap();
+                (dkg.me().share_index, transcript)
+            })
+            .collect::<HashMap<_, _>>();
+        let validator_map = online_indices
+            .iter()
+            .map(|share_index| {
+                let keypair = &validator_keypairs[*share_index as usize];
+                (*share_index, keypair.public_key())
+            })
+            .collect::<HashMap<_, _>>();
+        let refreshed = aggregate
+            .refresh(&update_transcripts, &validator_map)
+            .unwrap();
+        assert_eq!(refreshed.public_key(), aggregate.public_key());
+
+        // Refreshed validators can still decrypt together
+        let decryption_shares = izip!(online, &validator_keypairs)
+            .map(|(dkg, validator_keypair)| {
+                assert!(refreshed
+                    .is_share_current(dkg.me().share_index)
+                    .unwrap());
+                refreshed
+                    .create_decryption_share_simple(
+                        dkg,
+                        &ciphertext_header,
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            combine_shares_simple(&decryption_shares).unwrap(),
+            old_shared_secret
+        );
+
+        // But the offline validators' shares are stale
+        let offline = &dkgs[security_threshold as usize..];
+        for (dkg, validator_keypair) in
+            izip!(offline, &validator_keypairs[security_threshold as usize..])
+        {
+            let share_index = dkg.me().share_index;
+            assert!(!refreshed.is_share_current(share_index).unwrap());
+            assert!(matches!(
+                refreshed.create_decryption_share_simple(
+                    dkg,
+                    &ciphertext_header,
+                    AAD,
+                    validator_keypair,
+                ),
+                Err(Error::StaleShare(index)) if index == share_index
+            ));
+        }
+
+        // Refreshed validators must be able to reach the threshold on their own
+        assert!(matches!(
+            dkgs[0].generate_refresh_transcript_for(
+                &online_indices[..security_threshold as usize - 1],
+                rng
+            ),
+            Err(Error::InsufficientRefreshTargets(..))
+        ));
+        let mut too_few = validator_map.clone();
+        too_few.remove(&online_indices[0]);
+        assert!(matches!(
+            aggregate.refresh(&update_transcripts, &too_few),
+            Err(Error::InsufficientRefreshTargets(..))
+        ));
+    }
 }
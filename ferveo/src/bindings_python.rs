@@ -1,6 +1,7 @@
 #![allow(unexpected_cfgs)]
 
 use std::{
+    collections::HashMap,
     fmt,
     fmt::{Debug, Formatter},
 };
@@ -115,6 +116,62 @@ impl From<FerveoPythonError> for PyErr {
                 Error::DuplicateTranscript(validator) => {
                     DuplicateTranscript::new_err(validator.to_string())
                 }
+                Error::InsufficientRefreshTargets(expected, actual) => {
+                    InsufficientRefreshTargets::new_err(format!(
+                        "expected: {expected}, actual: {actual}"
+                    ))
+                }
+                Error::StaleShare(index) => {
+                    StaleShare::new_err(format!("{index}"))
+                }
+                Error::InsufficientRecoveryHelpers(expected, actual) => {
+                    InsufficientRecoveryHelpers::new_err(format!(
+                        "expected: {expected}, actual: {actual}"
+                    ))
+                }
+                Error::InvalidRecoveryShare(index) => {
+                    InvalidRecoveryShare::new_err(format!("{index}"))
+                }
+                Error::InsufficientResharingDealers(expected, actual) => {
+                    InsufficientResharingDealers::new_err(format!(
+                        "expected: {expected}, actual: {actual}"
+                    ))
+                }
+                Error::InvalidResharingTranscript(index) => {
+                    InvalidResharingTranscript::new_err(format!("{index}"))
+                }
+                Error::InvalidHandoverResult(index) => {
+                    InvalidHandoverResult::new_err(format!("{index}"))
+                }
+                Error::DuplicatedValidatorPublicKey(index) => {
+                    DuplicatedValidatorPublicKey::new_err(format!("{index}"))
+                }
+                Error::InvalidKeyRotation(index) => {
+                    InvalidKeyRotation::new_err(format!("{index}"))
+                }
+                Error::ShareGenerationMismatch(expected, actual) => {
+                    ShareGenerationMismatch::new_err(format!(
+                        "expected: {expected}, actual: {actual}"
+                    ))
+                }
+                Error::ShareGenerationOverflow => {
+                    ShareGenerationOverflow::new_err("")
+                }
+                Error::EmptyTranscript => EmptyTranscript::new_err(""),
+                Error::InvalidTranscriptSize(expected, actual) => {
+                    InvalidTranscriptSize::new_err(format!(
+                        "expected: {expected}, actual: {actual}"
+                    ))
+                }
+                Error::IdentityPointInTranscript => {
+                    IdentityPointInTranscript::new_err("")
+                }
+                Error::UnsupportedTranscriptFormat(version) => {
+                    UnsupportedTranscriptFormat::new_err(version.to_string())
+                }
+                Error::InvalidEvaluationDomainSize(size) => {
+                    InvalidEvaluationDomainSize::new_err(size.to_string())
+                }
                 // Remember to create Python exceptions using `create_exception!` macro, and to register them in the
                 // `make_ferveo_py_module` function. You will have to update the `ferveo/__init__.{py, pyi}` files too.
             },
@@ -168,6 +225,22 @@ create_exception!(
 create_exception!(exceptions, UnknownValidator, PyValueError);
 create_exception!(exceptions, TooManyTranscripts, PyValueError);
 create_exception!(exceptions, DuplicateTranscript, PyValueError);
+create_exception!(exceptions, InsufficientRefreshTargets, PyValueError);
+create_exception!(exceptions, StaleShare, PyValueError);
+create_exception!(exceptions, InsufficientRecoveryHelpers, PyValueError);
+create_exception!(exceptions, InvalidRecoveryShare, PyValueError);
+create_exception!(exceptions, InsufficientResharingDealers, PyValueError);
+create_exception!(exceptions, InvalidResharingTranscript, PyValueError);
+create_exception!(exceptions, InvalidHandoverResult, PyValueError);
+create_exception!(exceptions, DuplicatedValidatorPublicKey, PyValueError);
+create_exception!(exceptions, InvalidKeyRotation, PyValueError);
+create_exception!(exceptions, ShareGenerationMismatch, PyValueError);
+create_exception!(exceptions, ShareGenerationOverflow, PyValueError);
+create_exception!(exceptions, EmptyTranscript, PyValueError);
+create_exception!(exceptions, InvalidTranscriptSize, PyValueError);
+create_exception!(exceptions, IdentityPointInTranscript, PyValueError);
+create_exception!(exceptions, UnsupportedTranscriptFormat, PyValueError);
+create_exception!(exceptions, InvalidEvaluationDomainSize, PyValueError);
 
 fn from_py_bytes<T: FromBytes>(bytes: &[u8]) -> PyResult<T> {
     T::from_bytes(bytes)
@@ -272,25 +345,57 @@ pub fn encrypt(
 #[pyfunction]
 pub fn combine_decryption_shares_simple(
     decryption_shares: Vec<DecryptionShareSimple>,
-) -> SharedSecret {
+) -> PyResult<SharedSecret> {
     let shares = decryption_shares
         .iter()
         .map(|share| share.0.clone())
         .collect::<Vec<_>>();
-    let shared_secret = api::combine_shares_simple(&shares[..]);
-    SharedSecret(shared_secret)
+    let shared_secret = api::combine_shares_simple(&shares[..])
+        .map_err(FerveoPythonError::FerveoError)?;
+    Ok(SharedSecret(shared_secret))
 }
 
 #[pyfunction]
 pub fn combine_decryption_shares_precomputed(
     decryption_shares: Vec<DecryptionSharePrecomputed>,
-) -> SharedSecret {
+) -> PyResult<SharedSecret> {
+    let shares = decryption_shares
+        .iter()
+        .map(|share| share.0.clone())
+        .collect::<Vec<_>>();
+    let shared_secret = api::combine_shares_precomputed(&shares[..])
+        .map_err(FerveoPythonError::FerveoError)?;
+    Ok(SharedSecret(shared_secret))
+}
+
+#[pyfunction]
+pub fn combine_blinded_decryption_shares_simple(
+    decryption_shares: Vec<BlindedDecryptionShareSimple>,
+    requester_keypair: &RequesterKeypair,
+) -> PyResult<SharedSecret> {
     let shares = decryption_shares
         .iter()
         .map(|share| share.0.clone())
         .collect::<Vec<_>>();
-    let shared_secret = api::share_combine_precomputed(&shares[..]);
-    SharedSecret(api::SharedSecret(shared_secret))
+    let shared_secret =
+        api::combine_blinded_shares_simple(&shares, &requester_keypair.0)
+            .map_err(FerveoPythonError::FerveoError)?;
+    Ok(SharedSecret(shared_secret))
+}
+
+#[pyfunction]
+pub fn combine_blinded_decryption_shares_precomputed(
+    decryption_shares: Vec<BlindedDecryptionSharePrecomputed>,
+    requester_keypair: &RequesterKeypair,
+) -> PyResult<SharedSecret> {
+    let shares = decryption_shares
+        .iter()
+        .map(|share| share.0.clone())
+        .collect::<Vec<_>>();
+    let shared_secret =
+        api::combine_blinded_shares_precomputed(&shares, &requester_keypair.0)
+            .map_err(FerveoPythonError::FerveoError)?;
+    Ok(SharedSecret(shared_secret))
 }
 
 #[pyfunction]
@@ -549,6 +654,88 @@ impl Dkg {
             .map_err(FerveoPythonError::FerveoError)?;
         Ok(HandoverTranscript(handover_transcript))
     }
+
+    pub fn generate_refresh_transcript(&self) -> PyResult<RefreshTranscript> {
+        let rng = &mut thread_rng();
+        let refresh_transcript = self
+            .0
+            .generate_refresh_transcript(rng)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(RefreshTranscript(refresh_transcript))
+    }
+
+    pub fn generate_refresh_transcript_for(
+        &self,
+        share_indices: Vec<u32>,
+    ) -> PyResult<RefreshTranscript> {
+        let rng = &mut thread_rng();
+        let refresh_transcript = self
+            .0
+            .generate_refresh_transcript_for(&share_indices, rng)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(RefreshTranscript(refresh_transcript))
+    }
+
+    pub fn generate_recovery_transcript(
+        &self,
+        helper_indices: Vec<u32>,
+        recovery_index: u32,
+    ) -> PyResult<RecoveryTranscript> {
+        let rng = &mut thread_rng();
+        let recovery_transcript = self
+            .0
+            .generate_recovery_transcript(&helper_indices, recovery_index, rng)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(RecoveryTranscript(recovery_transcript))
+    }
+
+    pub fn generate_recovery_handover_transcript(
+        &self,
+        aggregate: &AggregatedTranscript,
+        recovery_transcripts: HashMap<u32, RecoveryTranscript>,
+        helper_index: u32,
+        recovering_validator_keypair: &Keypair,
+    ) -> PyResult<HandoverTranscript> {
+        let rng = &mut thread_rng();
+        let handover_transcript = self
+            .0
+            .generate_recovery_handover_transcript(
+                &aggregate.0,
+                &unwrap_recovery_transcripts(recovery_transcripts),
+                helper_index,
+                &recovering_validator_keypair.0,
+                rng,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(HandoverTranscript(handover_transcript))
+    }
+}
+
+fn unwrap_refresh_transcripts(
+    transcripts: HashMap<u32, RefreshTranscript>,
+) -> HashMap<u32, api::RefreshTranscript> {
+    transcripts
+        .into_iter()
+        .map(|(share_index, transcript)| (share_index, transcript.0))
+        .collect()
+}
+
+fn unwrap_recovery_transcripts(
+    transcripts: HashMap<u32, RecoveryTranscript>,
+) -> HashMap<u32, api::RecoveryTranscript> {
+    transcripts
+        .into_iter()
+        .map(|(share_index, transcript)| (share_index, transcript.0))
+        .collect()
+}
+
+fn unwrap_public_keys(
+    public_keys: HashMap<u32, FerveoPublicKey>,
+) -> HashMap<u32, api::ValidatorPublicKey> {
+    public_keys
+        .into_iter()
+        .map(|(share_index, public_key)| (share_index, public_key.0))
+        .collect()
 }
 
 #[pyclass(module = "ferveo")]
@@ -577,6 +764,11 @@ impl Ciphertext {
     pub fn payload(&self) -> Vec<u8> {
         self.0.payload().to_vec()
     }
+
+    pub fn verify(&self, aad: &[u8]) -> PyResult<VerifiedCiphertextHeader> {
+        let header = self.0.verify(aad).map_err(FerveoPythonError::from)?;
+        Ok(VerifiedCiphertextHeader(header))
+    }
 }
 
 generate_bytes_serialization!(Ciphertext);
@@ -595,8 +787,30 @@ generate_bytes_serialization!(Ciphertext);
 )]
 pub struct CiphertextHeader(api::CiphertextHeader);
 
+#[pymethods]
+impl CiphertextHeader {
+    pub fn verify(&self, aad: &[u8]) -> PyResult<VerifiedCiphertextHeader> {
+        let header = self.0.verify(aad).map_err(FerveoPythonError::from)?;
+        Ok(VerifiedCiphertextHeader(header))
+    }
+}
+
 generate_bytes_serialization!(CiphertextHeader);
 
+/// A ciphertext header that was checked once for an aad. It is not
+/// serializable, so it can't be made without checking the header.
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::AsRef, derive_more::From)]
+pub struct VerifiedCiphertextHeader(api::VerifiedCiphertextHeader);
+
+#[pymethods]
+impl VerifiedCiphertextHeader {
+    #[getter]
+    pub fn header(&self) -> CiphertextHeader {
+        CiphertextHeader(self.0.header())
+    }
+}
+
 #[pyclass(module = "ferveo")]
 #[derive(Clone, derive_more::AsRef, derive_more::From)]
 pub struct DecryptionShareSimple(api::DecryptionShareSimple);
@@ -609,18 +823,90 @@ pub struct DecryptionSharePrecomputed(api::DecryptionSharePrecomputed);
 
 generate_bytes_serialization!(DecryptionSharePrecomputed);
 
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::AsRef, derive_more::From)]
+pub struct DecryptionShareProof(api::DecryptionShareProof);
+
+generate_bytes_serialization!(DecryptionShareProof);
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::AsRef, derive_more::From)]
+pub struct BlindedDecryptionShareSimple(api::BlindedDecryptionShareSimple);
+
+generate_bytes_serialization!(BlindedDecryptionShareSimple);
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::AsRef, derive_more::From)]
+pub struct BlindedDecryptionSharePrecomputed(
+    api::BlindedDecryptionSharePrecomputed,
+);
+
+generate_bytes_serialization!(BlindedDecryptionSharePrecomputed);
+
 #[pyclass(module = "ferveo")]
 #[derive(derive_more::From, derive_more::AsRef)]
-pub struct AggregatedTranscript(api::AggregatedTranscript);
+pub struct RequesterKeypair(api::RequesterKeypair);
 
-generate_bytes_serialization!(AggregatedTranscript);
+generate_bytes_serialization!(RequesterKeypair);
+
+#[pymethods]
+impl RequesterKeypair {
+    #[staticmethod]
+    pub fn random() -> Self {
+        Self(api::RequesterKeypair::random())
+    }
+
+    pub fn public_key(&self) -> RequesterPublicKey {
+        RequesterPublicKey(self.0.public_key())
+    }
+}
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::From, derive_more::AsRef)]
+pub struct RequesterPublicKey(api::RequesterPublicKey);
+
+generate_bytes_serialization!(RequesterPublicKey);
 
 #[pyclass(module = "ferveo")]
 #[derive(derive_more::From, derive_more::AsRef)]
+pub struct AggregatedTranscript(api::AggregatedTranscript);
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::From, derive_more::AsRef)]
 pub struct HandoverTranscript(api::HandoverTranscript);
 
 generate_bytes_serialization!(HandoverTranscript);
 
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::From, derive_more::AsRef)]
+pub struct HandoverResult(api::HandoverResult);
+
+generate_bytes_serialization!(HandoverResult);
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::From, derive_more::AsRef)]
+pub struct RefreshTranscript(api::RefreshTranscript);
+
+generate_bytes_serialization!(RefreshTranscript);
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::From, derive_more::AsRef)]
+pub struct RecoveryTranscript(api::RecoveryTranscript);
+
+generate_bytes_serialization!(RecoveryTranscript);
+
+#[pyclass(module = "ferveo")]
+#[derive(Clone, derive_more::From, derive_more::AsRef)]
+pub struct RecoveryShare(api::RecoveryShare);
+
+generate_bytes_serialization!(RecoveryShare);
+
+#[pyclass(module = "ferveo")]
+#[derive(derive_more::From, derive_more::AsRef)]
+pub struct KeyRotation(api::KeyRotation);
+
+generate_bytes_serialization!(KeyRotation);
+
 #[pymethods]
 impl AggregatedTranscript {
     #[new]
@@ -632,6 +918,18 @@ impl AggregatedTranscript {
         Ok(Self(inner))
     }
 
+    #[staticmethod]
+    #[pyo3(signature = (data))]
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        let inner = api::AggregatedTranscript::from_bytes(data)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(Self(inner))
+    }
+
+    fn __bytes__(&self) -> PyResult<PyObject> {
+        to_py_bytes(&self.0)
+    }
+
     pub fn verify(
         &self,
         validators_num: u32,
@@ -688,6 +986,212 @@ impl AggregatedTranscript {
         Ok(DecryptionShareSimple(decryption_share))
     }
 
+    pub fn create_decryption_share_precomputed_verified(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &VerifiedCiphertextHeader,
+        validator_keypair: &Keypair,
+        selected_validators: Vec<Validator>,
+    ) -> PyResult<DecryptionSharePrecomputed> {
+        let selected_validators: Vec<_> =
+            selected_validators.into_iter().map(|v| v.0).collect();
+        let decryption_share = self
+            .0
+            .create_decryption_share_precomputed_verified(
+                &dkg.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+                &selected_validators,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(DecryptionSharePrecomputed(decryption_share))
+    }
+
+    pub fn create_decryption_share_simple_verified(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &VerifiedCiphertextHeader,
+        validator_keypair: &Keypair,
+    ) -> PyResult<DecryptionShareSimple> {
+        let decryption_share = self
+            .0
+            .create_decryption_share_simple_verified(
+                &dkg.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(DecryptionShareSimple(decryption_share))
+    }
+
+    pub fn create_blinded_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &Keypair,
+        selected_validators: Vec<Validator>,
+        requester_public_key: &RequesterPublicKey,
+    ) -> PyResult<BlindedDecryptionSharePrecomputed> {
+        let selected_validators: Vec<_> =
+            selected_validators.into_iter().map(|v| v.0).collect();
+        let decryption_share = self
+            .0
+            .create_blinded_decryption_share_precomputed(
+                &dkg.0,
+                &ciphertext_header.0,
+                aad,
+                &validator_keypair.0,
+                &selected_validators,
+                &requester_public_key.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(BlindedDecryptionSharePrecomputed(decryption_share))
+    }
+
+    pub fn create_blinded_decryption_share_simple(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &Keypair,
+        requester_public_key: &RequesterPublicKey,
+    ) -> PyResult<BlindedDecryptionShareSimple> {
+        let decryption_share = self
+            .0
+            .create_blinded_decryption_share_simple(
+                &dkg.0,
+                &ciphertext_header.0,
+                aad,
+                &validator_keypair.0,
+                &requester_public_key.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(BlindedDecryptionShareSimple(decryption_share))
+    }
+
+    pub fn verify_blinded_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &BlindedDecryptionSharePrecomputed,
+        ciphertext_header: &CiphertextHeader,
+        selected_validators: Vec<Validator>,
+        requester_public_key: &RequesterPublicKey,
+    ) -> PyResult<bool> {
+        let selected_validators: Vec<_> =
+            selected_validators.into_iter().map(|v| v.0).collect();
+        let is_valid = self
+            .0
+            .verify_blinded_decryption_share_precomputed(
+                &dkg.0,
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &selected_validators,
+                &requester_public_key.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(is_valid)
+    }
+
+    pub fn verify_blinded_decryption_share_simple(
+        &self,
+        decryption_share: &BlindedDecryptionShareSimple,
+        ciphertext_header: &CiphertextHeader,
+        requester_public_key: &RequesterPublicKey,
+    ) -> PyResult<bool> {
+        let is_valid = self
+            .0
+            .verify_blinded_decryption_share_simple(
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &requester_public_key.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(is_valid)
+    }
+
+    pub fn create_decryption_share_proof_simple(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &DecryptionShareSimple,
+        ciphertext_header: &CiphertextHeader,
+        validator_keypair: &Keypair,
+    ) -> PyResult<DecryptionShareProof> {
+        let proof = self
+            .0
+            .create_decryption_share_proof_simple(
+                &dkg.0,
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(DecryptionShareProof(proof))
+    }
+
+    pub fn create_decryption_share_proof_precomputed(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &DecryptionSharePrecomputed,
+        ciphertext_header: &CiphertextHeader,
+        validator_keypair: &Keypair,
+        selected_validators: Vec<Validator>,
+    ) -> PyResult<DecryptionShareProof> {
+        let selected_validators: Vec<_> =
+            selected_validators.into_iter().map(|v| v.0).collect();
+        let proof = self
+            .0
+            .create_decryption_share_proof_precomputed(
+                &dkg.0,
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+                &selected_validators,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(DecryptionShareProof(proof))
+    }
+
+    pub fn verify_decryption_share_simple(
+        &self,
+        decryption_share: &DecryptionShareSimple,
+        proof: &DecryptionShareProof,
+        ciphertext_header: &CiphertextHeader,
+    ) -> PyResult<bool> {
+        let is_valid = self
+            .0
+            .verify_decryption_share_simple(
+                &decryption_share.0,
+                &proof.0,
+                &ciphertext_header.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(is_valid)
+    }
+
+    pub fn verify_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &DecryptionSharePrecomputed,
+        proof: &DecryptionShareProof,
+        ciphertext_header: &CiphertextHeader,
+        selected_validators: Vec<Validator>,
+    ) -> PyResult<bool> {
+        let selected_validators: Vec<_> =
+            selected_validators.into_iter().map(|v| v.0).collect();
+        let is_valid = self
+            .0
+            .verify_decryption_share_precomputed(
+                &dkg.0,
+                &decryption_share.0,
+                &proof.0,
+                &ciphertext_header.0,
+                &selected_validators,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(is_valid)
+    }
+
     pub fn finalize_handover(
         &self,
         handover_transcript: &HandoverTranscript,
@@ -700,6 +1204,142 @@ impl AggregatedTranscript {
         Ok(Self(new_aggregate))
     }
 
+    pub fn create_handover_result(
+        &self,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &Keypair,
+    ) -> PyResult<HandoverResult> {
+        let rng = &mut thread_rng();
+        let handover_result = self
+            .0
+            .create_handover_result(
+                &handover_transcript.0,
+                &validator_keypair.0,
+                rng,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(HandoverResult(handover_result))
+    }
+
+    pub fn apply_handover_result(
+        &self,
+        handover_result: &HandoverResult,
+    ) -> PyResult<Self> {
+        let new_aggregate = self
+            .0
+            .apply_handover_result(&handover_result.0)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(Self(new_aggregate))
+    }
+
+    pub fn is_share_current(&self, share_index: u32) -> PyResult<bool> {
+        let is_current = self
+            .0
+            .is_share_current(share_index)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(is_current)
+    }
+
+    pub fn refresh(
+        &self,
+        refresh_transcripts: HashMap<u32, RefreshTranscript>,
+        validator_keys: HashMap<u32, FerveoPublicKey>,
+    ) -> PyResult<Self> {
+        let new_aggregate = self
+            .0
+            .refresh(
+                &unwrap_refresh_transcripts(refresh_transcripts),
+                &unwrap_public_keys(validator_keys),
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(Self(new_aggregate))
+    }
+
+    pub fn create_recovery_share(
+        &self,
+        recovery_transcripts: HashMap<u32, RecoveryTranscript>,
+        helper_keys: HashMap<u32, FerveoPublicKey>,
+        recovery_index: u32,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &Keypair,
+    ) -> PyResult<RecoveryShare> {
+        let recovery_share = self
+            .0
+            .create_recovery_share(
+                &unwrap_recovery_transcripts(recovery_transcripts),
+                &unwrap_public_keys(helper_keys),
+                recovery_index,
+                &handover_transcript.0,
+                &validator_keypair.0,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(RecoveryShare(recovery_share))
+    }
+
+    pub fn recover_share(
+        &self,
+        recovery_transcripts: HashMap<u32, RecoveryTranscript>,
+        helper_keys: HashMap<u32, FerveoPublicKey>,
+        recovery_index: u32,
+        recovery_shares: Vec<RecoveryShare>,
+    ) -> PyResult<Self> {
+        let recovery_shares = recovery_shares
+            .into_iter()
+            .map(|share| share.0)
+            .collect::<Vec<_>>();
+        let new_aggregate = self
+            .0
+            .recover_share(
+                &unwrap_recovery_transcripts(recovery_transcripts),
+                &unwrap_public_keys(helper_keys),
+                recovery_index,
+                &recovery_shares,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(Self(new_aggregate))
+    }
+
+    pub fn create_key_rotation(
+        &self,
+        share_index: u32,
+        validator_keypair: &Keypair,
+        new_validator_keypair: &Keypair,
+    ) -> PyResult<KeyRotation> {
+        let rng = &mut thread_rng();
+        let key_rotation = self
+            .0
+            .create_key_rotation(
+                share_index,
+                &validator_keypair.0,
+                &new_validator_keypair.0,
+                rng,
+            )
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(KeyRotation(key_rotation))
+    }
+
+    pub fn verify_key_rotation(
+        &self,
+        key_rotation: &KeyRotation,
+    ) -> PyResult<bool> {
+        let is_valid = self
+            .0
+            .verify_key_rotation(&key_rotation.0)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(is_valid)
+    }
+
+    pub fn apply_key_rotation(
+        &self,
+        key_rotation: &KeyRotation,
+    ) -> PyResult<Self> {
+        let new_aggregate = self
+            .0
+            .apply_key_rotation(&key_rotation.0)
+            .map_err(FerveoPythonError::FerveoError)?;
+        Ok(Self(new_aggregate))
+    }
+
     #[getter]
     pub fn public_key(&self) -> DkgPublicKey {
         DkgPublicKey(self.0.public_key())
@@ -725,6 +1365,24 @@ pub fn register_combine_decryption_shares_simple(m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(combine_decryption_shares_simple, m)?)
 }
 
+pub fn register_combine_blinded_decryption_shares_simple(
+    m: &PyModule,
+) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(
+        combine_blinded_decryption_shares_simple,
+        m
+    )?)
+}
+
+pub fn register_combine_blinded_decryption_shares_precomputed(
+    m: &PyModule,
+) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(
+        combine_blinded_decryption_shares_precomputed,
+        m
+    )?)
+}
+
 pub fn register_encrypt(m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(encrypt, m)?)
 }
@@ -734,6 +1392,8 @@ pub fn make_ferveo_py_module(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     register_encrypt(m)?;
     register_combine_decryption_shares_simple(m)?;
     register_combine_decryption_shares_precomputed(m)?;
+    register_combine_blinded_decryption_shares_simple(m)?;
+    register_combine_blinded_decryption_shares_precomputed(m)?;
     register_decrypt_with_shared_secret(m)?;
 
     // Classes
@@ -744,14 +1404,25 @@ pub fn make_ferveo_py_module(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<Dkg>()?;
     m.add_class::<Ciphertext>()?;
     m.add_class::<CiphertextHeader>()?;
+    m.add_class::<VerifiedCiphertextHeader>()?;
     m.add_class::<DecryptionShareSimple>()?;
     m.add_class::<DecryptionSharePrecomputed>()?;
+    m.add_class::<DecryptionShareProof>()?;
     m.add_class::<AggregatedTranscript>()?;
     m.add_class::<DkgPublicKey>()?;
     m.add_class::<SharedSecret>()?;
     m.add_class::<ValidatorMessage>()?;
     m.add_class::<FerveoVariant>()?;
     m.add_class::<HandoverTranscript>()?;
+    m.add_class::<KeyRotation>()?;
+    m.add_class::<HandoverResult>()?;
+    m.add_class::<RefreshTranscript>()?;
+    m.add_class::<RecoveryTranscript>()?;
+    m.add_class::<RecoveryShare>()?;
+    m.add_class::<BlindedDecryptionShareSimple>()?;
+    m.add_class::<BlindedDecryptionSharePrecomputed>()?;
+    m.add_class::<RequesterKeypair>()?;
+    m.add_class::<RequesterPublicKey>()?;
 
     // Exceptions
     m.add(
@@ -827,6 +1498,61 @@ pub fn make_ferveo_py_module(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add("UnknownValidator", py.get_type::<UnknownValidator>())?;
     m.add("TooManyTranscripts", py.get_type::<TooManyTranscripts>())?;
     m.add("DuplicateTranscript", py.get_type::<DuplicateTranscript>())?;
+    m.add(
+        "InsufficientRefreshTargets",
+        py.get_type::<InsufficientRefreshTargets>(),
+    )?;
+    m.add("StaleShare", py.get_type::<StaleShare>())?;
+    m.add(
+        "InsufficientRecoveryHelpers",
+        py.get_type::<InsufficientRecoveryHelpers>(),
+    )?;
+    m.add(
+        "InvalidRecoveryShare",
+        py.get_type::<InvalidRecoveryShare>(),
+    )?;
+    m.add(
+        "InsufficientResharingDealers",
+        py.get_type::<InsufficientResharingDealers>(),
+    )?;
+    m.add(
+        "InvalidResharingTranscript",
+        py.get_type::<InvalidResharingTranscript>(),
+    )?;
+    m.add(
+        "InvalidHandoverResult",
+        py.get_type::<InvalidHandoverResult>(),
+    )?;
+    m.add(
+        "DuplicatedValidatorPublicKey",
+        py.get_type::<DuplicatedValidatorPublicKey>(),
+    )?;
+    m.add("InvalidKeyRotation", py.get_type::<InvalidKeyRotation>())?;
+    m.add(
+        "ShareGenerationMismatch",
+        py.get_type::<ShareGenerationMismatch>(),
+    )?;
+    m.add(
+        "ShareGenerationOverflow",
+        py.get_type::<ShareGenerationOverflow>(),
+    )?;
+    m.add("EmptyTranscript", py.get_type::<EmptyTranscript>())?;
+    m.add(
+        "InvalidTranscriptSize",
+        py.get_type::<InvalidTranscriptSize>(),
+    )?;
+    m.add(
+        "IdentityPointInTranscript",
+        py.get_type::<IdentityPointInTranscript>(),
+    )?;
+    m.add(
+        "UnsupportedTranscriptFormat",
+        py.get_type::<UnsupportedTranscriptFormat>(),
+    )?;
+    m.add(
+        "InvalidEvaluationDomainSize",
+        py.get_type::<InvalidEvaluationDomainSize>(),
+    )?;
 
     Ok(())
 }
@@ -985,7 +1711,7 @@ mod test_ferveo_python {
         // Now, the decryption share can be used to decrypt the ciphertext
         // This part is part of the client API
         let shared_secret =
-            combine_decryption_shares_precomputed(decryption_shares);
+            combine_decryption_shares_precomputed(decryption_shares).unwrap();
         let plaintext =
             decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
                 .unwrap();
@@ -1060,20 +1786,169 @@ mod test_ferveo_python {
                 assert!(aggregate
                     .verify(validators_num, messages_vec.clone())
                     .unwrap());
-                aggregate
+                let decryption_share = aggregate
                     .create_decryption_share_simple(
                         &validator_dkg,
                         &ciphertext.header().unwrap(),
                         AAD,
                         validator_keypair,
                     )
-                    .unwrap()
+                    .unwrap();
+
+                // The share is the same for an already checked header
+                let verified_header = ciphertext.verify(AAD).unwrap();
+                let verified_share = aggregate
+                    .create_decryption_share_simple_verified(
+                        &validator_dkg,
+                        &verified_header,
+                        validator_keypair,
+                    )
+                    .unwrap();
+                assert_eq!(verified_share.0, decryption_share.0);
+                decryption_share
             })
             .collect();
 
         // Now, the decryption share can be used to decrypt the ciphertext
         // This part is part of the client API
-        let shared_secret = combine_decryption_shares_simple(decryption_shares);
+        let shared_secret =
+            combine_decryption_shares_simple(decryption_shares).unwrap();
+        let plaintext =
+            decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
+                .unwrap();
+        assert_eq!(plaintext, MSG);
+    }
+
+    #[test]
+    fn test_server_python_api_refresh_and_recovery() {
+        let (shares_num, security_threshold) = (4, 3);
+        let (messages, validators, validator_keypairs) =
+            make_test_inputs(TAU, security_threshold, shares_num, shares_num);
+        let messages_vec: Vec<ValidatorMessage> =
+            messages.values().cloned().collect();
+        let dkgs = (0..shares_num)
+            .map(|i| {
+                Dkg::new(
+                    TAU,
+                    shares_num,
+                    security_threshold,
+                    validators.values().cloned().collect(),
+                    &validators[&i],
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let aggregate = AggregatedTranscript::new(messages_vec).unwrap();
+        let dkg_public_key = aggregate.public_key();
+        let validator_keys = validator_keypairs
+            .iter()
+            .map(|(&i, keypair)| (i, keypair.public_key()))
+            .collect::<HashMap<_, _>>();
+
+        // Every validator deals a refresh transcript, and the shares are refreshed
+        let refresh_transcripts = dkgs
+            .iter()
+            .enumerate()
+            .map(|(i, dkg)| {
+                (i as u32, dkg.generate_refresh_transcript().unwrap())
+            })
+            .collect::<HashMap<_, _>>();
+        let refreshed = aggregate
+            .refresh(refresh_transcripts, validator_keys.clone())
+            .unwrap();
+        assert!(refreshed.is_share_current(0).unwrap());
+
+        // The last validator lost its share, and recovers it under a new key
+        let recovery_index = shares_num - 1;
+        let helper_indices = (0..security_threshold).collect::<Vec<_>>();
+        let helper_keys = helper_indices
+            .iter()
+            .map(|i| (*i, validator_keys[i].clone()))
+            .collect::<HashMap<_, _>>();
+        let recovery_transcripts = helper_indices
+            .iter()
+            .map(|&i| {
+                let transcript = dkgs[i as usize]
+                    .generate_recovery_transcript(
+                        helper_indices.clone(),
+                        recovery_index,
+                    )
+                    .unwrap();
+                (i, transcript)
+            })
+            .collect::<HashMap<_, _>>();
+        let recovering_keypair = Keypair::random();
+        let recovery_shares = helper_indices
+            .iter()
+            .map(|&i| {
+                let handover_transcript = dkgs[0]
+                    .generate_recovery_handover_transcript(
+                        &refreshed,
+                        recovery_transcripts.clone(),
+                        i,
+                        &recovering_keypair,
+                    )
+                    .unwrap();
+                refreshed
+                    .create_recovery_share(
+                        recovery_transcripts.clone(),
+                        helper_keys.clone(),
+                        recovery_index,
+                        &handover_transcript,
+                        &validator_keypairs[&i],
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let recovered = refreshed
+            .recover_share(
+                recovery_transcripts,
+                helper_keys,
+                recovery_index,
+                recovery_shares,
+            )
+            .unwrap();
+
+        // The recovering validator decrypts together with the helpers
+        let mut new_validators = validators;
+        new_validators.insert(
+            recovery_index,
+            Validator::new(
+                format!("0x{:040}", 123456789),
+                &recovering_keypair.public_key(),
+                recovery_index,
+            )
+            .unwrap(),
+        );
+        let ciphertext = encrypt(MSG.to_vec(), AAD, &dkg_public_key).unwrap();
+        let decryption_shares = (1..shares_num)
+            .map(|i| {
+                let validator_keypair = if i == recovery_index {
+                    &recovering_keypair
+                } else {
+                    &validator_keypairs[&i]
+                };
+                let validator_dkg = Dkg::new(
+                    TAU,
+                    shares_num,
+                    security_threshold,
+                    new_validators.values().cloned().collect(),
+                    &new_validators[&i],
+                )
+                .unwrap();
+                recovered
+                    .create_decryption_share_simple(
+                        &validator_dkg,
+                        &ciphertext.header().unwrap(),
+                        AAD,
+                        validator_keypair,
+                    )
+                    .unwrap()
+            })
+            .collect();
+
+        let shared_secret =
+            combine_decryption_shares_simple(decryption_shares).unwrap();
         let plaintext =
             decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
                 .unwrap();
@@ -1197,7 +2072,8 @@ mod test_ferveo_python {
 
         // Now, the decryption share can be used to decrypt the ciphertextsdfg
         // This part is part of the client APIsdfg
-        let shared_secret = combine_decryption_shares_simple(decryption_shares);
+        let shared_secret =
+            combine_decryption_shares_simple(decryption_shares).unwrap();
         let plaintext =
             decrypt_with_shared_secret(&ciphertext, AAD, &shared_secret)
                 .unwrap();
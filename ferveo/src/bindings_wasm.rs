@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     convert::{TryFrom, TryInto},
     fmt,
     str::FromStr,
@@ -84,6 +85,21 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "DecryptionSharePrecomputed[]")]
     pub type DecryptionSharePrecomputedArray;
+
+    #[wasm_bindgen(typescript_type = "BlindedDecryptionShareSimple[]")]
+    pub type BlindedDecryptionShareSimpleArray;
+
+    #[wasm_bindgen(typescript_type = "BlindedDecryptionSharePrecomputed[]")]
+    pub type BlindedDecryptionSharePrecomputedArray;
+
+    #[wasm_bindgen(typescript_type = "RefreshMessage[]")]
+    pub type RefreshMessageArray;
+
+    #[wasm_bindgen(typescript_type = "RecoveryMessage[]")]
+    pub type RecoveryMessageArray;
+
+    #[wasm_bindgen(typescript_type = "RecoveryShare[]")]
+    pub type RecoveryShareArray;
 }
 
 fn unwrap_messages_js(
@@ -97,6 +113,42 @@ fn unwrap_messages_js(
     Ok(messages)
 }
 
+fn unwrap_validators_js(
+    validators: &ValidatorArray,
+) -> JsResult<Vec<api::Validator>> {
+    try_from_js_array::<Validator>(validators)?
+        .into_iter()
+        .map(|v| v.to_inner())
+        .collect()
+}
+
+fn unwrap_refresh_messages_js(
+    messages: &RefreshMessageArray,
+) -> JsResult<HashMap<u32, api::RefreshTranscript>> {
+    Ok(try_from_js_array::<RefreshMessage>(messages)?
+        .into_iter()
+        .map(|m| (m.0.share_index, m.1 .0))
+        .collect())
+}
+
+fn unwrap_recovery_messages_js(
+    messages: &RecoveryMessageArray,
+) -> JsResult<HashMap<u32, api::RecoveryTranscript>> {
+    Ok(try_from_js_array::<RecoveryMessage>(messages)?
+        .into_iter()
+        .map(|m| (m.0.share_index, m.1 .0))
+        .collect())
+}
+
+fn unwrap_validator_keys_js(
+    validators: &ValidatorArray,
+) -> JsResult<HashMap<u32, api::ValidatorPublicKey>> {
+    Ok(unwrap_validators_js(validators)?
+        .into_iter()
+        .map(|v| (v.share_index, v.public_key))
+        .collect())
+}
+
 macro_rules! generate_equals {
     ($struct_name:ident) => {
         #[wasm_bindgen]
@@ -200,12 +252,58 @@ generate_common_methods!(DecryptionShareSimple);
 #[derive(TryFromJsValue)]
 #[wasm_bindgen]
 #[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
-pub struct DecryptionSharePrecomputed(
-    ferveo_tdec::api::DecryptionSharePrecomputed,
-);
+pub struct DecryptionSharePrecomputed(api::DecryptionSharePrecomputed);
 
 generate_common_methods!(DecryptionSharePrecomputed);
 
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct DecryptionShareProof(api::DecryptionShareProof);
+
+generate_common_methods!(DecryptionShareProof);
+
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct BlindedDecryptionShareSimple(api::BlindedDecryptionShareSimple);
+
+generate_common_methods!(BlindedDecryptionShareSimple);
+
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct BlindedDecryptionSharePrecomputed(
+    api::BlindedDecryptionSharePrecomputed,
+);
+
+generate_common_methods!(BlindedDecryptionSharePrecomputed);
+
+/// Ephemeral keypair of a decryption requester
+#[wasm_bindgen]
+#[derive(derive_more::From, derive_more::AsRef)]
+pub struct RequesterKeypair(api::RequesterKeypair);
+
+generate_bytes_serialization!(RequesterKeypair);
+
+#[wasm_bindgen]
+impl RequesterKeypair {
+    #[wasm_bindgen]
+    pub fn random() -> Self {
+        Self(api::RequesterKeypair::random())
+    }
+
+    #[wasm_bindgen(js_name = "publicKey", getter)]
+    pub fn public_key(&self) -> RequesterPublicKey {
+        RequesterPublicKey(self.0.public_key())
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct RequesterPublicKey(api::RequesterPublicKey);
+
+generate_common_methods!(RequesterPublicKey);
+
 type InnerPublicKey = api::ValidatorPublicKey;
 
 #[wasm_bindgen]
@@ -241,6 +339,12 @@ impl Ciphertext {
     pub fn payload(&self) -> Vec<u8> {
         self.0.payload()
     }
+
+    #[wasm_bindgen]
+    pub fn verify(&self, aad: &[u8]) -> JsResult<VerifiedCiphertextHeader> {
+        let header = self.0.verify(aad).map_err(map_js_err)?;
+        Ok(VerifiedCiphertextHeader(header))
+    }
 }
 
 generate_common_methods!(Ciphertext);
@@ -257,8 +361,31 @@ generate_common_methods!(Ciphertext);
 )]
 pub struct CiphertextHeader(api::CiphertextHeader);
 
+#[wasm_bindgen]
+impl CiphertextHeader {
+    #[wasm_bindgen]
+    pub fn verify(&self, aad: &[u8]) -> JsResult<VerifiedCiphertextHeader> {
+        let header = self.0.verify(aad).map_err(map_js_err)?;
+        Ok(VerifiedCiphertextHeader(header))
+    }
+}
+
 generate_common_methods!(CiphertextHeader);
 
+/// A ciphertext header that was checked once for an aad. It is not
+/// serializable, so it can't be made without checking the header.
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct VerifiedCiphertextHeader(api::VerifiedCiphertextHeader);
+
+#[wasm_bindgen]
+impl VerifiedCiphertextHeader {
+    #[wasm_bindgen(js_name = "header", getter)]
+    pub fn header(&self) -> CiphertextHeader {
+        CiphertextHeader(self.0.header())
+    }
+}
+
 #[wasm_bindgen(js_name = "ferveoEncrypt")]
 pub fn ferveo_encrypt(
     message: &[u8],
@@ -285,7 +412,8 @@ pub fn combine_decryption_shares_simple(
     let shares =
         try_from_js_array::<DecryptionShareSimple>(decryption_shares_js)?;
     let shares: Vec<_> = shares.iter().map(|share| share.0.clone()).collect();
-    let shared_secret = api::combine_shares_simple(&shares[..]);
+    let shared_secret =
+        api::combine_shares_simple(&shares[..]).map_err(map_js_err)?;
     Ok(SharedSecret(shared_secret))
 }
 
@@ -299,8 +427,39 @@ pub fn combine_decryption_shares_precomputed(
         .iter()
         .map(|share| share.0.clone())
         .collect::<Vec<_>>();
-    let shared_secret = api::share_combine_precomputed(&shares[..]);
-    Ok(SharedSecret(api::SharedSecret(shared_secret)))
+    let shared_secret =
+        api::combine_shares_precomputed(&shares[..]).map_err(map_js_err)?;
+    Ok(SharedSecret(shared_secret))
+}
+
+#[wasm_bindgen(js_name = "combineBlindedDecryptionSharesSimple")]
+pub fn combine_blinded_decryption_shares_simple(
+    decryption_shares_js: &BlindedDecryptionShareSimpleArray,
+    requester_keypair: &RequesterKeypair,
+) -> JsResult<SharedSecret> {
+    let shares = try_from_js_array::<BlindedDecryptionShareSimple>(
+        decryption_shares_js,
+    )?;
+    let shares: Vec<_> = shares.iter().map(|share| share.0.clone()).collect();
+    let shared_secret =
+        api::combine_blinded_shares_simple(&shares, &requester_keypair.0)
+            .map_err(map_js_err)?;
+    Ok(SharedSecret(shared_secret))
+}
+
+#[wasm_bindgen(js_name = "combineBlindedDecryptionSharesPrecomputed")]
+pub fn combine_blinded_decryption_shares_precomputed(
+    decryption_shares_js: &BlindedDecryptionSharePrecomputedArray,
+    requester_keypair: &RequesterKeypair,
+) -> JsResult<SharedSecret> {
+    let shares = try_from_js_array::<BlindedDecryptionSharePrecomputed>(
+        decryption_shares_js,
+    )?;
+    let shares: Vec<_> = shares.iter().map(|share| share.0.clone()).collect();
+    let shared_secret =
+        api::combine_blinded_shares_precomputed(&shares, &requester_keypair.0)
+            .map_err(map_js_err)?;
+    Ok(SharedSecret(shared_secret))
 }
 
 #[wasm_bindgen(js_name = "decryptWithSharedSecret")]
@@ -323,6 +482,44 @@ pub struct DkgPublicKey(InnerDkgPublicKey);
 generate_equals!(DkgPublicKey);
 generate_boxed_bytes_serialization!(DkgPublicKey, InnerDkgPublicKey);
 
+/// A `DkgPublicKey` with precomputed values for faster encryption.
+/// Serialize it with `toBytes` to cache it across sessions.
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::From, derive_more::AsRef)]
+pub struct EncryptionKey(api::EncryptionKey);
+
+generate_common_methods!(EncryptionKey);
+
+#[wasm_bindgen]
+impl EncryptionKey {
+    #[wasm_bindgen(constructor)]
+    pub fn new(dkg_public_key: &DkgPublicKey) -> EncryptionKey {
+        set_panic_hook();
+        EncryptionKey(api::EncryptionKey::new(&dkg_public_key.0))
+    }
+
+    #[wasm_bindgen(js_name = "publicKey", getter)]
+    pub fn public_key(&self) -> DkgPublicKey {
+        DkgPublicKey(self.0.public_key())
+    }
+}
+
+#[wasm_bindgen(js_name = "ferveoEncryptWithKey")]
+pub fn ferveo_encrypt_with_key(
+    message: &[u8],
+    aad: &[u8],
+    encryption_key: &EncryptionKey,
+) -> JsResult<Ciphertext> {
+    set_panic_hook();
+    let ciphertext = api::encrypt_with_key(
+        SecretBox::new(message.to_vec()),
+        aad,
+        &encryption_key.0,
+    )
+    .map_err(map_js_err)?;
+    Ok(Ciphertext(ciphertext))
+}
+
 #[wasm_bindgen]
 pub struct Dkg(api::Dkg);
 
@@ -371,6 +568,87 @@ impl Dkg {
             .map_err(map_js_err)?;
         Ok(AggregatedTranscript(aggregated_transcript))
     }
+
+    #[wasm_bindgen(js_name = "generateRefreshTranscript")]
+    pub fn generate_refresh_transcript(&self) -> JsResult<RefreshTranscript> {
+        let rng = &mut thread_rng();
+        let refresh_transcript = self
+            .0
+            .generate_refresh_transcript(rng)
+            .map_err(map_js_err)?;
+        Ok(RefreshTranscript(refresh_transcript))
+    }
+
+    #[wasm_bindgen(js_name = "generateRefreshTranscriptFor")]
+    pub fn generate_refresh_transcript_for(
+        &self,
+        share_indices: &[u32],
+    ) -> JsResult<RefreshTranscript> {
+        let rng = &mut thread_rng();
+        let refresh_transcript = self
+            .0
+            .generate_refresh_transcript_for(share_indices, rng)
+            .map_err(map_js_err)?;
+        Ok(RefreshTranscript(refresh_transcript))
+    }
+
+    #[wasm_bindgen(js_name = "generateHandoverTranscript")]
+    pub fn generate_handover_transcript(
+        &self,
+        aggregate: &AggregatedTranscript,
+        handover_slot_index: u32,
+        incoming_validator_keypair: &Keypair,
+    ) -> JsResult<HandoverTranscript> {
+        let rng = &mut thread_rng();
+        let handover_transcript = self
+            .0
+            .generate_handover_transcript(
+                &aggregate.0,
+                handover_slot_index,
+                &incoming_validator_keypair.0,
+                rng,
+            )
+            .map_err(map_js_err)?;
+        Ok(HandoverTranscript(handover_transcript))
+    }
+
+    #[wasm_bindgen(js_name = "generateRecoveryTranscript")]
+    pub fn generate_recovery_transcript(
+        &self,
+        helper_indices: &[u32],
+        recovery_index: u32,
+    ) -> JsResult<RecoveryTranscript> {
+        let rng = &mut thread_rng();
+        let recovery_transcript = self
+            .0
+            .generate_recovery_transcript(helper_indices, recovery_index, rng)
+            .map_err(map_js_err)?;
+        Ok(RecoveryTranscript(recovery_transcript))
+    }
+
+    #[wasm_bindgen(js_name = "generateRecoveryHandoverTranscript")]
+    pub fn generate_recovery_handover_transcript(
+        &self,
+        aggregate: &AggregatedTranscript,
+        recovery_messages_js: &RecoveryMessageArray,
+        helper_index: u32,
+        recovering_validator_keypair: &Keypair,
+    ) -> JsResult<HandoverTranscript> {
+        let rng = &mut thread_rng();
+        let recovery_transcripts =
+            unwrap_recovery_messages_js(recovery_messages_js)?;
+        let handover_transcript = self
+            .0
+            .generate_recovery_handover_transcript(
+                &aggregate.0,
+                &recovery_transcripts,
+                helper_index,
+                &recovering_validator_keypair.0,
+                rng,
+            )
+            .map_err(map_js_err)?;
+        Ok(HandoverTranscript(handover_transcript))
+    }
 }
 
 #[wasm_bindgen]
@@ -379,6 +657,39 @@ pub struct Transcript(pub(crate) api::Transcript);
 
 generate_common_methods!(Transcript);
 
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct RefreshTranscript(api::RefreshTranscript);
+
+generate_common_methods!(RefreshTranscript);
+
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct RecoveryTranscript(api::RecoveryTranscript);
+
+generate_common_methods!(RecoveryTranscript);
+
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct RecoveryShare(api::RecoveryShare);
+
+generate_common_methods!(RecoveryShare);
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct HandoverTranscript(api::HandoverTranscript);
+
+generate_common_methods!(HandoverTranscript);
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct HandoverResult(api::HandoverResult);
+
+generate_common_methods!(HandoverResult);
+
 #[wasm_bindgen(js_name = EthereumAddress)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EthereumAddress(api::EthereumAddress);
@@ -445,6 +756,11 @@ impl Validator {
     pub fn address(&self) -> EthereumAddress {
         self.address.clone()
     }
+
+    #[wasm_bindgen(getter, js_name = "shareIndex")]
+    pub fn share_index(&self) -> u32 {
+        self.share_index
+    }
 }
 
 #[derive(TryFromJsValue)]
@@ -479,6 +795,58 @@ impl ValidatorMessage {
     }
 }
 
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct RefreshMessage(Validator, RefreshTranscript);
+
+#[wasm_bindgen]
+impl RefreshMessage {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        validator: &Validator,
+        transcript: &RefreshTranscript,
+    ) -> JsResult<RefreshMessage> {
+        Ok(Self(validator.clone(), transcript.clone()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn validator(&self) -> Validator {
+        self.0.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn transcript(&self) -> RefreshTranscript {
+        self.1.clone()
+    }
+}
+
+#[derive(TryFromJsValue)]
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct RecoveryMessage(Validator, RecoveryTranscript);
+
+#[wasm_bindgen]
+impl RecoveryMessage {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        validator: &Validator,
+        transcript: &RecoveryTranscript,
+    ) -> JsResult<RecoveryMessage> {
+        Ok(Self(validator.clone(), transcript.clone()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn validator(&self) -> Validator {
+        self.0.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn transcript(&self) -> RecoveryTranscript {
+        self.1.clone()
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AggregatedTranscript(api::AggregatedTranscript);
@@ -489,9 +857,21 @@ impl AggregatedTranscript {
     pub fn public_key(&self) -> DkgPublicKey {
         DkgPublicKey(self.0.public_key())
     }
+
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> JsResult<AggregatedTranscript> {
+        api::AggregatedTranscript::from_bytes(bytes)
+            .map_err(map_js_err)
+            .map(Self)
+    }
+
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> JsResult<Vec<u8>> {
+        to_js_bytes(&self.0)
+    }
 }
 
-generate_common_methods!(AggregatedTranscript);
+generate_equals!(AggregatedTranscript);
 
 #[wasm_bindgen]
 impl AggregatedTranscript {
@@ -531,12 +911,7 @@ impl AggregatedTranscript {
         selected_validators_js: &ValidatorArray,
     ) -> JsResult<DecryptionSharePrecomputed> {
         set_panic_hook();
-        let selected_validators =
-            try_from_js_array::<Validator>(selected_validators_js)?;
-        let selected_validators = selected_validators
-            .into_iter()
-            .map(|v| v.to_inner())
-            .collect::<JsResult<Vec<_>>>()?;
+        let selected_validators = unwrap_validators_js(selected_validators_js)?;
         let decryption_share = self
             .0
             .create_decryption_share_precomputed(
@@ -570,8 +945,396 @@ impl AggregatedTranscript {
             .map_err(map_js_err)?;
         Ok(DecryptionShareSimple(decryption_share))
     }
+
+    #[wasm_bindgen(js_name = "createDecryptionSharePrecomputedVerified")]
+    pub fn create_decryption_share_precomputed_verified(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &VerifiedCiphertextHeader,
+        validator_keypair: &Keypair,
+        selected_validators_js: &ValidatorArray,
+    ) -> JsResult<DecryptionSharePrecomputed> {
+        set_panic_hook();
+        let selected_validators = unwrap_validators_js(selected_validators_js)?;
+        let decryption_share = self
+            .0
+            .create_decryption_share_precomputed_verified(
+                &dkg.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+                &selected_validators,
+            )
+            .map_err(map_js_err)?;
+        Ok(DecryptionSharePrecomputed(decryption_share))
+    }
+
+    #[wasm_bindgen(js_name = "createDecryptionShareSimpleVerified")]
+    pub fn create_decryption_share_simple_verified(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &VerifiedCiphertextHeader,
+        validator_keypair: &Keypair,
+    ) -> JsResult<DecryptionShareSimple> {
+        set_panic_hook();
+        let decryption_share = self
+            .0
+            .create_decryption_share_simple_verified(
+                &dkg.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+            )
+            .map_err(map_js_err)?;
+        Ok(DecryptionShareSimple(decryption_share))
+    }
+
+    #[wasm_bindgen(js_name = "createDecryptionShareProofPrecomputed")]
+    pub fn create_decryption_share_proof_precomputed(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &DecryptionSharePrecomputed,
+        ciphertext_header: &CiphertextHeader,
+        validator_keypair: &Keypair,
+        selected_validators_js: &ValidatorArray,
+    ) -> JsResult<DecryptionShareProof> {
+        set_panic_hook();
+        let selected_validators = unwrap_validators_js(selected_validators_js)?;
+        let proof = self
+            .0
+            .create_decryption_share_proof_precomputed(
+                &dkg.0,
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+                &selected_validators,
+            )
+            .map_err(map_js_err)?;
+        Ok(DecryptionShareProof(proof))
+    }
+
+    #[wasm_bindgen(js_name = "createDecryptionShareProofSimple")]
+    pub fn create_decryption_share_proof_simple(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &DecryptionShareSimple,
+        ciphertext_header: &CiphertextHeader,
+        validator_keypair: &Keypair,
+    ) -> JsResult<DecryptionShareProof> {
+        set_panic_hook();
+        let proof = self
+            .0
+            .create_decryption_share_proof_simple(
+                &dkg.0,
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &validator_keypair.0,
+            )
+            .map_err(map_js_err)?;
+        Ok(DecryptionShareProof(proof))
+    }
+
+    #[wasm_bindgen(js_name = "verifyDecryptionSharePrecomputed")]
+    pub fn verify_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &DecryptionSharePrecomputed,
+        proof: &DecryptionShareProof,
+        ciphertext_header: &CiphertextHeader,
+        selected_validators_js: &ValidatorArray,
+    ) -> JsResult<bool> {
+        set_panic_hook();
+        let selected_validators = unwrap_validators_js(selected_validators_js)?;
+        self.0
+            .verify_decryption_share_precomputed(
+                &dkg.0,
+                &decryption_share.0,
+                &proof.0,
+                &ciphertext_header.0,
+                &selected_validators,
+            )
+            .map_err(map_js_err)
+    }
+
+    #[wasm_bindgen(js_name = "verifyDecryptionShareSimple")]
+    pub fn verify_decryption_share_simple(
+        &self,
+        decryption_share: &DecryptionShareSimple,
+        proof: &DecryptionShareProof,
+        ciphertext_header: &CiphertextHeader,
+    ) -> JsResult<bool> {
+        set_panic_hook();
+        self.0
+            .verify_decryption_share_simple(
+                &decryption_share.0,
+                &proof.0,
+                &ciphertext_header.0,
+            )
+            .map_err(map_js_err)
+    }
+
+    #[wasm_bindgen(js_name = "createBlindedDecryptionSharePrecomputed")]
+    pub fn create_blinded_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &Keypair,
+        selected_validators_js: &ValidatorArray,
+        requester_public_key: &RequesterPublicKey,
+    ) -> JsResult<BlindedDecryptionSharePrecomputed> {
+        set_panic_hook();
+        let selected_validators = unwrap_validators_js(selected_validators_js)?;
+        let decryption_share = self
+            .0
+            .create_blinded_decryption_share_precomputed(
+                &dkg.0,
+                &ciphertext_header.0,
+                aad,
+                &validator_keypair.0,
+                &selected_validators,
+                &requester_public_key.0,
+            )
+            .map_err(map_js_err)?;
+        Ok(BlindedDecryptionSharePrecomputed(decryption_share))
+    }
+
+    #[wasm_bindgen(js_name = "createBlindedDecryptionShareSimple")]
+    pub fn create_blinded_decryption_share_simple(
+        &self,
+        dkg: &Dkg,
+        ciphertext_header: &CiphertextHeader,
+        aad: &[u8],
+        validator_keypair: &Keypair,
+        requester_public_key: &RequesterPublicKey,
+    ) -> JsResult<BlindedDecryptionShareSimple> {
+        set_panic_hook();
+        let decryption_share = self
+            .0
+            .create_blinded_decryption_share_simple(
+                &dkg.0,
+                &ciphertext_header.0,
+                aad,
+                &validator_keypair.0,
+                &requester_public_key.0,
+            )
+            .map_err(map_js_err)?;
+        Ok(BlindedDecryptionShareSimple(decryption_share))
+    }
+
+    #[wasm_bindgen(js_name = "verifyBlindedDecryptionSharePrecomputed")]
+    pub fn verify_blinded_decryption_share_precomputed(
+        &self,
+        dkg: &Dkg,
+        decryption_share: &BlindedDecryptionSharePrecomputed,
+        ciphertext_header: &CiphertextHeader,
+        selected_validators_js: &ValidatorArray,
+        requester_public_key: &RequesterPublicKey,
+    ) -> JsResult<bool> {
+        set_panic_hook();
+        let selected_validators = unwrap_validators_js(selected_validators_js)?;
+        self.0
+            .verify_blinded_decryption_share_precomputed(
+                &dkg.0,
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &selected_validators,
+                &requester_public_key.0,
+            )
+            .map_err(map_js_err)
+    }
+
+    #[wasm_bindgen(js_name = "verifyBlindedDecryptionShareSimple")]
+    pub fn verify_blinded_decryption_share_simple(
+        &self,
+        decryption_share: &BlindedDecryptionShareSimple,
+        ciphertext_header: &CiphertextHeader,
+        requester_public_key: &RequesterPublicKey,
+    ) -> JsResult<bool> {
+        set_panic_hook();
+        self.0
+            .verify_blinded_decryption_share_simple(
+                &decryption_share.0,
+                &ciphertext_header.0,
+                &requester_public_key.0,
+            )
+            .map_err(map_js_err)
+    }
+
+    #[wasm_bindgen(js_name = "createKeyRotation")]
+    pub fn create_key_rotation(
+        &self,
+        share_index: u32,
+        validator_keypair: &Keypair,
+        new_validator_keypair: &Keypair,
+    ) -> JsResult<KeyRotation> {
+        set_panic_hook();
+        let rng = &mut thread_rng();
+        let key_rotation = self
+            .0
+            .create_key_rotation(
+                share_index,
+                &validator_keypair.0,
+                &new_validator_keypair.0,
+                rng,
+            )
+            .map_err(map_js_err)?;
+        Ok(KeyRotation(key_rotation))
+    }
+
+    #[wasm_bindgen(js_name = "verifyKeyRotation")]
+    pub fn verify_key_rotation(
+        &self,
+        key_rotation: &KeyRotation,
+    ) -> JsResult<bool> {
+        set_panic_hook();
+        self.0
+            .verify_key_rotation(&key_rotation.0)
+            .map_err(map_js_err)
+    }
+
+    #[wasm_bindgen(js_name = "applyKeyRotation")]
+    pub fn apply_key_rotation(
+        &self,
+        key_rotation: &KeyRotation,
+    ) -> JsResult<AggregatedTranscript> {
+        set_panic_hook();
+        let new_aggregate = self
+            .0
+            .apply_key_rotation(&key_rotation.0)
+            .map_err(map_js_err)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+
+    #[wasm_bindgen(js_name = "isShareCurrent")]
+    pub fn is_share_current(&self, share_index: u32) -> JsResult<bool> {
+        set_panic_hook();
+        self.0.is_share_current(share_index).map_err(map_js_err)
+    }
+
+    #[wasm_bindgen]
+    pub fn refresh(
+        &self,
+        refresh_messages_js: &RefreshMessageArray,
+        validators_js: &ValidatorArray,
+    ) -> JsResult<AggregatedTranscript> {
+        set_panic_hook();
+        let refresh_transcripts =
+            unwrap_refresh_messages_js(refresh_messages_js)?;
+        let validator_keys_map = unwrap_validator_keys_js(validators_js)?;
+        let refreshed = self
+            .0
+            .refresh(&refresh_transcripts, &validator_keys_map)
+            .map_err(map_js_err)?;
+        Ok(AggregatedTranscript(refreshed))
+    }
+
+    #[wasm_bindgen(js_name = "createRecoveryShare")]
+    pub fn create_recovery_share(
+        &self,
+        recovery_messages_js: &RecoveryMessageArray,
+        helpers_js: &ValidatorArray,
+        recovery_index: u32,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &Keypair,
+    ) -> JsResult<RecoveryShare> {
+        set_panic_hook();
+        let recovery_transcripts =
+            unwrap_recovery_messages_js(recovery_messages_js)?;
+        let helper_keys_map = unwrap_validator_keys_js(helpers_js)?;
+        let recovery_share = self
+            .0
+            .create_recovery_share(
+                &recovery_transcripts,
+                &helper_keys_map,
+                recovery_index,
+                &handover_transcript.0,
+                &validator_keypair.0,
+            )
+            .map_err(map_js_err)?;
+        Ok(RecoveryShare(recovery_share))
+    }
+
+    #[wasm_bindgen(js_name = "recoverShare")]
+    pub fn recover_share(
+        &self,
+        recovery_messages_js: &RecoveryMessageArray,
+        helpers_js: &ValidatorArray,
+        recovery_index: u32,
+        recovery_shares_js: &RecoveryShareArray,
+    ) -> JsResult<AggregatedTranscript> {
+        set_panic_hook();
+        let recovery_transcripts =
+            unwrap_recovery_messages_js(recovery_messages_js)?;
+        let helper_keys_map = unwrap_validator_keys_js(helpers_js)?;
+        let recovery_shares =
+            try_from_js_array::<RecoveryShare>(recovery_shares_js)?
+                .into_iter()
+                .map(|share| share.0)
+                .collect::<Vec<_>>();
+        let recovered = self
+            .0
+            .recover_share(
+                &recovery_transcripts,
+                &helper_keys_map,
+                recovery_index,
+                &recovery_shares,
+            )
+            .map_err(map_js_err)?;
+        Ok(AggregatedTranscript(recovered))
+    }
+
+    #[wasm_bindgen(js_name = "finalizeHandover")]
+    pub fn finalize_handover(
+        &self,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &Keypair,
+    ) -> JsResult<AggregatedTranscript> {
+        set_panic_hook();
+        let new_aggregate = self
+            .0
+            .finalize_handover(&handover_transcript.0, &validator_keypair.0)
+            .map_err(map_js_err)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
+
+    #[wasm_bindgen(js_name = "createHandoverResult")]
+    pub fn create_handover_result(
+        &self,
+        handover_transcript: &HandoverTranscript,
+        validator_keypair: &Keypair,
+    ) -> JsResult<HandoverResult> {
+        set_panic_hook();
+        let rng = &mut thread_rng();
+        let handover_result = self
+            .0
+            .create_handover_result(
+                &handover_transcript.0,
+                &validator_keypair.0,
+                rng,
+            )
+            .map_err(map_js_err)?;
+        Ok(HandoverResult(handover_result))
+    }
+
+    #[wasm_bindgen(js_name = "applyHandoverResult")]
+    pub fn apply_handover_result(
+        &self,
+        handover_result: &HandoverResult,
+    ) -> JsResult<AggregatedTranscript> {
+        set_panic_hook();
+        let new_aggregate = self
+            .0
+            .apply_handover_result(&handover_result.0)
+            .map_err(map_js_err)?;
+        Ok(AggregatedTranscript(new_aggregate))
+    }
 }
 
+#[wasm_bindgen]
+#[derive(Clone, Debug, derive_more::AsRef, derive_more::From)]
+pub struct KeyRotation(api::KeyRotation);
+
+generate_common_methods!(KeyRotation);
+
 #[wasm_bindgen]
 #[derive(Serialize, Deserialize)]
 pub struct Keypair(api::ValidatorKeypair);
@@ -4,16 +4,18 @@ use ark_ec::pairing::Pairing;
 use ark_poly::EvaluationDomain;
 use ark_std::UniformRand;
 use ferveo_common::PublicKey;
-use ferveo_tdec::DomainPoint;
+use ferveo_tdec::{DomainPoint, HashToG2};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    assert_no_share_duplicates, refresh, AggregatedTranscript, Error,
-    EthereumAddress, PubliclyVerifiableSS, Result, UpdateTranscript, Validator,
+    assert_no_share_duplicates, evaluation_domain, refresh,
+    AggregatedTranscript, Error, EthereumAddress, PubliclyVerifiableSS, Result,
+    UpdateTranscript, Validator,
 };
 
 pub type DomainIndexMap<E> = HashMap<u32, DomainPoint<E>>;
+pub type DomainAndKeyMap<E> = HashMap<u32, (DomainPoint<E>, PublicKey<E>)>;
 pub type ValidatorMessage<E> = (Validator<E>, PubliclyVerifiableSS<E>);
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -80,7 +82,7 @@ pub struct PubliclyVerifiableDkg<E: Pairing> {
     pub me: Validator<E>,
 }
 
-impl<E: Pairing> PubliclyVerifiableDkg<E> {
+impl<E: HashToG2> PubliclyVerifiableDkg<E> {
     /// Create a new DKG context to participate in the DKG
     /// Every identity in the DKG is linked to a bls12-381 public key;
     /// `validators`: List of validators
@@ -94,10 +96,7 @@ impl<E: Pairing> PubliclyVerifiableDkg<E> {
     ) -> Result<Self> {
         assert_no_share_duplicates(validators)?;
 
-        let domain = ark_poly::GeneralEvaluationDomain::<E::ScalarField>::new(
-            validators.len(),
-        )
-        .expect("unable to construct domain");
+        let domain = evaluation_domain::<E::ScalarField>(validators.len())?;
 
         let validators: ValidatorsByIndex<E> = validators
             .iter()
@@ -177,17 +176,14 @@ impl<E: Pairing> PubliclyVerifiableDkg<E> {
 
     // TODO: Revisit naming later
     /// Return a map of domain points for the DKG
-    pub fn domain_and_key_map(
-        &self,
-    ) -> HashMap<u32, (DomainPoint<E>, PublicKey<E>)> {
-        let map = self.domain_point_map();
+    pub fn domain_and_key_map(&self) -> Result<DomainAndKeyMap<E>> {
         self.validators
             .values()
             .map(|v| {
-                let domain_point = map.get(&v.share_index).unwrap();
-                (v.share_index, (*domain_point, v.public_key))
+                let domain_point = self.get_domain_point(v.share_index)?;
+                Ok((v.share_index, (domain_point, v.public_key)))
             })
-            .collect::<_>()
+            .collect()
     }
 
     /// Verify PVSS transcripts against the set of validators in the DKG
@@ -206,7 +202,12 @@ impl<E: Pairing> PubliclyVerifiableDkg<E> {
                 return Err(Error::DuplicateDealer(sender.clone()));
             } else if transcript_set.contains(transcript) {
                 return Err(Error::DuplicateTranscript(sender.clone()));
-            } else if !transcript.verify_optimistic() {
+            }
+            transcript.check_structure(
+                self.dkg_params.security_threshold(),
+                self.validators.len() as u32,
+            )?;
+            if !transcript.verify_optimistic() {
                 return Err(Error::InvalidPvssTranscript(sender.clone()));
             }
             validator_set.insert(sender.clone());
@@ -226,18 +227,166 @@ impl<E: Pairing> PubliclyVerifiableDkg<E> {
     }
 
     // Returns a new refresh transcript for current validators in DKG
-    // TODO: Allow to pass a parameter to restrict target validators - #199
     pub fn generate_refresh_transcript<R: RngCore>(
         &self,
         rng: &mut R,
     ) -> Result<refresh::UpdateTranscript<E>> {
+        let share_indices = self.validators.keys().copied().collect::<Vec<_>>();
+        self.generate_refresh_transcript_for(&share_indices, rng)
+    }
+
+    // Returns a new refresh transcript for a subset of the validators in DKG,
+    // e.g. the ones currently online
+    pub fn generate_refresh_transcript_for<R: RngCore>(
+        &self,
+        share_indices: &[u32],
+        rng: &mut R,
+    ) -> Result<refresh::UpdateTranscript<E>> {
+        let threshold = self.dkg_params.security_threshold();
+        if (share_indices.len() as u32) < threshold {
+            return Err(Error::InsufficientRefreshTargets(
+                threshold,
+                share_indices.len() as u32,
+            ));
+        }
+        self.check_share_indices(share_indices)?;
         Ok(UpdateTranscript::create_refresh_updates(
-            &self.domain_and_key_map(),
-            self.dkg_params.security_threshold(),
+            &self.domain_and_key_map_for(share_indices)?,
+            threshold,
+            rng,
+        ))
+    }
+
+    // Returns a new recovery transcript for the share at `recovery_index`,
+    // dealing updates to the helpers in `helper_indices`
+    pub fn generate_recovery_transcript<R: RngCore>(
+        &self,
+        helper_indices: &[u32],
+        recovery_index: u32,
+        rng: &mut R,
+    ) -> Result<refresh::UpdateTranscript<E>> {
+        let threshold = self.dkg_params.security_threshold();
+        if (helper_indices.len() as u32) < threshold {
+            return Err(Error::InsufficientRecoveryHelpers(
+                threshold,
+                helper_indices.len() as u32,
+            ));
+        }
+        if helper_indices.contains(&recovery_index) {
+            return Err(Error::InvalidShareIndex(recovery_index));
+        }
+        self.check_share_indices(helper_indices)?;
+        let recovery_point = self.get_domain_point(recovery_index)?;
+        Ok(UpdateTranscript::create_recovery_updates(
+            &self.domain_and_key_map_for(helper_indices)?,
+            &recovery_point,
+            threshold,
             rng,
         ))
     }
 
+    // Returns the handover transcript a recovering validator sends to a helper,
+    // for the helper's share masked by the recovery transcripts
+    pub fn generate_recovery_handover_transcript<R: RngCore>(
+        &self,
+        aggregate: &AggregatedTranscript<E>,
+        update_transcripts: &HashMap<u32, refresh::UpdateTranscript<E>>,
+        helper_index: u32,
+        recovering_validator_keypair: &ferveo_common::Keypair<E>,
+        rng: &mut R,
+    ) -> Result<refresh::HandoverTranscript<E>> {
+        let helper = self
+            .validators
+            .get(&helper_index)
+            .ok_or(Error::InvalidShareIndex(helper_index))?;
+        let masked_share = aggregate.aggregate.get_recovery_blinded_share(
+            update_transcripts,
+            helper_index,
+            &helper.public_key,
+        )?;
+        Ok(refresh::HandoverTranscript::<E>::new(
+            helper_index,
+            &masked_share,
+            helper.public_key,
+            recovering_validator_keypair,
+            rng,
+        ))
+    }
+
+    /// Domain points and keys of the given validators
+    fn domain_and_key_map_for(
+        &self,
+        share_indices: &[u32],
+    ) -> Result<DomainAndKeyMap<E>> {
+        let mut domain_and_key_map = self.domain_and_key_map()?;
+        domain_and_key_map
+            .retain(|share_index, _| share_indices.contains(share_index));
+        Ok(domain_and_key_map)
+    }
+
+    /// Fails on unknown or duplicated share indices
+    fn check_share_indices(&self, share_indices: &[u32]) -> Result<()> {
+        let mut seen = HashSet::new();
+        for share_index in share_indices {
+            if !self.validators.contains_key(share_index) {
+                return Err(Error::InvalidShareIndex(*share_index));
+            }
+            if !seen.insert(share_index) {
+                return Err(Error::DuplicatedShareIndex(*share_index));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails on unknown or duplicated handover slots, and on incoming
+    /// validators that appear twice or are already in the cohort
+    pub fn check_handover_batch(
+        &self,
+        handovers: impl IntoIterator<Item = (u32, PublicKey<E>)>,
+    ) -> Result<()> {
+        let (share_indices, incoming_pubkeys): (Vec<_>, Vec<_>) =
+            handovers.into_iter().unzip();
+        self.check_share_indices(&share_indices)?;
+        let mut seen = self
+            .validators
+            .values()
+            .map(|validator| validator.public_key)
+            .collect::<Vec<_>>();
+        for (share_index, incoming_pubkey) in
+            share_indices.into_iter().zip(incoming_pubkeys)
+        {
+            if seen.contains(&incoming_pubkey) {
+                return Err(Error::DuplicatedValidatorPublicKey(share_index));
+            }
+            seen.push(incoming_pubkey);
+        }
+        Ok(())
+    }
+
+    // Returns the handover transcripts of several slots at once, e.g. when an
+    // operator migrates several nodes
+    pub fn generate_handover_transcripts<R: RngCore>(
+        &self,
+        aggregate: &AggregatedTranscript<E>,
+        handovers: &[(u32, &ferveo_common::Keypair<E>)],
+        rng: &mut R,
+    ) -> Result<Vec<refresh::HandoverTranscript<E>>> {
+        self.check_handover_batch(handovers.iter().map(
+            |(share_index, keypair)| (*share_index, keypair.public_key()),
+        ))?;
+        handovers
+            .iter()
+            .map(|(share_index, keypair)| {
+                self.generate_handover_transcript(
+                    aggregate,
+                    *share_index,
+                    keypair,
+                    rng,
+                )
+            })
+            .collect()
+    }
+
     // Returns a handover transcript between an incoming and a departing validator
     pub fn generate_handover_transcript<R: RngCore>(
         &self,
@@ -271,7 +420,7 @@ mod test_dkg_init {
     use crate::{
         dkg::{PubliclyVerifiableDkg, Validator},
         test_common::*,
-        DkgParams,
+        DkgParams, Error,
     };
 
     /// Test that dkg fails to start if the `me` input
@@ -294,14 +443,43 @@ mod test_dkg_init {
         .unwrap_err();
         assert_eq!(err.to_string(), "Expected validator to be a part of the DKG validator set: 0x0000000000000000000000000000000000000005")
     }
+
+    /// Test that a share index outside of the evaluation domain is reported
+    /// as an error instead of panicking
+    #[test]
+    fn test_dkg_share_index_outside_domain() {
+        let rng = &mut ark_std::test_rng();
+        let keypairs = gen_keypairs(SHARES_NUM);
+        let mut validators = gen_validators(&keypairs);
+        let bad_index = SHARES_NUM + 5;
+        validators.last_mut().unwrap().share_index = bad_index;
+        let dkg = PubliclyVerifiableDkg::<E>::new(
+            &validators,
+            &DkgParams::new(TAU, SECURITY_THRESHOLD, SHARES_NUM).unwrap(),
+            &validators[0],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            dkg.domain_and_key_map(),
+            Err(Error::InvalidShareIndex(index)) if index == bad_index
+        ));
+        assert!(matches!(
+            dkg.generate_refresh_transcript(rng),
+            Err(Error::InvalidShareIndex(index)) if index == bad_index
+        ));
+    }
 }
 
 /// Test the dealing phase of the DKG
 #[cfg(test)]
 mod test_dealing {
+    use ark_ec::AffineRepr;
+    use ferveo_common::{FromBytes, ToBytes};
 
     use crate::{
-        test_common::*, DkgParams, Error, PubliclyVerifiableDkg, Validator,
+        test_common::*, DkgParams, Error, PubliclyVerifiableDkg,
+        PubliclyVerifiableSS, Validator,
     };
 
     /// Check that the canonical share indices of validators are expected and enforced
@@ -426,6 +604,46 @@ mod test_dealing {
 
         assert!(dkg.verify_transcripts(&messages).is_ok());
     }
+
+    /// Test that malformed pvss transcripts are rejected, both when decoding
+    /// and when verifying them
+    #[test]
+    fn test_malformed_pvss_rejected() {
+        let rng = &mut ark_std::test_rng();
+        let (dkg, _) = setup_dkg(0);
+        let messages = make_messages(rng, &dkg);
+
+        // One commitment too many for the threshold
+        let mut bad_messages = messages.clone();
+        let extra_coeff = bad_messages[0].1.coeffs[0];
+        bad_messages[0].1.coeffs.push(extra_coeff);
+        assert!(matches!(
+            dkg.verify_transcripts(&bad_messages),
+            Err(Error::InvalidTranscriptSize(_, _))
+        ));
+
+        // One share too few for the validators
+        let mut bad_messages = messages.clone();
+        bad_messages[0].1.shares.pop();
+        assert!(matches!(
+            dkg.verify_transcripts(&bad_messages),
+            Err(Error::InvalidTranscriptSize(_, _))
+        ));
+
+        // A zero secret passes the proof of knowledge with identity points
+        let mut bad_messages = messages.clone();
+        bad_messages[0].1.coeffs[0] = G1::zero();
+        bad_messages[0].1.sigma = G2::zero();
+        assert!(bad_messages[0].1.verify_optimistic());
+        assert!(matches!(
+            dkg.verify_transcripts(&bad_messages),
+            Err(Error::IdentityPointInTranscript)
+        ));
+
+        // Such a transcript doesn't decode either
+        let bytes = bad_messages[0].1.to_bytes().unwrap();
+        assert!(PubliclyVerifiableSS::<E>::from_bytes(&bytes).is_err());
+    }
 }
 
 /// Test aggregating transcripts into final key
@@ -14,6 +14,7 @@ pub mod dkg;
 pub mod primitives;
 pub mod pvss;
 pub mod refresh;
+pub mod reshare;
 pub mod validator;
 
 #[cfg(test)]
@@ -23,6 +24,7 @@ pub use dkg::*;
 pub use primitives::*;
 pub use pvss::*;
 pub use refresh::*;
+pub use reshare::*;
 pub use validator::*;
 
 #[derive(Debug, thiserror::Error)]
@@ -107,6 +109,75 @@ pub enum Error {
     /// Received a duplicated transcript from a validator
     #[error("Received a duplicated transcript from validator: {0}")]
     DuplicateTranscript(EthereumAddress),
+
+    /// Refreshed validators must be able to reach the threshold on their own
+    #[error(
+        "Insufficient validators to refresh. Expected at least: {0}, got: {1}"
+    )]
+    InsufficientRefreshTargets(u32, u32),
+
+    /// The share missed the latest refresh round of the aggregate
+    #[error("Share {0} missed the latest refresh round")]
+    StaleShare(u32),
+
+    /// Recovering a share requires at least `threshold` helpers
+    #[error("Insufficient helpers to recover a share. Expected at least: {0}, got: {1}")]
+    InsufficientRecoveryHelpers(u32, u32),
+
+    /// A recovery share doesn't match the commitments of the aggregate
+    #[error("Invalid recovery share: {0}")]
+    InvalidRecoveryShare(u32),
+
+    /// Resharing requires transcripts from at least `threshold` validators
+    #[error(
+        "Insufficient dealers to reshare. Expected at least: {0}, got: {1}"
+    )]
+    InsufficientResharingDealers(u32, u32),
+
+    /// A resharing transcript doesn't match the commitments of the aggregate
+    #[error("Invalid resharing transcript from dealer: {0}")]
+    InvalidResharingTranscript(u32),
+
+    /// A handover result doesn't match the commitments of the aggregate
+    #[error("Invalid handover result for share index: {0}")]
+    InvalidHandoverResult(u32),
+
+    /// The incoming validator of a handover is already in the cohort, or
+    /// appears twice in a batch
+    #[error("Duplicated validator public key for share index: {0}")]
+    DuplicatedValidatorPublicKey(u32),
+
+    /// A key rotation doesn't match the share it rotates
+    #[error("Invalid key rotation for share index: {0}")]
+    InvalidKeyRotation(u32),
+
+    /// Decryption shares come from aggregates of different share generations
+    #[error("Decryption shares from different share generations: {0} and {1}")]
+    ShareGenerationMismatch(u32, u32),
+
+    /// The share generation counter of an aggregate can't move forward
+    #[error("Share generation overflow")]
+    ShareGenerationOverflow,
+
+    /// A transcript must commit to at least one polynomial coefficient
+    #[error("Transcript has no polynomial commitments")]
+    EmptyTranscript,
+
+    /// Transcripts to aggregate must have the same number of coefficients and shares
+    #[error("Invalid transcript size. Expected: {0}, got: {1}")]
+    InvalidTranscriptSize(u32, u32),
+
+    /// Commitments, shares and proofs in a transcript can't be the identity
+    #[error("Transcript contains an identity point")]
+    IdentityPointInTranscript,
+
+    /// Aggregated transcript was encoded in an unknown format
+    #[error("Unsupported aggregated transcript format version: {0}")]
+    UnsupportedTranscriptFormat(u16),
+
+    /// The scalar field has no evaluation domain for this number of shares
+    #[error("No evaluation domain of size {0}")]
+    InvalidEvaluationDomainSize(usize),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -115,13 +186,14 @@ pub type Result<T> = std::result::Result<T, Error>;
 mod test_dkg_full {
     use std::collections::HashMap;
 
-    use ark_bls12_381::{Bls12_381 as E, Fr, G1Affine};
     use ark_ec::{AffineRepr, CurveGroup};
     use ark_ff::{UniformRand, Zero};
     use ark_std::test_rng;
     use ferveo_common::Keypair;
     use ferveo_tdec::{
-        self, DecryptionSharePrecomputed, DecryptionShareSimple, SecretBox,
+        self,
+        api::{Fr, G1Affine, E},
+        DecryptionSharePrecomputed, DecryptionShareSimple, SecretBox,
         ShareCommitment, SharedSecret,
     };
     use itertools::{izip, Itertools};
@@ -665,8 +737,25 @@ mod test_dkg_full {
             .refresh(&update_transcripts, &validator_map)
             .unwrap();
 
-        // TODO: Assert new aggregate is different than original, including coefficients
         assert_ne!(local_aggregate.aggregate, new_aggregate);
+        assert_ne!(local_aggregate.aggregate.coeffs, new_aggregate.coeffs);
+        // The public key is preserved, and the refreshed commitments match
+        // the refreshed shares
+        assert_eq!(
+            local_aggregate.aggregate.coeffs[0],
+            new_aggregate.coeffs[0]
+        );
+        assert!(new_aggregate.verify_full(&dkg).unwrap());
+
+        // Share updates must match the update polynomial commitments
+        let mut tampered_transcripts = update_transcripts.clone();
+        let tampered = tampered_transcripts.values_mut().next().unwrap();
+        let update = tampered.updates.values_mut().next().unwrap();
+        update.commitment = (update.commitment + G1Affine::generator()).into();
+        assert!(local_aggregate
+            .aggregate
+            .refresh(&tampered_transcripts, &validator_map)
+            .is_err());
 
         // TODO: Show that all participants obtain the same new aggregate transcript.
 
@@ -1,4 +1,8 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::FftField;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+
+use crate::{Error, Result};
 
 // pub fn batch_to_projective<A: ark_ec::AffineCurve>(
 //     p: &[A],
@@ -15,3 +19,32 @@ pub fn batch_to_projective_g1<E: Pairing>(p: &[E::G1Affine]) -> Vec<E::G1> {
 pub fn batch_to_projective_g2<E: Pairing>(p: &[E::G2Affine]) -> Vec<E::G2> {
     p.iter().map(|a| a.into_group()).collect::<Vec<_>>()
 }
+
+/// Evaluation domain for `size` shares. Fails if the scalar field has no
+/// domain that large, e.g. for the share count of a malformed transcript.
+pub fn evaluation_domain<F: FftField>(
+    size: usize,
+) -> Result<GeneralEvaluationDomain<F>> {
+    GeneralEvaluationDomain::<F>::new(size)
+        .ok_or(Error::InvalidEvaluationDomainSize(size))
+}
+
+#[cfg(test)]
+mod test_primitives {
+    use ferveo_tdec::api::Fr;
+
+    use super::*;
+
+    #[test]
+    fn test_evaluation_domain() {
+        assert_eq!(evaluation_domain::<Fr>(4).unwrap().size(), 4);
+        assert_eq!(evaluation_domain::<Fr>(5).unwrap().size(), 8);
+
+        // Beyond the largest mixed-radix subgroup of the scalar field
+        let too_large = 1 << 40;
+        assert!(matches!(
+            evaluation_domain::<Fr>(too_large),
+            Err(Error::InvalidEvaluationDomainSize(size)) if size == too_large
+        ));
+    }
+}
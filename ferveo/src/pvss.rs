@@ -1,4 +1,9 @@
-use std::{collections::HashMap, hash::Hash, marker::PhantomData, ops::Mul};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Mul,
+};
 
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
 use ark_ff::Zero;
@@ -6,22 +11,30 @@ use ark_poly::{
     polynomial::univariate::DensePolynomial, DenseUVPolynomial,
     EvaluationDomain, Polynomial,
 };
-use ferveo_common::{serialization, Keypair, PublicKey};
+use ark_serialize::CanonicalSerialize;
+use ferveo_common::{serialization, FromBytes, Keypair, PublicKey};
 use ferveo_tdec::{
-    BlindedKeyShare, CiphertextHeader, DecryptionSharePrecomputed,
-    DecryptionShareSimple, DomainPoint, ShareCommitment,
+    lagrange_coeff_at_zero, BlindedDecryptionSharePrecomputed,
+    BlindedDecryptionShareSimple, BlindedKeyShare, CiphertextHeader,
+    DecryptionService, DecryptionSharePrecomputed, DecryptionShareProof,
+    DecryptionShareSimple, DkgPublicKey, DomainPoint, ElGamalCiphertext,
+    ElGamalDecryptionShare, HashToG2, ReEncryptionShare, RequesterPublicKey,
+    ShareCommitment, VerifiedCiphertextHeader, VerifiedElGamalTally,
 };
 use itertools::Itertools;
 use rand::RngCore;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
+use sha2::{Digest, Sha256};
 use subproductdomain::fast_multiexp;
 use zeroize::{self, Zeroize, ZeroizeOnDrop};
 
 use crate::{
-    assert_no_share_duplicates, batch_to_projective_g1, batch_to_projective_g2,
-    Error, HandoverTranscript, PubliclyVerifiableDkg, Result,
-    UpdatableBlindedKeyShare, UpdateTranscript, Validator,
+    aggregate_resharing_transcripts, assert_no_share_duplicates,
+    batch_to_projective_g1, batch_to_projective_g2, evaluation_domain, Error,
+    HandoverResult, HandoverTranscript, KeyRotation, PubliclyVerifiableDkg,
+    RecoveryShare, ResharingTranscript, Result, UpdatableBlindedKeyShare,
+    UpdateTranscript, Validator,
 };
 
 /// Marker struct for unaggregated PVSS transcripts
@@ -78,15 +91,15 @@ impl<E: Pairing> ZeroizeOnDrop for SecretPolynomial<E> {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct PubliclyVerifiableSS<E: Pairing, T = Unaggregated> {
     /// Used in Feldman commitment to the VSS polynomial, F_i = g^{a_i}, where a_i are poly coefficients
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentityVec")]
     pub coeffs: Vec<E::G1Affine>,
 
     /// The blinded shares to be dealt to each validator, Y_i
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentityVec")]
     pub shares: Vec<E::G2Affine>,
 
     /// Proof of Knowledge
-    #[serde_as(as = "serialization::SerdeAs")]
+    #[serde_as(as = "serialization::NonIdentity")]
     pub sigma: E::G2Affine,
 
     /// Marker struct to distinguish between aggregated and
@@ -103,7 +116,7 @@ impl<E: Pairing> Hash for PubliclyVerifiableSS<E> {
     }
 }
 
-impl<E: Pairing, T> PubliclyVerifiableSS<E, T> {
+impl<E: HashToG2, T> PubliclyVerifiableSS<E, T> {
     /// Create a new PVSS instance
     /// `s`: the secret constant coefficient to share
     /// `dkg`: the current DKG session
@@ -135,15 +148,18 @@ impl<E: Pairing, T> PubliclyVerifiableSS<E, T> {
             .validators
             .values()
             .map(|validator| {
+                let eval = evals
+                    .get(validator.share_index as usize)
+                    .ok_or(Error::InvalidShareIndex(validator.share_index))?;
                 // ek_{i}^{eval_i}, i = validator index
                 // TODO: Replace with regular, single-element exponentiation - #195
-                fast_multiexp(
+                Ok(fast_multiexp(
                     // &evals.evals[i..i] = &evals.evals[i]
-                    &[evals[validator.share_index as usize]], // one share per validator
+                    &[*eval], // one share per validator
                     validator.public_key.encryption_key.into_group(),
-                )[0]
+                )[0])
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>>>()?;
         if shares.len() != dkg.validators.len() {
             return Err(Error::InsufficientValidators(
                 shares.len() as u32,
@@ -163,14 +179,36 @@ impl<E: Pairing, T> PubliclyVerifiableSS<E, T> {
         Ok(vss)
     }
 
+    /// Check the shape of a transcript received from another party: one
+    /// commitment per coefficient of a polynomial of degree `threshold - 1`,
+    /// one share per validator, and no identity points
+    pub fn check_structure(
+        &self,
+        security_threshold: u32,
+        validators_num: u32,
+    ) -> Result<()> {
+        check_transcript_size(self.coeffs.len(), security_threshold as usize)?;
+        check_transcript_size(self.shares.len(), validators_num as usize)?;
+        if self.sigma.is_zero()
+            || self.coeffs.iter().any(|coeff| coeff.is_zero())
+            || self.shares.iter().any(|share| share.is_zero())
+        {
+            return Err(Error::IdentityPointInTranscript);
+        }
+        Ok(())
+    }
+
     /// Verify the pvss transcript from a validator. This is not the full check,
     /// i.e. we optimistically do not check the commitment. This is deferred
     /// until the aggregation step
     pub fn verify_optimistic(&self) -> bool {
+        let Some(f_0) = self.coeffs.first() else {
+            return false;
+        };
         // We're only checking the proof of knowledge here, sigma ?= h^s
         // "Does the first coefficient of the secret polynomial match the proof of knowledge?"
         E::pairing(
-            self.coeffs[0].into_group(), // F_0 = g^s
+            f_0.into_group(), // F_0 = g^s
             E::G2::generator(),
         ) == E::pairing(
             E::G1::generator(),
@@ -278,8 +316,9 @@ pub fn do_verify_aggregation<E: Pairing>(
     // Now, we verify that the aggregated PVSS transcript is a valid aggregation
     let y = pvss
         .iter()
-        .fold(E::G1::zero(), |acc, pvss| acc + pvss.coeffs[0].into_group());
-    if y.into_affine() == pvss_agg_coefficients[0] {
+        .map(|pvss| pvss.coeffs.first().ok_or(Error::EmptyTranscript))
+        .sum::<Result<E::G1>>()?;
+    if Some(&y.into_affine()) == pvss_agg_coefficients.first() {
         Ok(true)
     } else {
         Err(Error::InvalidTranscriptAggregate)
@@ -287,7 +326,22 @@ pub fn do_verify_aggregation<E: Pairing>(
 }
 
 /// Extra methods available to aggregated PVSS transcripts
-impl<E: Pairing, T: Aggregate> PubliclyVerifiableSS<E, T> {
+impl<E: HashToG2, T: Aggregate> PubliclyVerifiableSS<E, T> {
+    /// Aggregate made by other means than aggregating dealt transcripts,
+    /// e.g. by resharing
+    pub(crate) fn new_aggregate(
+        coeffs: Vec<E::G1Affine>,
+        shares: Vec<E::G2Affine>,
+        sigma: E::G2Affine,
+    ) -> Self {
+        Self {
+            coeffs,
+            shares,
+            sigma,
+            phantom: Default::default(),
+        }
+    }
+
     /// Verify that this PVSS instance is a valid aggregation of
     /// the PVSS instances, produced by [`aggregate`],
     /// and received by the DKG context `dkg`.
@@ -315,10 +369,10 @@ impl<E: Pairing, T: Aggregate> PubliclyVerifiableSS<E, T> {
         let blinded_key_share = self
             .shares
             .get(share_index as usize)
-            .ok_or(Error::InvalidShareIndex(share_index));
+            .ok_or(Error::InvalidShareIndex(share_index))?;
         let blinded_key_share = BlindedKeyShare {
             validator_public_key: public_key.encryption_key,
-            blinded_key_share: *blinded_key_share.unwrap(),
+            blinded_key_share: *blinded_key_share,
         };
         Ok(blinded_key_share)
     }
@@ -341,18 +395,16 @@ impl<E: Pairing, T: Aggregate> PubliclyVerifiableSS<E, T> {
         validator_keypair: &Keypair<E>,
         share_index: u32,
     ) -> Result<DecryptionShareSimple<E>> {
-        let decryption_share = self
+        Ok(self
             .get_share_for_index_and_pubkey(
                 share_index,
                 &validator_keypair.public_key(),
-            )
-            .unwrap()
+            )?
             .create_decryption_share_simple(
                 ciphertext_header,
                 aad,
                 validator_keypair,
-            );
-        Ok(decryption_share.unwrap())
+            )?)
     }
 
     /// Make a decryption share (precomputed variant) for a given ciphertext
@@ -364,68 +416,422 @@ impl<E: Pairing, T: Aggregate> PubliclyVerifiableSS<E, T> {
         share_index: u32,
         domain_points: &HashMap<u32, DomainPoint<E>>,
     ) -> Result<DecryptionSharePrecomputed<E>> {
-        let share = self
+        let share = self.get_share_for_index_and_pubkey(
+            share_index,
+            &validator_keypair.public_key(),
+        )?;
+        Ok(share.create_decryption_share_precomputed(
+            ciphertext_header,
+            aad,
+            validator_keypair,
+            share_index,
+            domain_points,
+        )?)
+    }
+
+    /// Make a decryption share (simple variant) for an already checked
+    /// ciphertext header
+    pub fn create_decryption_share_simple_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+    ) -> Result<DecryptionShareSimple<E>> {
+        Ok(self
             .get_share_for_index_and_pubkey(
                 share_index,
                 &validator_keypair.public_key(),
-            )
-            .unwrap();
-        Ok(share
-            .create_decryption_share_precomputed(
+            )?
+            .create_decryption_share_simple_verified(
                 ciphertext_header,
-                aad,
                 validator_keypair,
+            )?)
+    }
+
+    /// Make a decryption share (precomputed variant) for an already checked
+    /// ciphertext header
+    pub fn create_decryption_share_precomputed_verified(
+        &self,
+        ciphertext_header: &VerifiedCiphertextHeader<E>,
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        domain_points: &HashMap<u32, DomainPoint<E>>,
+    ) -> Result<DecryptionSharePrecomputed<E>> {
+        let share = self.get_share_for_index_and_pubkey(
+            share_index,
+            &validator_keypair.public_key(),
+        )?;
+        Ok(share.create_decryption_share_precomputed_verified(
+            ciphertext_header,
+            validator_keypair,
+            share_index,
+            domain_points,
+        )?)
+    }
+
+    /// Make a long-lived decryption context for the validator holding `share_index`
+    pub fn create_decryption_service(
+        &self,
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        domain_points: HashMap<u32, DomainPoint<E>>,
+        lagrange_cache_capacity: usize,
+    ) -> Result<DecryptionService<E>> {
+        let share = self.get_share_for_index_and_pubkey(
+            share_index,
+            &validator_keypair.public_key(),
+        )?;
+        Ok(DecryptionService::new(
+            share_index,
+            &share,
+            validator_keypair,
+            domain_points,
+            lagrange_cache_capacity,
+        )?)
+    }
+
+    /// Make a decryption share (simple variant) that only the holder of the
+    /// requester keypair for `requester_public_key` can combine
+    pub fn create_blinded_decryption_share_simple<R: RngCore>(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        requester_public_key: &RequesterPublicKey<E>,
+        rng: &mut R,
+    ) -> Result<BlindedDecryptionShareSimple<E>> {
+        let private_key_share = self
+            .get_share_for_index_and_pubkey(
                 share_index,
-                domain_points,
-            )
-            .unwrap())
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        Ok(BlindedDecryptionShareSimple::create(
+            ciphertext_header,
+            aad,
+            &private_key_share,
+            &self.get_share_commitment(share_index)?,
+            requester_public_key,
+            rng,
+        )?)
+    }
+
+    /// Make a decryption share (precomputed variant) that only the holder of
+    /// the requester keypair for `requester_public_key` can combine
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_blinded_decryption_share_precomputed<R: RngCore>(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        domain_points: &HashMap<u32, DomainPoint<E>>,
+        requester_public_key: &RequesterPublicKey<E>,
+        rng: &mut R,
+    ) -> Result<BlindedDecryptionSharePrecomputed<E>> {
+        let private_key_share = self
+            .get_share_for_index_and_pubkey(
+                share_index,
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        Ok(BlindedDecryptionSharePrecomputed::create(
+            share_index as usize,
+            ciphertext_header,
+            aad,
+            &private_key_share,
+            &self.get_share_commitment(share_index)?,
+            &lagrange_coeff_at_zero::<E>(share_index, domain_points)
+                .ok_or(Error::InvalidShareIndex(share_index))?,
+            requester_public_key,
+            rng,
+        )?)
+    }
+
+    /// Verify a blinded decryption share made by the validator holding `share_index`
+    pub fn verify_blinded_decryption_share_simple(
+        &self,
+        share: &BlindedDecryptionShareSimple<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        share_index: u32,
+        requester_public_key: &RequesterPublicKey<E>,
+    ) -> Result<bool> {
+        Ok(share.verify(
+            ciphertext_header,
+            &self.get_share_commitment(share_index)?,
+            requester_public_key,
+        )?)
+    }
+
+    /// Verify a blinded decryption share, made for the validators in `domain_points`
+    pub fn verify_blinded_decryption_share_precomputed(
+        &self,
+        share: &BlindedDecryptionSharePrecomputed<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        domain_points: &HashMap<u32, DomainPoint<E>>,
+        requester_public_key: &RequesterPublicKey<E>,
+    ) -> Result<bool> {
+        let share_index = share.decrypter_index as u32;
+        Ok(share.verify(
+            ciphertext_header,
+            &self.get_share_commitment(share_index)?,
+            &lagrange_coeff_at_zero::<E>(share_index, domain_points)
+                .ok_or(Error::InvalidShareIndex(share_index))?,
+            requester_public_key,
+        )?)
+    }
+
+    /// Make a decryption share of a verified tally of ElGamal ballots, with a
+    /// proof against the share commitment for `share_index`
+    pub fn create_elgamal_decryption_share<R: RngCore>(
+        &self,
+        tally: &VerifiedElGamalTally<E>,
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        rng: &mut R,
+    ) -> Result<ElGamalDecryptionShare<E>> {
+        let private_key_share = self
+            .get_share_for_index_and_pubkey(
+                share_index,
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        Ok(ElGamalDecryptionShare::create(
+            tally,
+            &private_key_share,
+            &self.get_share_commitment(share_index)?,
+            rng,
+        )?)
+    }
+
+    pub fn verify_elgamal_decryption_share(
+        &self,
+        share: &ElGamalDecryptionShare<E>,
+        ciphertext: &ElGamalCiphertext<E>,
+        share_index: u32,
+    ) -> Result<bool> {
+        Ok(share
+            .verify(ciphertext, &self.get_share_commitment(share_index)?)?)
+    }
+
+    /// Prove that a decryption share (simple variant) made by this validator
+    /// matches the share commitment for `share_index`
+    pub fn create_decryption_share_proof_simple<R: RngCore>(
+        &self,
+        share: &DecryptionShareSimple<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        rng: &mut R,
+    ) -> Result<DecryptionShareProof<E>> {
+        let private_key_share = self
+            .get_share_for_index_and_pubkey(
+                share_index,
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        Ok(share.create_proof(
+            &private_key_share,
+            &self.get_share_commitment(share_index)?,
+            ciphertext_header,
+            rng,
+        )?)
+    }
+
+    /// Prove that a decryption share (precomputed variant) made by this
+    /// validator matches its share commitment
+    pub fn create_decryption_share_proof_precomputed<R: RngCore>(
+        &self,
+        share: &DecryptionSharePrecomputed<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        validator_keypair: &Keypair<E>,
+        domain_points: &HashMap<u32, DomainPoint<E>>,
+        rng: &mut R,
+    ) -> Result<DecryptionShareProof<E>> {
+        let share_index = share.decrypter_index as u32;
+        let private_key_share = self
+            .get_share_for_index_and_pubkey(
+                share_index,
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        Ok(share.create_proof(
+            &private_key_share,
+            &self.get_share_commitment(share_index)?,
+            ciphertext_header,
+            &lagrange_coeff_at_zero::<E>(share_index, domain_points)
+                .ok_or(Error::InvalidShareIndex(share_index))?,
+            rng,
+        )?)
+    }
+
+    /// Verify a decryption share (simple variant) against the share
+    /// commitment for `share_index`
+    pub fn verify_decryption_share_proof_simple(
+        &self,
+        share: &DecryptionShareSimple<E>,
+        proof: &DecryptionShareProof<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        share_index: u32,
+    ) -> Result<bool> {
+        Ok(share.verify_proof(
+            proof,
+            &self.get_share_commitment(share_index)?,
+            ciphertext_header,
+        )?)
+    }
+
+    /// Verify a decryption share (precomputed variant) against the share
+    /// commitment of its decrypter
+    pub fn verify_decryption_share_proof_precomputed(
+        &self,
+        share: &DecryptionSharePrecomputed<E>,
+        proof: &DecryptionShareProof<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        domain_points: &HashMap<u32, DomainPoint<E>>,
+    ) -> Result<bool> {
+        let share_index = share.decrypter_index as u32;
+        Ok(share.verify_proof(
+            proof,
+            &self.get_share_commitment(share_index)?,
+            ciphertext_header,
+            &lagrange_coeff_at_zero::<E>(share_index, domain_points)
+                .ok_or(Error::InvalidShareIndex(share_index))?,
+        )?)
     }
 
+    /// Share commitment A_i of the validator holding `share_index`
+    pub fn get_share_commitment(
+        &self,
+        share_index: u32,
+    ) -> Result<ShareCommitment<E>> {
+        // The evaluation domain may be larger than the number of shares
+        if share_index as usize >= self.shares.len() {
+            return Err(Error::InvalidShareIndex(share_index));
+        }
+        let domain = evaluation_domain::<E::ScalarField>(self.shares.len())?;
+        let share_commitments = get_share_commitments_from_poly_commitments::<E>(
+            &self.coeffs,
+            &domain,
+        );
+        share_commitments
+            .get(share_index as usize)
+            .map(|a_i| ShareCommitment(a_i.into_affine()))
+            .ok_or(Error::InvalidShareIndex(share_index))
+    }
+
+    /// Make a share of the re-encryption of a ciphertext to the ritual
+    /// with the public key `target_public_key`
+    pub fn create_reencryption_share<R: RngCore>(
+        &self,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        validator_keypair: &Keypair<E>,
+        share_index: u32,
+        target_public_key: &DkgPublicKey<E>,
+        rng: &mut R,
+    ) -> Result<ReEncryptionShare<E>> {
+        let private_key_share = self
+            .get_share_for_index_and_pubkey(
+                share_index,
+                &validator_keypair.public_key(),
+            )?
+            .unblind(validator_keypair)?;
+        let share_commitment = self.get_share_commitment(share_index)?;
+        Ok(ReEncryptionShare::create(
+            ciphertext_header,
+            aad,
+            &private_key_share,
+            &share_commitment,
+            target_public_key,
+            rng,
+        )?)
+    }
+
+    /// Verify a re-encryption share made by the validator holding `share_index`
+    pub fn verify_reencryption_share(
+        &self,
+        share: &ReEncryptionShare<E>,
+        ciphertext_header: &CiphertextHeader<E>,
+        aad: &[u8],
+        share_index: u32,
+        target_public_key: &DkgPublicKey<E>,
+    ) -> Result<bool> {
+        let share_commitment = self.get_share_commitment(share_index)?;
+        Ok(share.verify(
+            ciphertext_header,
+            aad,
+            &share_commitment,
+            target_public_key,
+        )?)
+    }
+
+    /// Refresh the shares of the validators in `validator_keys_map` with the
+    /// update transcripts dealt to them. Shares of other validators are left
+    /// as they are, and can't be combined with the refreshed ones anymore.
     pub fn refresh(
         &self,
         update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
         validator_keys_map: &HashMap<u32, PublicKey<E>>,
     ) -> Result<Self> {
         let num_shares = self.shares.len();
-        let fft_domain =
-            ark_poly::GeneralEvaluationDomain::<E::ScalarField>::new(
-                num_shares,
-            )
-            .unwrap();
+        let fft_domain = evaluation_domain::<E::ScalarField>(num_shares)?;
+
+        // The refreshed validators must be able to reach the threshold on their own
+        let threshold = self.coeffs.len() as u32;
+        if (validator_keys_map.len() as u32) < threshold {
+            return Err(Error::InsufficientRefreshTargets(
+                threshold,
+                validator_keys_map.len() as u32,
+            ));
+        }
 
         // First, verify that all update transcript are valid
         // TODO: Consider what to do with failed verifications - #176
         // TODO: Find a better way to ensure they're always validated - #176
         for update_transcript in update_transcripts.values() {
+            // Update polynomials must have the same degree as the shared one
+            if update_transcript.coeffs.len() != self.coeffs.len() {
+                return Err(Error::InvalidShareUpdate);
+            }
+            // And updates must be dealt to exactly the refreshed validators
+            if update_transcript.updates.len() != validator_keys_map.len() {
+                return Err(Error::InvalidShareUpdate);
+            }
             update_transcript
-                .verify_refresh(validator_keys_map, &fft_domain)
-                .unwrap();
-        }
-
-        // Participants refresh their shares with the updates from each other:
-        // TODO: Here we're just iterating over all current shares,
-        //       implicitly assuming all of them will be refreshed.
-        //       Generalize to allow refreshing just a subset of the shares. - #199
-        let mut indices: Vec<u32> =
-            validator_keys_map.keys().copied().collect::<Vec<u32>>();
-        indices.sort();
-        let updated_blinded_shares: Vec<E::G2Affine> = indices
-            .iter()
-            .map(|&index| {
-                let pubkey = validator_keys_map
-                    .get(&index)
-                    .ok_or(Error::InvalidShareIndex(index))
-                    .unwrap();
-                let blinded_key_share =
-                    self.get_share_for_index_and_pubkey(index, pubkey).unwrap();
-                let updated_share = UpdatableBlindedKeyShare(blinded_key_share)
-                    .apply_share_updates(update_transcripts, index);
-                updated_share.0.blinded_key_share
-            })
-            .collect();
+                .verify_refresh(validator_keys_map, &fft_domain)?;
+        }
+
+        // Participants refresh their shares with the updates from each other
+        let mut updated_blinded_shares = self.shares.clone();
+        for (&index, pubkey) in validator_keys_map {
+            let blinded_key_share =
+                self.get_share_for_index_and_pubkey(index, pubkey)?;
+            let domain_point = self.share_domain_point(index)?;
+            let updated_share = UpdatableBlindedKeyShare(blinded_key_share)
+                .apply_share_updates(
+                    update_transcripts,
+                    index,
+                    &domain_point,
+                )?;
+            updated_blinded_shares[index as usize] =
+                updated_share.0.blinded_key_share;
+        }
+
+        // The refreshed polynomial is the sum of the shared polynomial and
+        // the update polynomials, so we sum their commitments too
+        let mut updated_coeffs = batch_to_projective_g1::<E>(&self.coeffs);
+        for update_transcript in update_transcripts.values() {
+            for (coeff, update_coeff) in
+                updated_coeffs.iter_mut().zip(&update_transcript.coeffs)
+            {
+                *coeff += update_coeff;
+            }
+        }
 
         let refreshed_aggregate_transcript = Self {
-            coeffs: self.coeffs.clone(), // FIXME: coeffs need to be updated too - #200
+            coeffs: E::G1::normalize_batch(&updated_coeffs),
             shares: updated_blinded_shares,
             sigma: self.sigma,
             phantom: Default::default(),
@@ -433,86 +839,571 @@ impl<E: Pairing, T: Aggregate> PubliclyVerifiableSS<E, T> {
         Ok(refreshed_aggregate_transcript)
     }
 
-    pub fn finalize_handover(
+    /// Domain point of the share at `share_index`
+    fn share_domain_point(&self, share_index: u32) -> Result<DomainPoint<E>> {
+        if share_index as usize >= self.shares.len() {
+            return Err(Error::InvalidShareIndex(share_index));
+        }
+        let domain = evaluation_domain::<E::ScalarField>(self.shares.len())?;
+        domain
+            .elements()
+            .nth(share_index as usize)
+            .ok_or(Error::InvalidShareIndex(share_index))
+    }
+
+    /// Verify the recovery transcripts dealt by the helpers in `helper_keys_map`
+    /// to each other, for the recovery of the share at `recovery_index`
+    pub fn verify_recovery_transcripts(
         &self,
-        handover_transcript: &HandoverTranscript<E>,
-        validator_keypair: &Keypair<E>,
-    ) -> Result<Self> {
-        let num_shares = self.shares.len();
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_keys_map: &HashMap<u32, PublicKey<E>>,
+        recovery_index: u32,
+    ) -> Result<()> {
+        let threshold = self.coeffs.len() as u32;
+        if (helper_keys_map.len() as u32) < threshold {
+            return Err(Error::InsufficientRecoveryHelpers(
+                threshold,
+                helper_keys_map.len() as u32,
+            ));
+        }
+        if helper_keys_map.contains_key(&recovery_index) {
+            return Err(Error::InvalidShareIndex(recovery_index));
+        }
+        // Every helper must mask its share, otherwise the recovering validator
+        // learns the unmasked shares of the helpers
+        if update_transcripts.len() != helper_keys_map.len()
+            || update_transcripts
+                .keys()
+                .any(|dealer| !helper_keys_map.contains_key(dealer))
+        {
+            return Err(Error::InvalidShareUpdate);
+        }
+
         let fft_domain =
-            ark_poly::GeneralEvaluationDomain::<E::ScalarField>::new(
-                num_shares,
-            )
-            .unwrap();
-        let share_commitments = get_share_commitments_from_poly_commitments::<E>(
-            &self.coeffs,
-            &fft_domain,
-        );
+            evaluation_domain::<E::ScalarField>(self.shares.len())?;
+        let recovery_point = self.share_domain_point(recovery_index)?;
+        for update_transcript in update_transcripts.values() {
+            if update_transcript.coeffs.len() != self.coeffs.len()
+                || update_transcript.updates.len() != helper_keys_map.len()
+            {
+                return Err(Error::InvalidShareUpdate);
+            }
+            update_transcript.verify_recovery(
+                helper_keys_map,
+                &fft_domain,
+                recovery_point,
+            )?;
+        }
+        Ok(())
+    }
 
-        // TODO: Check transcript and handover consistency in the DKG level:
-        // - share index corresponds to validator
-        // - validator PKs are correct
-        // - share index is in range
-        let share_index = handover_transcript.share_index as usize;
-        if share_index >= num_shares {
-            return Err(Error::InvalidShareIndex(
-                handover_transcript.share_index,
-            ));
+    /// Commitment to a helper's share masked by the recovery updates
+    pub fn get_recovery_share_commitment(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_index: u32,
+    ) -> Result<ShareCommitment<E>> {
+        let mut commitment =
+            self.get_share_commitment(helper_index)?.0.into_group();
+        for update_transcript in update_transcripts.values() {
+            let update = update_transcript
+                .updates
+                .get(&helper_index)
+                .ok_or(Error::InvalidShareIndex(helper_index))?;
+            commitment += update.commitment;
         }
-        if handover_transcript.outgoing_pubkey != validator_keypair.public_key()
+        Ok(ShareCommitment(commitment.into_affine()))
+    }
+
+    /// A helper's blinded share masked by the recovery updates. The recovering
+    /// validator builds its handover transcript to the helper from this share.
+    pub fn get_recovery_blinded_share(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_index: u32,
+        helper_public_key: &PublicKey<E>,
+    ) -> Result<BlindedKeyShare<E>> {
+        let blinded_key_share = self
+            .get_share_for_index_and_pubkey(helper_index, helper_public_key)?;
+        let updated_share = UpdatableBlindedKeyShare(blinded_key_share)
+            .apply_share_updates(
+                update_transcripts,
+                helper_index,
+                &self.share_domain_point(helper_index)?,
+            )?;
+        Ok(updated_share.0)
+    }
+
+    /// Make a helper's recovery share, by finalizing the handover transcript
+    /// of the recovering validator to the helper's masked share
+    pub fn create_recovery_share(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_keys_map: &HashMap<u32, PublicKey<E>>,
+        recovery_index: u32,
+        handover_transcript: &HandoverTranscript<E>,
+        helper_keypair: &Keypair<E>,
+    ) -> Result<RecoveryShare<E>> {
+        self.verify_recovery_transcripts(
+            update_transcripts,
+            helper_keys_map,
+            recovery_index,
+        )?;
+        let helper_index = handover_transcript.share_index;
+        let helper_public_key = helper_keypair.public_key();
+        if handover_transcript.outgoing_pubkey != helper_public_key
+            || helper_keys_map.get(&helper_index) != Some(&helper_public_key)
         {
             return Err(Error::ValidatorPublicKeyMismatch);
         }
+        // Fails unless the transcript is for the masked share
+        let share_commitment = self
+            .get_recovery_share_commitment(update_transcripts, helper_index)?;
+        let blinded_key_share =
+            handover_transcript.finalize(helper_keypair, share_commitment)?;
+        Ok(RecoveryShare {
+            helper_index,
+            blinded_key_share: blinded_key_share.blinded_key_share,
+            recovering_pubkey: handover_transcript.incoming_pubkey,
+        })
+    }
 
-        let share_commitment = ShareCommitment::<E>(
-            share_commitments
-                .get(share_index)
-                .ok_or(Error::InvalidShareIndex(share_index as u32))
-                .unwrap()
-                .into_affine(),
-        );
-        let new_blind_share = handover_transcript
-            .finalize(validator_keypair, share_commitment)
-            .unwrap();
+    /// Combine the recovery shares of the helpers into the share at
+    /// `recovery_index`, blinded to the recovering validator. The recovered
+    /// share is checked against the commitments of the aggregate.
+    pub fn recover_share(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_keys_map: &HashMap<u32, PublicKey<E>>,
+        recovery_index: u32,
+        recovery_shares: &[RecoveryShare<E>],
+    ) -> Result<Self> {
+        self.verify_recovery_transcripts(
+            update_transcripts,
+            helper_keys_map,
+            recovery_index,
+        )?;
+        let threshold = self.coeffs.len();
+        let recovering_pubkey = recovery_shares
+            .first()
+            .map(|share| share.recovering_pubkey)
+            .ok_or(Error::InsufficientRecoveryHelpers(threshold as u32, 0))?;
+
+        let mut domain_points = Vec::with_capacity(recovery_shares.len());
+        let mut blinded_shares = Vec::with_capacity(recovery_shares.len());
+        for share in recovery_shares.iter().unique_by(|s| s.helper_index) {
+            let helper_index = share.helper_index;
+            if !helper_keys_map.contains_key(&helper_index) {
+                return Err(Error::InvalidShareIndex(helper_index));
+            }
+            if share.recovering_pubkey != recovering_pubkey {
+                return Err(Error::ValidatorPublicKeyMismatch);
+            }
+            // e(G, Y'_j) = e(A'_j, ek_r), as for any other blinded share
+            let share_commitment = self.get_recovery_share_commitment(
+                update_transcripts,
+                helper_index,
+            )?;
+            if E::pairing(E::G1::generator(), share.blinded_key_share)
+                != E::pairing(
+                    share_commitment.0,
+                    recovering_pubkey.encryption_key,
+                )
+            {
+                return Err(Error::InvalidRecoveryShare(helper_index));
+            }
+            domain_points.push(self.share_domain_point(helper_index)?);
+            blinded_shares.push(share.blinded_key_share);
+        }
+        if domain_points.len() < threshold {
+            return Err(Error::InsufficientRecoveryHelpers(
+                threshold as u32,
+                domain_points.len() as u32,
+            ));
+        }
 
-        let mut original_shares = self.shares.clone();
-        let new_shares = original_shares.as_mut_slice();
-        new_shares[share_index] = new_blind_share.blinded_key_share;
+        // Interpolate the masked shares at the recovery point, where the
+        // recovery updates vanish
+        let recovery_point = self.share_domain_point(recovery_index)?;
+        let domain = evaluation_domain::<E::ScalarField>(self.shares.len())?;
+        let lagrange = ferveo_tdec::lagrange_basis_at_fft_domain::<E>(
+            &domain,
+            &domain_points,
+            &recovery_point,
+        )
+        .ok_or(Error::InvalidShareIndex(recovery_index))?;
+        let recovered_share = blinded_shares
+            .iter()
+            .zip(lagrange)
+            .fold(E::G2::zero(), |acc, (share, lambda)| {
+                acc + share.mul(lambda)
+            })
+            .into_affine();
 
-        // Check that the new encrypted share is valid for the new validator
-        verify_validator_share(
+        let mut shares = self.shares.clone();
+        shares[recovery_index as usize] = recovered_share;
+        let share_commitments = get_share_commitments_from_poly_commitments::<E>(
+            &self.coeffs,
+            &domain,
+        );
+        if !verify_validator_share(
             &share_commitments,
-            new_shares,
-            share_index,
-            handover_transcript.incoming_pubkey,
+            &shares,
+            recovery_index as usize,
+            recovering_pubkey,
+        )? {
+            return Err(Error::InvalidRecoveryShare(recovery_index));
+        }
+
+        Ok(Self {
+            coeffs: self.coeffs.clone(),
+            shares,
+            sigma: self.sigma,
+            phantom: Default::default(),
+        })
+    }
+
+    pub fn finalize_handover(
+        &self,
+        handover_transcript: &HandoverTranscript<E>,
+        validator_keypair: &Keypair<E>,
+    ) -> Result<Self> {
+        self.finalize_handovers(&[(handover_transcript, validator_keypair)])
+    }
+
+    /// Finalize the handovers of several slots at once. Share commitments are
+    /// derived once for the whole batch, and each slot may appear only once.
+    pub fn finalize_handovers(
+        &self,
+        handovers: &[(&HandoverTranscript<E>, &Keypair<E>)],
+    ) -> Result<Self> {
+        self.check_handover_slots(
+            handovers
+                .iter()
+                .map(|(transcript, _)| transcript.share_index),
         )?;
+        let share_commitments = self.get_share_commitments()?;
+
+        let mut shares = self.shares.clone();
+        for (handover_transcript, validator_keypair) in handovers {
+            if handover_transcript.outgoing_pubkey
+                != validator_keypair.public_key()
+            {
+                return Err(Error::ValidatorPublicKeyMismatch);
+            }
+            let share_index = handover_transcript.share_index as usize;
+            let share_commitment = ShareCommitment::<E>(
+                share_commitments[share_index].into_affine(),
+            );
+            let new_blind_share = handover_transcript
+                .finalize(validator_keypair, share_commitment)?;
+            shares[share_index] = new_blind_share.blinded_key_share;
+
+            // Check that the new encrypted share is valid for the new validator
+            if !verify_validator_share(
+                &share_commitments,
+                &shares,
+                share_index,
+                handover_transcript.incoming_pubkey,
+            )? {
+                return Err(Error::InvalidShareUpdate);
+            }
+        }
 
-        let aggregrate_post_handover = Self {
+        Ok(Self {
             coeffs: self.coeffs.clone(),
-            shares: new_shares.to_vec(),
+            shares,
             sigma: self.sigma,
             phantom: Default::default(),
-        };
-        Ok(aggregrate_post_handover)
+        })
+    }
+
+    /// Check a handover result against the commitments of this aggregate and
+    /// the blinded share currently held at its share index
+    pub fn verify_handover_result(
+        &self,
+        handover_result: &HandoverResult<E>,
+    ) -> Result<bool> {
+        let share_index = handover_result.share_index();
+        let share_commitment = self.get_share_commitment(share_index)?;
+        self.do_verify_handover_result(handover_result, share_commitment)
+    }
+
+    fn do_verify_handover_result(
+        &self,
+        handover_result: &HandoverResult<E>,
+        share_commitment: ShareCommitment<E>,
+    ) -> Result<bool> {
+        // The outgoing validator must hold the share being handed over
+        let share_index = handover_result.share_index();
+        let outgoing_share = self
+            .shares
+            .get(share_index as usize)
+            .ok_or(Error::InvalidShareIndex(share_index))?;
+        let is_outgoing_share = verify_validator_share(
+            &[share_commitment.0.into_group()],
+            &[*outgoing_share],
+            0,
+            handover_result.handover_transcript.outgoing_pubkey,
+        )?;
+        Ok(is_outgoing_share && handover_result.verify(share_commitment)?)
+    }
+
+    /// Apply a handover result published by the departing validator
+    pub fn apply_handover_result(
+        &self,
+        handover_result: &HandoverResult<E>,
+    ) -> Result<Self> {
+        self.apply_handover_results(std::slice::from_ref(handover_result))
+    }
+
+    /// Apply the handover results of several slots at once, verifying them in
+    /// a single pass over the share commitments
+    pub fn apply_handover_results(
+        &self,
+        handover_results: &[HandoverResult<E>],
+    ) -> Result<Self> {
+        self.check_handover_slots(
+            handover_results.iter().map(HandoverResult::share_index),
+        )?;
+        let share_commitments = self.get_share_commitments()?;
+
+        let mut shares = self.shares.clone();
+        for handover_result in handover_results {
+            let share_index = handover_result.share_index();
+            let share_commitment = ShareCommitment::<E>(
+                share_commitments[share_index as usize].into_affine(),
+            );
+            if !self
+                .do_verify_handover_result(handover_result, share_commitment)?
+            {
+                return Err(Error::InvalidHandoverResult(share_index));
+            }
+            shares[share_index as usize] = handover_result.blinded_key_share;
+        }
+        Ok(Self {
+            coeffs: self.coeffs.clone(),
+            shares,
+            sigma: self.sigma,
+            phantom: Default::default(),
+        })
+    }
+
+    /// Check a key rotation against the commitments of this aggregate and
+    /// the blinded share currently held at its share index
+    pub fn verify_key_rotation(
+        &self,
+        key_rotation: &KeyRotation<E>,
+    ) -> Result<bool> {
+        let share_index = key_rotation.share_index;
+        let share_commitment = self.get_share_commitment(share_index)?;
+        let share = self
+            .shares
+            .get(share_index as usize)
+            .ok_or(Error::InvalidShareIndex(share_index))?;
+        key_rotation.verify(share, share_commitment)
+    }
+
+    /// Apply a validator's rotation of its own key
+    pub fn apply_key_rotation(
+        &self,
+        key_rotation: &KeyRotation<E>,
+    ) -> Result<Self> {
+        let share_index = key_rotation.share_index;
+        if !self.verify_key_rotation(key_rotation)? {
+            return Err(Error::InvalidKeyRotation(share_index));
+        }
+        let mut shares = self.shares.clone();
+        shares[share_index as usize] = key_rotation.blinded_key_share;
+        Ok(Self {
+            coeffs: self.coeffs.clone(),
+            shares,
+            sigma: self.sigma,
+            phantom: Default::default(),
+        })
+    }
+
+    fn get_share_commitments(&self) -> Result<Vec<E::G1>> {
+        let domain = evaluation_domain::<E::ScalarField>(self.shares.len())?;
+        Ok(get_share_commitments_from_poly_commitments::<E>(
+            &self.coeffs,
+            &domain,
+        ))
+    }
+
+    /// Fails on out of range or duplicated handover slots
+    fn check_handover_slots(
+        &self,
+        share_indices: impl Iterator<Item = u32>,
+    ) -> Result<()> {
+        let mut seen = HashSet::new();
+        for share_index in share_indices {
+            if share_index as usize >= self.shares.len() {
+                return Err(Error::InvalidShareIndex(share_index));
+            }
+            if !seen.insert(share_index) {
+                return Err(Error::DuplicatedShareIndex(share_index));
+            }
+        }
+        Ok(())
     }
 }
 
+const SHARE_GENERATION_DST: &[u8] = b"FERVEO_SHARE_GENERATION_V1";
+
+/// The generation of the key shares of an aggregate. It moves forward every
+/// time the shares change, and its lineage hash chains over every state the
+/// aggregate went through, so that aggregates with the same number but a
+/// different history don't match either.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ShareGeneration {
+    pub number: u32,
+    pub lineage: [u8; 32],
+}
+
+impl ShareGeneration {
+    fn genesis<E: Pairing>(
+        aggregate: &PubliclyVerifiableSS<E, Aggregated>,
+    ) -> Result<Self> {
+        Self::chain(0, &[0; 32], aggregate)
+    }
+
+    /// The generation of `aggregate`, updated from this one
+    fn next<E: Pairing>(
+        &self,
+        aggregate: &PubliclyVerifiableSS<E, Aggregated>,
+    ) -> Result<Self> {
+        let number = self
+            .number
+            .checked_add(1)
+            .ok_or(Error::ShareGenerationOverflow)?;
+        Self::chain(number, &self.lineage, aggregate)
+    }
+
+    fn chain<E: Pairing>(
+        number: u32,
+        parent_lineage: &[u8; 32],
+        aggregate: &PubliclyVerifiableSS<E, Aggregated>,
+    ) -> Result<Self> {
+        let mut hash_input = SHARE_GENERATION_DST.to_vec();
+        hash_input.extend_from_slice(parent_lineage);
+        hash_input.extend_from_slice(&number.to_be_bytes());
+        for coeff in &aggregate.coeffs {
+            coeff.serialize_compressed(&mut hash_input)?;
+        }
+        for share in &aggregate.shares {
+            share.serialize_compressed(&mut hash_input)?;
+        }
+        Ok(Self {
+            number,
+            lineage: Sha256::digest(&hash_input).into(),
+        })
+    }
+}
+
+/// Leading field of the versioned encoding of an aggregate. Unversioned
+/// encodings start with the length of the serialized coefficients instead,
+/// which can never take this value.
+const AGGREGATE_FORMAT_MARKER: u64 = u64::MAX;
+
+/// Version of the encoding of an aggregate, bumped on every layout change
+const AGGREGATE_FORMAT_VERSION: u16 = 1;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(
+    into = "VersionedAggregatedTranscript<E>",
+    try_from = "VersionedAggregatedTranscript<E>",
+    bound(
+        serialize = "PubliclyVerifiableSS<E, Aggregated>: Serialize, \
+                     ferveo_tdec::DkgPublicKey<E>: Serialize",
+        deserialize = "PubliclyVerifiableSS<E, Aggregated>: DeserializeOwned, \
+                       ferveo_tdec::DkgPublicKey<E>: DeserializeOwned"
+    )
+)]
 pub struct AggregatedTranscript<E: Pairing> {
+    pub aggregate: PubliclyVerifiableSS<E, Aggregated>,
+    pub public_key: ferveo_tdec::DkgPublicKey<E>,
+    /// Number of refresh rounds applied to the aggregate
+    pub refresh_round: u32,
+    /// The last refresh round each share took part in. Shares that missed
+    /// the latest round are stale, and can't be combined with refreshed ones.
+    pub share_rounds: Vec<u32>,
+    /// Generation of the key shares, updated on every change to them
+    pub generation: ShareGeneration,
+}
+
+/// Encoding of [AggregatedTranscript], tagged with its format version
+#[derive(Serialize, Deserialize)]
+struct VersionedAggregatedTranscript<E: Pairing> {
+    marker: u64,
+    version: u16,
     #[serde(bound(
         serialize = "PubliclyVerifiableSS<E, Aggregated>: Serialize",
         deserialize = "PubliclyVerifiableSS<E, Aggregated>: DeserializeOwned"
     ))]
-    pub aggregate: PubliclyVerifiableSS<E, Aggregated>,
+    aggregate: PubliclyVerifiableSS<E, Aggregated>,
     #[serde(bound(
         serialize = "ferveo_tdec::DkgPublicKey<E>: Serialize",
         deserialize = "ferveo_tdec::DkgPublicKey<E>: DeserializeOwned"
     ))]
-    pub public_key: ferveo_tdec::DkgPublicKey<E>,
+    public_key: ferveo_tdec::DkgPublicKey<E>,
+    refresh_round: u32,
+    share_rounds: Vec<u32>,
+    generation: ShareGeneration,
+}
+
+/// Encoding of an aggregate from before refresh rounds and share generations
+#[derive(Deserialize)]
+struct UnversionedAggregatedTranscript<E: Pairing> {
+    #[serde(bound(
+        deserialize = "PubliclyVerifiableSS<E, Aggregated>: DeserializeOwned"
+    ))]
+    aggregate: PubliclyVerifiableSS<E, Aggregated>,
+    #[serde(bound(
+        deserialize = "ferveo_tdec::DkgPublicKey<E>: DeserializeOwned"
+    ))]
+    public_key: ferveo_tdec::DkgPublicKey<E>,
+}
+
+impl<E: Pairing> From<AggregatedTranscript<E>>
+    for VersionedAggregatedTranscript<E>
+{
+    fn from(transcript: AggregatedTranscript<E>) -> Self {
+        Self {
+            marker: AGGREGATE_FORMAT_MARKER,
+            version: AGGREGATE_FORMAT_VERSION,
+            aggregate: transcript.aggregate,
+            public_key: transcript.public_key,
+            refresh_round: transcript.refresh_round,
+            share_rounds: transcript.share_rounds,
+            generation: transcript.generation,
+        }
+    }
+}
+
+impl<E: Pairing> TryFrom<VersionedAggregatedTranscript<E>>
+    for AggregatedTranscript<E>
+{
+    type Error = Error;
+
+    fn try_from(encoded: VersionedAggregatedTranscript<E>) -> Result<Self> {
+        if encoded.marker != AGGREGATE_FORMAT_MARKER
+            || encoded.version != AGGREGATE_FORMAT_VERSION
+        {
+            return Err(Error::UnsupportedTranscriptFormat(encoded.version));
+        }
+        Ok(Self {
+            aggregate: encoded.aggregate,
+            public_key: encoded.public_key,
+            refresh_round: encoded.refresh_round,
+            share_rounds: encoded.share_rounds,
+            generation: encoded.generation,
+        })
+    }
 }
 
 // TODO: Add tests - #202
-impl<E: Pairing> AggregatedTranscript<E> {
+impl<E: HashToG2> AggregatedTranscript<E> {
     pub fn from_transcripts(
         transcripts: &[PubliclyVerifiableSS<E>],
     ) -> Result<Self> {
@@ -523,12 +1414,294 @@ impl<E: Pairing> AggregatedTranscript<E> {
     pub fn from_aggregate(
         aggregate: PubliclyVerifiableSS<E, Aggregated>,
     ) -> Result<Self> {
-        let public_key = ferveo_tdec::DkgPublicKey::<E>(aggregate.coeffs[0]);
+        let public_key = ferveo_tdec::DkgPublicKey::<E>(
+            *aggregate.coeffs.first().ok_or(Error::EmptyTranscript)?,
+        );
+        let share_rounds = vec![0; aggregate.shares.len()];
+        let generation = ShareGeneration::genesis(&aggregate)?;
         Ok(AggregatedTranscript {
             aggregate,
             public_key,
+            refresh_round: 0,
+            share_rounds,
+            generation,
         })
     }
+
+    /// Decode an aggregate from its bytes, as well as from the unversioned
+    /// encoding that predates refresh rounds. The latter have never been
+    /// refreshed, so they decode to the state of a fresh aggregate.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if let Some(rest) =
+            bytes.strip_prefix(&AGGREGATE_FORMAT_MARKER.to_le_bytes())
+        {
+            let version = rest
+                .get(..2)
+                .and_then(|version| version.try_into().ok())
+                .map(u16::from_le_bytes)
+                .ok_or(Error::InvalidByteLength(10, bytes.len()))?;
+            if version != AGGREGATE_FORMAT_VERSION {
+                return Err(Error::UnsupportedTranscriptFormat(version));
+            }
+            return Ok(<Self as FromBytes>::from_bytes(bytes)?);
+        }
+        let UnversionedAggregatedTranscript {
+            aggregate,
+            public_key,
+        } = FromBytes::from_bytes(bytes)?;
+        let transcript = Self::from_aggregate(aggregate)?;
+        if transcript.public_key != public_key {
+            return Err(Error::InvalidTranscriptAggregate);
+        }
+        Ok(transcript)
+    }
+
+    /// This aggregate with its shares updated to `aggregate`, one generation
+    /// later
+    fn with_next_generation(
+        &self,
+        aggregate: PubliclyVerifiableSS<E, Aggregated>,
+    ) -> Result<Self> {
+        let generation = self.generation.next(&aggregate)?;
+        Ok(Self {
+            aggregate,
+            generation,
+            ..self.clone()
+        })
+    }
+
+    /// Whether the share took part in the latest refresh round
+    pub fn is_share_current(&self, share_index: u32) -> Result<bool> {
+        self.share_rounds
+            .get(share_index as usize)
+            .map(|round| *round == self.refresh_round)
+            .ok_or(Error::InvalidShareIndex(share_index))
+    }
+
+    /// Fails with `Error::StaleShare` if the share missed the latest refresh round
+    pub fn check_share_is_current(&self, share_index: u32) -> Result<()> {
+        if !self.is_share_current(share_index)? {
+            return Err(Error::StaleShare(share_index));
+        }
+        Ok(())
+    }
+
+    /// Run a refresh round for the validators in `validator_keys_map`
+    pub fn refresh(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        validator_keys_map: &HashMap<u32, PublicKey<E>>,
+    ) -> Result<Self> {
+        let aggregate = self
+            .aggregate
+            .refresh(update_transcripts, validator_keys_map)?;
+        let refresh_round = self.refresh_round + 1;
+        let mut share_rounds = self.share_rounds.clone();
+        for &share_index in validator_keys_map.keys() {
+            *share_rounds
+                .get_mut(share_index as usize)
+                .ok_or(Error::InvalidShareIndex(share_index))? = refresh_round;
+        }
+        Ok(Self {
+            refresh_round,
+            share_rounds,
+            ..self.with_next_generation(aggregate)?
+        })
+    }
+
+    /// Deal this validator's key share to the new committee of `new_dkg`.
+    /// Dealers must hold current shares.
+    pub fn create_resharing_transcript<R: RngCore>(
+        &self,
+        dealer_index: u32,
+        dealer_keypair: &Keypair<E>,
+        handover_transcripts: &HashMap<u32, HandoverTranscript<E>>,
+        new_dkg: &PubliclyVerifiableDkg<E>,
+        rng: &mut R,
+    ) -> Result<ResharingTranscript<E>> {
+        self.check_share_is_current(dealer_index)?;
+        ResharingTranscript::new(
+            dealer_index,
+            dealer_keypair,
+            self.aggregate.get_share_commitment(dealer_index)?,
+            handover_transcripts,
+            new_dkg,
+            rng,
+        )
+    }
+
+    /// Aggregate for the new committee of `new_dkg`, sharing the same secret
+    pub fn reshare(
+        &self,
+        new_dkg: &PubliclyVerifiableDkg<E>,
+        transcripts: &[ResharingTranscript<E>],
+    ) -> Result<Self> {
+        for transcript in transcripts {
+            self.check_share_is_current(transcript.dealer_index)?;
+        }
+        let aggregate = aggregate_resharing_transcripts(
+            &self.aggregate,
+            new_dkg,
+            transcripts,
+        )?;
+        // The new committee carries on the lineage of the old one
+        let generation = self.generation.next(&aggregate)?;
+        Ok(Self {
+            generation,
+            ..Self::from_aggregate(aggregate)?
+        })
+    }
+
+    /// Make a helper's recovery share. Helpers must hold current shares.
+    pub fn create_recovery_share(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_keys_map: &HashMap<u32, PublicKey<E>>,
+        recovery_index: u32,
+        handover_transcript: &HandoverTranscript<E>,
+        helper_keypair: &Keypair<E>,
+    ) -> Result<RecoveryShare<E>> {
+        self.check_share_is_current(handover_transcript.share_index)?;
+        self.aggregate.create_recovery_share(
+            update_transcripts,
+            helper_keys_map,
+            recovery_index,
+            handover_transcript,
+            helper_keypair,
+        )
+    }
+
+    /// Recover the share at `recovery_index` from the recovery shares of
+    /// current helpers. The recovered share is current too.
+    pub fn recover_share(
+        &self,
+        update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
+        helper_keys_map: &HashMap<u32, PublicKey<E>>,
+        recovery_index: u32,
+        recovery_shares: &[RecoveryShare<E>],
+    ) -> Result<Self> {
+        for &helper_index in helper_keys_map.keys() {
+            self.check_share_is_current(helper_index)?;
+        }
+        let aggregate = self.aggregate.recover_share(
+            update_transcripts,
+            helper_keys_map,
+            recovery_index,
+            recovery_shares,
+        )?;
+        let mut share_rounds = self.share_rounds.clone();
+        *share_rounds
+            .get_mut(recovery_index as usize)
+            .ok_or(Error::InvalidShareIndex(recovery_index))? =
+            self.refresh_round;
+        Ok(Self {
+            share_rounds,
+            ..self.with_next_generation(aggregate)?
+        })
+    }
+
+    pub fn finalize_handover(
+        &self,
+        handover_transcript: &HandoverTranscript<E>,
+        validator_keypair: &Keypair<E>,
+    ) -> Result<Self> {
+        let aggregate = self
+            .aggregate
+            .finalize_handover(handover_transcript, validator_keypair)?;
+        self.with_next_generation(aggregate)
+    }
+
+    /// Finalize a handover of this validator's share as a result that anyone
+    /// can verify and apply with `apply_handover_result`
+    pub fn create_handover_result<R: RngCore>(
+        &self,
+        handover_transcript: &HandoverTranscript<E>,
+        validator_keypair: &Keypair<E>,
+        rng: &mut R,
+    ) -> Result<HandoverResult<E>> {
+        let share_commitment = self
+            .aggregate
+            .get_share_commitment(handover_transcript.share_index)?;
+        handover_transcript.finalize_with_proof(
+            validator_keypair,
+            share_commitment,
+            rng,
+        )
+    }
+
+    pub fn apply_handover_result(
+        &self,
+        handover_result: &HandoverResult<E>,
+    ) -> Result<Self> {
+        let aggregate =
+            self.aggregate.apply_handover_result(handover_result)?;
+        self.with_next_generation(aggregate)
+    }
+
+    /// Rotate this validator's key to `new_keypair`, keeping its share index
+    pub fn create_key_rotation<R: RngCore>(
+        &self,
+        share_index: u32,
+        validator_keypair: &Keypair<E>,
+        new_keypair: &Keypair<E>,
+        rng: &mut R,
+    ) -> Result<KeyRotation<E>> {
+        let blinded_share = self.aggregate.get_share_for_index_and_pubkey(
+            share_index,
+            &validator_keypair.public_key(),
+        )?;
+        let key_rotation = KeyRotation::new(
+            share_index,
+            &blinded_share,
+            validator_keypair,
+            new_keypair,
+            rng,
+        )?;
+        // The share at `share_index` must be held by `validator_keypair`
+        if !self.aggregate.verify_key_rotation(&key_rotation)? {
+            return Err(Error::ValidatorPublicKeyMismatch);
+        }
+        Ok(key_rotation)
+    }
+
+    pub fn apply_key_rotation(
+        &self,
+        key_rotation: &KeyRotation<E>,
+    ) -> Result<Self> {
+        let aggregate = self.aggregate.apply_key_rotation(key_rotation)?;
+        self.with_next_generation(aggregate)
+    }
+
+    /// Finalize the handovers of several slots of the cohort of `dkg` at once
+    pub fn finalize_handovers(
+        &self,
+        dkg: &PubliclyVerifiableDkg<E>,
+        handovers: &[(&HandoverTranscript<E>, &Keypair<E>)],
+    ) -> Result<Self> {
+        dkg.check_handover_batch(handovers.iter().map(|(transcript, _)| {
+            (transcript.share_index, transcript.incoming_pubkey)
+        }))?;
+        let aggregate = self.aggregate.finalize_handovers(handovers)?;
+        self.with_next_generation(aggregate)
+    }
+
+    /// Apply the handover results of several slots of the cohort of `dkg` at
+    /// once
+    pub fn apply_handover_results(
+        &self,
+        dkg: &PubliclyVerifiableDkg<E>,
+        handover_results: &[HandoverResult<E>],
+    ) -> Result<Self> {
+        dkg.check_handover_batch(handover_results.iter().map(|result| {
+            (
+                result.share_index(),
+                result.handover_transcript.incoming_pubkey,
+            )
+        }))?;
+        let aggregate =
+            self.aggregate.apply_handover_results(handover_results)?;
+        self.with_next_generation(aggregate)
+    }
 }
 
 /// Aggregate the PVSS instances in `pvss` from DKG session `dkg`
@@ -539,6 +1712,13 @@ fn aggregate<E: Pairing>(
 ) -> Result<PubliclyVerifiableSS<E, Aggregated>> {
     let mut pvss_iter = transcripts.iter();
     let first_pvss = pvss_iter.next().ok_or(Error::NoTranscriptsToAggregate)?;
+    if first_pvss.coeffs.is_empty() {
+        return Err(Error::EmptyTranscript);
+    }
+    for pvss in transcripts {
+        check_transcript_size(pvss.coeffs.len(), first_pvss.coeffs.len())?;
+        check_transcript_size(pvss.shares.len(), first_pvss.shares.len())?;
+    }
     let mut coeffs = batch_to_projective_g1::<E>(&first_pvss.coeffs);
     let mut sigma = first_pvss.sigma;
 
@@ -568,15 +1748,23 @@ fn aggregate<E: Pairing>(
     })
 }
 
+fn check_transcript_size(size: usize, expected: usize) -> Result<()> {
+    if size != expected {
+        return Err(Error::InvalidTranscriptSize(expected as u32, size as u32));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test_pvss {
-    use ark_bls12_381::Bls12_381 as EllipticCurve;
     use ark_ec::AffineRepr;
     use ark_ff::UniformRand;
     use test_case::test_case;
 
+    use ferveo_common::ToBytes;
+
     use super::*;
-    use crate::test_common::*;
+    use crate::test_common::{E as EllipticCurve, *};
 
     /// Test that an aggregate message will fail to verify if the
     /// security threshold is not met
@@ -704,6 +1892,48 @@ mod test_pvss {
             .expect("Test failed"));
     }
 
+    /// Check that malformed transcripts are rejected with an error
+    #[test]
+    fn test_aggregate_rejects_malformed_transcripts() {
+        // With 6 validators, the evaluation domain has 8 points
+        let (_, _, messages) = setup_dealt_dkg_with_n_validators(
+            SECURITY_THRESHOLD,
+            SHARES_NUM,
+            VALIDATORS_NUM,
+        );
+        let pvss_list =
+            messages.iter().map(|(_, pvss)| pvss).cloned().collect_vec();
+        let shares_num = pvss_list[0].shares.len() as u32;
+
+        let mut bad_pvss_list = pvss_list.clone();
+        bad_pvss_list[1].shares.pop();
+        assert!(matches!(
+            aggregate(&bad_pvss_list),
+            Err(Error::InvalidTranscriptSize(expected, actual))
+                if expected == shares_num && actual == shares_num - 1
+        ));
+
+        let mut bad_pvss_list = pvss_list.clone();
+        bad_pvss_list[0].coeffs.clear();
+        assert!(!bad_pvss_list[0].verify_optimistic());
+        assert!(matches!(
+            aggregate(&bad_pvss_list),
+            Err(Error::EmptyTranscript)
+        ));
+
+        // A share index within the evaluation domain, but past the last share
+        let mut aggregated = aggregate(&pvss_list).unwrap();
+        assert!(matches!(
+            aggregated.get_share_commitment(shares_num),
+            Err(Error::InvalidShareIndex(index)) if index == shares_num
+        ));
+        aggregated.coeffs.clear();
+        assert!(matches!(
+            AggregatedTranscript::from_aggregate(aggregated),
+            Err(Error::EmptyTranscript)
+        ));
+    }
+
     /// Check that if the aggregated PVSS transcript has an
     /// incorrect constant term, the verification fails
     #[test]
@@ -725,4 +1955,48 @@ mod test_pvss {
             "Transcript aggregate doesn't match the received PVSS instances"
         )
     }
+
+    /// Aggregates encoded before refresh rounds and share generations were
+    /// tracked still decode, to the state of a fresh aggregate
+    #[test]
+    fn test_aggregate_unversioned_encoding() {
+        let bytes = hex::decode(
+            include_str!("../test-data/aggregated_transcript_unversioned.hex")
+                .split_whitespace()
+                .collect::<String>(),
+        )
+        .unwrap();
+        let transcript =
+            AggregatedTranscript::<ark_bls12_381::Bls12_381>::from_bytes(
+                &bytes,
+            )
+            .unwrap();
+        assert_eq!(transcript.refresh_round, 0);
+        assert_eq!(transcript.share_rounds, vec![0; 4]);
+        assert_eq!(
+            transcript.generation,
+            ShareGeneration::genesis(&transcript.aggregate).unwrap()
+        );
+        assert_eq!(transcript.public_key.0, transcript.aggregate.coeffs[0]);
+
+        // It's encoded again in the versioned format
+        let encoded = transcript.to_bytes().unwrap();
+        assert!(encoded.starts_with(&AGGREGATE_FORMAT_MARKER.to_le_bytes()));
+        assert_eq!(
+            AggregatedTranscript::from_bytes(&encoded).unwrap(),
+            transcript
+        );
+
+        // Future versions are rejected rather than misread
+        let mut future = encoded.clone();
+        future[8..10]
+            .copy_from_slice(&(AGGREGATE_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            AggregatedTranscript::<ark_bls12_381::Bls12_381>::from_bytes(
+                &future
+            ),
+            Err(Error::UnsupportedTranscriptFormat(version))
+                if version == AGGREGATE_FORMAT_VERSION + 1
+        ));
+    }
 }
@@ -1,21 +1,26 @@
-use std::{collections::HashMap, ops::Mul};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Mul,
+};
 
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
-use ark_ff::{Field, Zero};
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ff::{Field, PrimeField, Zero};
 use ark_poly::{
     univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain,
     Polynomial,
 };
+use ark_serialize::CanonicalSerialize;
 use ark_std::{One, UniformRand};
 use ferveo_common::{serialization, Keypair, PublicKey};
 use ferveo_tdec::{
     prepare_combine_simple, BlindedKeyShare, CiphertextHeader,
-    DecryptionSharePrecomputed, DecryptionShareSimple, DomainPoint,
+    DecryptionSharePrecomputed, DecryptionShareSimple, DomainPoint, HashToG2,
     ShareCommitment,
 };
 use rand_core::RngCore;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
+use sha2::{digest::Digest, Sha256};
 use subproductdomain::fast_multiexp;
 use zeroize::ZeroizeOnDrop;
 
@@ -23,21 +28,20 @@ use crate::{batch_to_projective_g1, Error, Result};
 
 type InnerBlindedKeyShare<E> = ferveo_tdec::BlindedKeyShare<E>;
 
+const REBLINDING_PROOF_DST: &[u8] = b"FERVEO_HANDOVER_REBLINDING_PROOF_V1";
+const KEY_ROTATION_PROOF_DST: &[u8] = b"FERVEO_KEY_ROTATION_PROOF_V1";
+
 /// Blinded key share held by a participant in the DKG protocol
-// TODO: What about the commented macros?
-#[derive(
-    Debug,
-    Clone, //PartialEq, Eq, ZeroizeOnDrop, Serialize, Deserialize,
-)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UpdatableBlindedKeyShare<E: Pairing>(
-    // #[serde(bound(
-    //     serialize = "ferveo_tdec::PrivateKeyShare<E>: Serialize",
-    //     deserialize = "ferveo_tdec::PrivateKeyShare<E>: DeserializeOwned"
-    // ))]
+    #[serde(bound(
+        serialize = "InnerBlindedKeyShare<E>: Serialize",
+        deserialize = "InnerBlindedKeyShare<E>: DeserializeOwned"
+    ))]
     pub InnerBlindedKeyShare<E>,
 );
 
-impl<E: Pairing> UpdatableBlindedKeyShare<E> {
+impl<E: HashToG2> UpdatableBlindedKeyShare<E> {
     pub fn new(blinded_key_share: InnerBlindedKeyShare<E>) -> Self {
         Self(blinded_key_share)
     }
@@ -47,38 +51,44 @@ impl<E: Pairing> UpdatableBlindedKeyShare<E> {
         &self,
         update_transcripts: &HashMap<u32, UpdateTranscript<E>>,
         index: u32,
-    ) -> Self {
+        domain_point: &DomainPoint<E>,
+    ) -> Result<Self> {
+        let validator_public_key = PublicKey {
+            encryption_key: self.0.validator_public_key,
+        };
         // Current participant receives update transcripts from other participants
-        let share_updates_for_index: Vec<_> = update_transcripts
+        let share_updates_for_index = update_transcripts
             .values()
             .map(|update_transcript_from_producer| {
-                let update_for_participant: ShareUpdate<E> =
-                    update_transcript_from_producer
-                        .updates
-                        .get(&index)
-                        .cloned()
-                        .unwrap();
+                let update_for_participant = update_transcript_from_producer
+                    .updates
+                    .get(&index)
+                    .ok_or(Error::InvalidShareIndex(index))?;
                 // Validate share update against the target validator public key
-                update_for_participant
-                    .verify(&PublicKey {
-                        encryption_key: self.0.validator_public_key,
-                    })
-                    .unwrap();
-                update_for_participant
+                update_for_participant.verify(&validator_public_key)?;
+                // And its commitment against the update polynomial commitments
+                let expected_commitment = evaluate_commitment::<E>(
+                    &update_transcript_from_producer.coeffs,
+                    domain_point,
+                );
+                if expected_commitment.into_affine()
+                    != update_for_participant.commitment
+                {
+                    return Err(Error::InvalidShareUpdate);
+                }
+                Ok(update_for_participant)
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        // TODO: Validate commitments from share update
-        // FIXME: Don't forget!!!!! - #200
         let updated_key_share = share_updates_for_index
             .iter()
             .fold(self.0.blinded_key_share, |acc, delta| {
                 (acc + delta.update).into()
             });
-        Self(BlindedKeyShare {
+        Ok(Self(BlindedKeyShare {
             validator_public_key: self.0.validator_public_key,
             blinded_key_share: updated_key_share,
-        })
+        }))
     }
 
     pub fn create_decryption_share_simple(
@@ -87,15 +97,11 @@ impl<E: Pairing> UpdatableBlindedKeyShare<E> {
         aad: &[u8],
         validator_keypair: &Keypair<E>,
     ) -> Result<DecryptionShareSimple<E>> {
-        let decryption_share = self
-            .0
-            .create_decryption_share_simple(
-                ciphertext_header,
-                aad,
-                validator_keypair,
-            )
-            .unwrap();
-        Ok(decryption_share)
+        Ok(self.0.create_decryption_share_simple(
+            ciphertext_header,
+            aad,
+            validator_keypair,
+        )?)
     }
 
     // TODO: Move to BlindedKeyShare
@@ -143,11 +149,11 @@ impl<E: Pairing> UpdatableBlindedKeyShare<E> {
 
         // Finally, pick the lagrange coefficient for the current share index
         let lagrange_coeff = &lagrange_coeffs[adjusted_share_index];
-        let private_key_share = self.0.unblind(validator_keypair);
+        let private_key_share = self.0.unblind(validator_keypair)?;
         DecryptionSharePrecomputed::create(
             share_index as usize,
             &validator_keypair.decryption_key,
-            &private_key_share.unwrap(),
+            &private_key_share,
             ciphertext_header,
             aad,
             lagrange_coeff,
@@ -188,13 +194,21 @@ impl<E: Pairing> ShareUpdate<E> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// A refresh or recovery transcript. Share updates are ordered by share
+/// index, so that the encoding is deterministic.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UpdateTranscript<E: Pairing> {
     /// Used in Feldman commitment to the update polynomial
+    #[serde_as(as = "serialization::SerdeAs")]
     pub coeffs: Vec<E::G1Affine>,
 
     /// The share updates to be dealt to each validator
-    pub updates: HashMap<u32, ShareUpdate<E>>,
+    #[serde(bound(
+        serialize = "ShareUpdate<E>: Serialize",
+        deserialize = "ShareUpdate<E>: DeserializeOwned"
+    ))]
+    pub updates: BTreeMap<u32, ShareUpdate<E>>,
 }
 
 impl<E: Pairing> UpdateTranscript<E> {
@@ -240,6 +254,9 @@ impl<E: Pairing> UpdateTranscript<E> {
         // TODO: Make sure input validators and transcript validators match
 
         // TODO: Validate that update polynomial commitments have proper length
+        if self.coeffs.is_empty() {
+            return Err(Error::InvalidShareUpdate);
+        }
 
         // Validate consistency between share updates, validator keys and polynomial commitments.
         // Let's first reconstruct the expected update commitments from the polynomial commitments:
@@ -249,39 +266,27 @@ impl<E: Pairing> UpdateTranscript<E> {
 
         for (index, update) in self.updates.iter() {
             // Next, validate share updates against their corresponding target validators
-            update
-                .verify(validator_public_keys.get(index).unwrap())
-                .unwrap();
+            let validator_public_key = validator_public_keys
+                .get(index)
+                .ok_or(Error::InvalidShareIndex(*index))?;
+            update.verify(validator_public_key)?;
 
             // Finally, validate update commitments against update polynomial commitments
             let expected_commitment = reconstructed_commitments
                 .get(*index as usize)
                 .ok_or(Error::InvalidShareIndex(*index))?;
-            assert_eq!(expected_commitment.into_affine(), update.commitment);
-            // TODO: Error handling of everything in this block
+            if expected_commitment.into_affine() != update.commitment {
+                return Err(Error::InvalidShareUpdate);
+            }
         }
 
         // Validate update polynomial commitments C_i are consistent with the type of update
         // * For refresh  (root 0): f(0) = 0  ==>  a_0 = 0  ==>  C_0 = [0]G = 1
         // * For recovery (root z): f(z) = 0  ==>  sum{a_i * z^i} = 0  ==>  [sum{...}]G = 1  ==> sum{[z^i]C_i} = 1
-
-        if root.is_zero() {
-            // Refresh
-            assert!(self.coeffs[0].is_zero());
-            // TODO: Check remaining are not zero? Only if we disallow producing zero coeffs
-        } else {
-            // Recovery
-            // TODO: There's probably a much better way to do this
-            let mut reverse_coeffs = self.coeffs.iter().rev();
-            let mut acc: E::G1Affine = *reverse_coeffs.next().unwrap();
-            for &coeff in reverse_coeffs {
-                let b = acc.mul(root).into_affine();
-                acc = (coeff + b).into();
-            }
-            assert!(acc.is_zero());
+        // TODO: Check remaining are not zero? Only if we disallow producing zero coeffs
+        if !evaluate_commitment::<E>(&self.coeffs, &root).is_zero() {
+            return Err(Error::InvalidShareUpdate);
         }
-
-        // TODO: Handle errors properly
         Ok(true)
     }
 
@@ -402,7 +407,7 @@ impl<E: Pairing> HandoverTranscript<E> {
         departing_validator_keypair: &Keypair<E>,
         share_commitment: ShareCommitment<E>,
     ) -> Result<BlindedKeyShare<E>> {
-        let is_valid = &self.validate(share_commitment).unwrap();
+        let is_valid = self.validate(share_commitment)?;
         if !is_valid {
             return Err(Error::InvalidShareUpdate); // TODO: Make this more specific
         }
@@ -410,7 +415,7 @@ impl<E: Pairing> HandoverTranscript<E> {
             departing_validator_keypair
                 .decryption_key
                 .inverse()
-                .unwrap(),
+                .ok_or(ferveo_tdec::Error::InvalidValidatorDecryptionKey)?,
         );
         Ok(BlindedKeyShare::<E> {
             validator_public_key: self.incoming_pubkey.encryption_key,
@@ -419,6 +424,330 @@ impl<E: Pairing> HandoverTranscript<E> {
     }
 }
 
+/// Chaum-Pedersen proof that the departing validator re-blinded a handover
+/// with its own key, i.e. that `ek_i = [d_i] H` and
+/// `double_blind_share = [d_i] blinded_key_share` for the same `d_i`.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReblindingProof<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub challenge: E::ScalarField,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub response: E::ScalarField,
+}
+
+/// The outcome of a handover, published by the departing validator: the new
+/// blinded share of the incoming validator, with a proof of re-blinding.
+/// Anyone can verify it against the aggregate and apply it to their own copy.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandoverResult<E: Pairing> {
+    #[serde(bound(
+        serialize = "HandoverTranscript<E>: Serialize",
+        deserialize = "HandoverTranscript<E>: DeserializeOwned"
+    ))]
+    pub handover_transcript: HandoverTranscript<E>,
+
+    /// `[s_i] ek_j`, the share blinded to the incoming validator
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub blinded_key_share: E::G2Affine,
+
+    #[serde(bound(
+        serialize = "ReblindingProof<E>: Serialize",
+        deserialize = "ReblindingProof<E>: DeserializeOwned"
+    ))]
+    pub proof: ReblindingProof<E>,
+}
+
+impl<E: Pairing> HandoverTranscript<E> {
+    /// Finalize the handover, proving the re-blinding so that anyone can
+    /// verify and apply the result
+    pub fn finalize_with_proof(
+        &self,
+        departing_validator_keypair: &Keypair<E>,
+        share_commitment: ShareCommitment<E>,
+        rng: &mut impl RngCore,
+    ) -> Result<HandoverResult<E>> {
+        if self.outgoing_pubkey != departing_validator_keypair.public_key() {
+            return Err(Error::ValidatorPublicKeyMismatch);
+        }
+        let blinded_key_share = self
+            .finalize(departing_validator_keypair, share_commitment)?
+            .blinded_key_share;
+
+        let h = E::G2::generator();
+        let blinding = E::ScalarField::rand(rng);
+        let challenge = reblinding_challenge(
+            self,
+            &blinded_key_share,
+            &[h * blinding, blinded_key_share * blinding],
+        )?;
+        let proof = ReblindingProof {
+            challenge,
+            response: blinding
+                + departing_validator_keypair.decryption_key * challenge,
+        };
+        Ok(HandoverResult {
+            handover_transcript: self.clone(),
+            blinded_key_share,
+            proof,
+        })
+    }
+}
+
+impl<E: Pairing> HandoverResult<E> {
+    pub fn share_index(&self) -> u32 {
+        self.handover_transcript.share_index
+    }
+
+    /// Verify the result against the share commitment `A_i` of the handed
+    /// over share
+    pub fn verify(&self, share_commitment: ShareCommitment<E>) -> Result<bool> {
+        let transcript = &self.handover_transcript;
+        if !matches!(transcript.validate(share_commitment), Ok(true)) {
+            return Ok(false);
+        }
+
+        // Recover the prover's commitments as [z] base - [c] image
+        let h = E::G2::generator();
+        let ek_i = transcript.outgoing_pubkey.encryption_key;
+        let expected_challenge = reblinding_challenge(
+            transcript,
+            &self.blinded_key_share,
+            &[
+                h * self.proof.response - ek_i * self.proof.challenge,
+                self.blinded_key_share * self.proof.response
+                    - transcript.double_blind_share * self.proof.challenge,
+            ],
+        )?;
+        if expected_challenge != self.proof.challenge {
+            return Ok(false);
+        }
+
+        // e(G, Y_j) = e(A_i, ek_j), as for the shares of a transcript
+        Ok(E::pairing(E::G1::generator(), self.blinded_key_share)
+            == E::pairing(
+                share_commitment.0,
+                transcript.incoming_pubkey.encryption_key,
+            ))
+    }
+}
+
+fn reblinding_challenge<E: Pairing>(
+    transcript: &HandoverTranscript<E>,
+    blinded_key_share: &E::G2Affine,
+    commitments: &[E::G2],
+) -> Result<E::ScalarField> {
+    let mut hash_input = REBLINDING_PROOF_DST.to_vec();
+    hash_input.extend_from_slice(&transcript.share_index.to_be_bytes());
+    transcript
+        .outgoing_pubkey
+        .encryption_key
+        .serialize_compressed(&mut hash_input)?;
+    transcript
+        .incoming_pubkey
+        .encryption_key
+        .serialize_compressed(&mut hash_input)?;
+    transcript
+        .double_blind_share
+        .serialize_compressed(&mut hash_input)?;
+    blinded_key_share.serialize_compressed(&mut hash_input)?;
+    for commitment in commitments {
+        commitment.serialize_compressed(&mut hash_input)?;
+    }
+    Ok(E::ScalarField::from_be_bytes_mod_order(&Sha256::digest(
+        &hash_input,
+    )))
+}
+
+/// Proof that a key rotation re-blinded the share from the old to the new
+/// validator key, by knowledge of both decryption keys `d` and `d'` such that
+/// `ek = [d] H`, `ek' = [d'] H` and `[d] Y' = [d'] Y`.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotationProof<E: Pairing> {
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub challenge: E::ScalarField,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub old_key_response: E::ScalarField,
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub new_key_response: E::ScalarField,
+}
+
+/// A validator's rotation of its own key, keeping its share index: the share
+/// `Y = [s_i] ek` re-blinded to `Y' = [s_i] ek'`, with a proof of re-blinding.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation<E: Pairing> {
+    pub share_index: u32,
+
+    #[serde(bound(
+        serialize = "ferveo_common::PublicKey<E>: Serialize",
+        deserialize = "ferveo_common::PublicKey<E>: DeserializeOwned"
+    ))]
+    pub old_pubkey: PublicKey<E>,
+
+    #[serde(bound(
+        serialize = "ferveo_common::PublicKey<E>: Serialize",
+        deserialize = "ferveo_common::PublicKey<E>: DeserializeOwned"
+    ))]
+    pub new_pubkey: PublicKey<E>,
+
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub blinded_key_share: E::G2Affine,
+
+    #[serde(bound(
+        serialize = "KeyRotationProof<E>: Serialize",
+        deserialize = "KeyRotationProof<E>: DeserializeOwned"
+    ))]
+    pub proof: KeyRotationProof<E>,
+}
+
+impl<E: Pairing> KeyRotation<E> {
+    /// Re-blind `blinded_share`, held at `share_index` under `old_keypair`, to
+    /// `new_keypair`
+    pub fn new(
+        share_index: u32,
+        blinded_share: &BlindedKeyShare<E>,
+        old_keypair: &Keypair<E>,
+        new_keypair: &Keypair<E>,
+        rng: &mut impl RngCore,
+    ) -> Result<Self> {
+        if blinded_share.validator_public_key
+            != old_keypair.public_key().encryption_key
+        {
+            return Err(Error::ValidatorPublicKeyMismatch);
+        }
+        let old_share = blinded_share.blinded_key_share;
+        let old_key_inv = old_keypair
+            .decryption_key
+            .inverse()
+            .ok_or(ferveo_tdec::Error::InvalidValidatorDecryptionKey)?;
+        // Y' = [d' / d] Y
+        let blinded_key_share = (old_share
+            * (new_keypair.decryption_key * old_key_inv))
+            .into_affine();
+
+        let h = E::G2::generator();
+        let old_key_blinding = E::ScalarField::rand(rng);
+        let new_key_blinding = E::ScalarField::rand(rng);
+        let mut rotation = Self {
+            share_index,
+            old_pubkey: old_keypair.public_key(),
+            new_pubkey: new_keypair.public_key(),
+            blinded_key_share,
+            proof: KeyRotationProof {
+                challenge: E::ScalarField::zero(),
+                old_key_response: old_key_blinding,
+                new_key_response: new_key_blinding,
+            },
+        };
+        let challenge = rotation.challenge(
+            &old_share,
+            &[
+                h * old_key_blinding,
+                h * new_key_blinding,
+                blinded_key_share * old_key_blinding
+                    - old_share * new_key_blinding,
+            ],
+        )?;
+        rotation.proof = KeyRotationProof {
+            challenge,
+            old_key_response: old_key_blinding
+                + old_keypair.decryption_key * challenge,
+            new_key_response: new_key_blinding
+                + new_keypair.decryption_key * challenge,
+        };
+        Ok(rotation)
+    }
+
+    /// Verify the rotation of `old_share`, the share currently held at its
+    /// share index, against the share commitment `A_i`
+    pub fn verify(
+        &self,
+        old_share: &E::G2Affine,
+        share_commitment: ShareCommitment<E>,
+    ) -> Result<bool> {
+        // The old key must hold the old share: e(G, Y) = e(A_i, ek)
+        if E::pairing(E::G1::generator(), *old_share)
+            != E::pairing(share_commitment.0, self.old_pubkey.encryption_key)
+        {
+            return Ok(false);
+        }
+
+        // Recover the prover's commitments as [z] base - [c] image. The image
+        // of the re-blinding relation [d] Y' - [d'] Y is zero.
+        let h = E::G2::generator();
+        let proof = &self.proof;
+        let expected_challenge = self.challenge(
+            old_share,
+            &[
+                h * proof.old_key_response
+                    - self.old_pubkey.encryption_key * proof.challenge,
+                h * proof.new_key_response
+                    - self.new_pubkey.encryption_key * proof.challenge,
+                self.blinded_key_share * proof.old_key_response
+                    - *old_share * proof.new_key_response,
+            ],
+        )?;
+        Ok(expected_challenge == proof.challenge)
+    }
+
+    fn challenge(
+        &self,
+        old_share: &E::G2Affine,
+        commitments: &[E::G2],
+    ) -> Result<E::ScalarField> {
+        let mut hash_input = KEY_ROTATION_PROOF_DST.to_vec();
+        hash_input.extend_from_slice(&self.share_index.to_be_bytes());
+        self.old_pubkey
+            .encryption_key
+            .serialize_compressed(&mut hash_input)?;
+        self.new_pubkey
+            .encryption_key
+            .serialize_compressed(&mut hash_input)?;
+        old_share.serialize_compressed(&mut hash_input)?;
+        self.blinded_key_share
+            .serialize_compressed(&mut hash_input)?;
+        for commitment in commitments {
+            commitment.serialize_compressed(&mut hash_input)?;
+        }
+        Ok(E::ScalarField::from_be_bytes_mod_order(&Sha256::digest(
+            &hash_input,
+        )))
+    }
+}
+
+/// A helper's contribution to the recovery of a lost share: the helper's share,
+/// masked by the recovery updates and blinded to the recovering validator.
+/// Combining `threshold` of them at the lost share's domain point yields the
+/// lost share, blinded to the recovering validator.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryShare<E: Pairing> {
+    pub helper_index: u32,
+
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub blinded_key_share: E::G2Affine,
+
+    #[serde(bound(
+        serialize = "ferveo_common::PublicKey<E>: Serialize",
+        deserialize = "ferveo_common::PublicKey<E>: DeserializeOwned"
+    ))]
+    pub recovering_pubkey: PublicKey<E>,
+}
+
+/// Evaluate a Feldman commitment to a polynomial at `x`, i.e. sum{[x^i]C_i}
+fn evaluate_commitment<E: Pairing>(
+    coeffs: &[E::G1Affine],
+    x: &DomainPoint<E>,
+) -> E::G1 {
+    coeffs
+        .iter()
+        .rev()
+        .fold(E::G1::zero(), |acc, coeff| acc * x + coeff)
+}
+
 /// Prepare share updates with a given root (0 for refresh, some x coord for recovery)
 /// This is a helper function for `ShareUpdate::create_share_updates_for_recovery` and `ShareUpdate::create_share_updates_for_refresh`
 /// It generates a new random polynomial with a defined root and evaluates it at each of the participants' indices.
@@ -439,7 +768,7 @@ fn prepare_share_updates_with_root<E: Pairing>(
     let coeff_commitments = fast_multiexp(&update_poly.coeffs, g);
 
     // Now, we need to evaluate the polynomial at each of participants' indices
-    let share_updates: HashMap<u32, ShareUpdate<E>> = domain_points_and_keys
+    let share_updates: BTreeMap<u32, ShareUpdate<E>> = domain_points_and_keys
         .iter()
         .map(|(share_index, tuple)| {
             let (x_i, pubkey_i) = tuple;
@@ -451,7 +780,7 @@ fn prepare_share_updates_with_root<E: Pairing>(
             let share_update = ShareUpdate { update, commitment };
             (*share_index, share_update)
         })
-        .collect::<HashMap<u32, ShareUpdate<E>>>();
+        .collect::<BTreeMap<u32, ShareUpdate<E>>>();
 
     UpdateTranscript {
         coeffs: coeff_commitments,
@@ -489,74 +818,77 @@ mod tests_refresh {
 
     use ark_ec::CurveGroup;
     use ark_poly::EvaluationDomain;
-    use ark_std::{test_rng, UniformRand, Zero};
-    use ferveo_common::Keypair;
+    use ark_std::{test_rng, One, UniformRand, Zero};
+    use ferveo_common::{FromBytes, Keypair, ToBytes};
     use ferveo_tdec::{
         lagrange_basis_at, test_common::setup_simple, DomainPoint,
+        PrivateDecryptionContextSimple,
     };
     use itertools::{zip_eq, Itertools};
+    use rand_core::RngCore;
+    use serde::{de::DeserializeOwned, Serialize};
     use test_case::test_case;
 
     use crate::{
-        test_common::*, HandoverTranscript, UpdatableBlindedKeyShare,
-        UpdateTranscript,
+        test_common::*, HandoverTranscript, KeyRotation, RecoveryShare,
+        UpdatableBlindedKeyShare, UpdateTranscript,
     };
 
-    type ScalarField =
-        <ark_bls12_381::Bls12_381 as ark_ec::pairing::Pairing>::ScalarField;
-    type G2 = <ark_bls12_381::Bls12_381 as ark_ec::pairing::Pairing>::G2;
-
-    // TODO: Part of recovery tests - #193
-    // /// Using tdec test utilities here instead of PVSS to test the internals of the shared key recovery
-    // fn create_updated_private_key_shares<R: RngCore>(
-    //     rng: &mut R,
-    //     threshold: u32,
-    //     x_r: &Fr,
-    //     remaining_participants: &[PrivateDecryptionContextSimple<E>],
-    // ) -> HashMap<u32, UpdatedPrivateKeyShare<E>> {
-    //     // Each participant prepares an update for each other participant
-    //     let domain_points_and_keys = remaining_participants
-    //         .iter()
-    //         .map(|c| {
-    //             let ctxt = &c.public_decryption_contexts[c.index];
-    //             (c.index as u32, (ctxt.domain, ctxt.validator_public_key))
-    //         })
-    //         .collect::<HashMap<_, _>>();
-    //     let share_updates = remaining_participants
-    //         .iter()
-    //         .map(|p| {
-    //             let share_updates = UpdateTranscript::create_recovery_updates(
-    //                 &domain_points_and_keys,
-    //                 x_r,
-    //                 threshold,
-    //                 rng,
-    //             );
-    //             (p.index as u32, share_updates.updates)
-    //         })
-    //         .collect::<HashMap<u32, _>>();
-
-    //     // Participants share updates and update their shares
-    //     let updated_private_key_shares = remaining_participants
-    //         .iter()
-    //         .map(|p| {
-    //             // Current participant receives updates from other participants
-    //             let updates_for_participant: Vec<_> = share_updates
-    //                 .values()
-    //                 .map(|updates| {
-    //                     updates.get(&(p.index as u32)).cloned().unwrap()
-    //                 })
-    //                 .collect();
-
-    //             // And updates their share
-    //             let updated_share =
-    //                 PrivateKeyShare(p.private_key_share.clone())
-    //                     .create_updated_key_share(&updates_for_participant);
-    //             (p.index as u32, updated_share)
-    //         })
-    //         .collect::<HashMap<u32, _>>();
-
-    //     updated_private_key_shares
-    // }
+    type G2 = <E as ark_ec::pairing::Pairing>::G2;
+
+    /// Each helper deals recovery updates for `x_r` to all helpers, who
+    /// apply them to their shares. Using tdec test utilities here instead of
+    /// PVSS to test the internals of the shared key recovery.
+    fn create_updated_private_key_shares(
+        rng: &mut impl RngCore,
+        threshold: u32,
+        x_r: &DomainPoint<E>,
+        helpers: &[PrivateDecryptionContextSimple<E>],
+    ) -> HashMap<u32, ferveo_tdec::PrivateKeyShare<E>> {
+        let domain_points_and_keys = helpers
+            .iter()
+            .map(|c| {
+                let ctxt = &c.public_decryption_contexts[c.index];
+                (c.index as u32, (ctxt.domain, ctxt.validator_public_key))
+            })
+            .collect::<HashMap<_, _>>();
+        let update_transcripts = helpers
+            .iter()
+            .map(|p| {
+                let update_transcript =
+                    UpdateTranscript::<E>::create_recovery_updates(
+                        &domain_points_and_keys,
+                        x_r,
+                        threshold,
+                        rng,
+                    );
+                (p.index as u32, update_transcript)
+            })
+            .collect::<HashMap<u32, _>>();
+
+        // Helpers apply the updates from each other and unblind their shares
+        helpers
+            .iter()
+            .map(|p| {
+                let ctxt = &p.public_decryption_contexts[p.index];
+                let validator_keypair = Keypair {
+                    decryption_key: p.setup_params.b,
+                };
+                let updated_share =
+                    UpdatableBlindedKeyShare(ctxt.blinded_key_share)
+                        .apply_share_updates(
+                            &update_transcripts,
+                            p.index as u32,
+                            &ctxt.domain,
+                        )
+                        .unwrap()
+                        .0
+                        .unblind(&validator_keypair)
+                        .unwrap();
+                (p.index as u32, updated_share)
+            })
+            .collect()
+    }
 
     /// `x_r` is the point at which the share is to be recovered
     fn combine_private_shares_at(
@@ -581,14 +913,9 @@ mod tests_refresh {
 
     /// Ñ parties (where t <= Ñ <= N) jointly execute a "share recovery" algorithm, and the output is 1 new share.
     /// The new share is intended to restore a previously existing share, e.g., due to loss or corruption.
-    // FIXME: This test is currently broken, and adjusted to allow compilation
-    #[ignore = "Re-introduce recovery tests - #193"]
-    #[test_case(4, 4; "number of shares (validators) is a power of 2")]
-    #[test_case(7, 7; "number of shares (validators) is not a power of 2")]
-    fn tdec_simple_variant_share_recovery_at_selected_point(
-        shares_num: u32,
-        _validators_num: u32,
-    ) {
+    #[test_case(4; "number of shares (validators) is a power of 2")]
+    #[test_case(7; "number of shares (validators) is not a power of 2")]
+    fn tdec_simple_variant_share_recovery_at_selected_point(shares_num: u32) {
         let rng = &mut test_rng();
         let security_threshold = shares_num * 2 / 3;
 
@@ -602,7 +929,7 @@ mod tests_refresh {
 
         // First, save the soon-to-be-removed participant
         let selected_participant = contexts.pop().unwrap();
-        let _x_r = selected_participant
+        let x_r = selected_participant
             .public_decryption_contexts
             .last()
             .unwrap()
@@ -616,20 +943,20 @@ mod tests_refresh {
         }
 
         // Each participant prepares an update for each other participant, and uses it to create a new share fragment
-        // let updated_private_key_shares = create_updated_private_key_shares(
-        //     rng,
-        //     security_threshold,
-        //     &x_r,
-        //     &remaining_participants,
-        // );
+        let updated_private_key_shares = create_updated_private_key_shares(
+            rng,
+            security_threshold,
+            &x_r,
+            &remaining_participants,
+        );
         // We only need `security_threshold` updates to recover the original share
-        // let updated_private_key_shares = updated_private_key_shares
-        //     .into_iter()
-        //     .take(security_threshold as usize)
-        //     .collect::<HashMap<_, _>>();
+        let updated_private_key_shares = updated_private_key_shares
+            .into_iter()
+            .take(security_threshold as usize)
+            .collect::<HashMap<_, _>>();
 
         // Now, we have to combine new share fragments into a new share
-        let _domain_points = remaining_participants
+        let domain_points = remaining_participants
             .into_iter()
             .map(|ctxt| {
                 (
@@ -638,36 +965,27 @@ mod tests_refresh {
                 )
             })
             .collect::<HashMap<u32, _>>();
-        // let new_private_key_share =
-        //     PrivateKeyShare::recover_share_from_updated_private_shares(
-        //         &x_r,
-        //         &domain_points,
-        //         &updated_private_key_shares,
-        //     )
-        //     .unwrap();
+        let new_private_key_share = combine_private_shares_at(
+            &x_r,
+            &domain_points,
+            &updated_private_key_shares,
+        );
 
         // The new share should be the same as the original
-        // assert_eq!(new_private_key_share, original_private_key_share);
+        assert_eq!(new_private_key_share, original_private_key_share);
 
         // But if we don't have enough private share updates, the resulting private share will be incorrect
-        // let not_enough_shares = updated_private_key_shares
-        //     .into_iter()
-        //     .take(security_threshold as usize - 1)
-        //     .collect::<HashMap<_, _>>();
-        // let incorrect_private_key_share =
-        //     PrivateKeyShare::recover_share_from_updated_private_shares(
-        //         &x_r,
-        //         &domain_points,
-        //         &not_enough_shares,
-        //     )
-        //     .unwrap();
-        assert_ne!(original_private_key_share, original_private_key_share);
+        let not_enough_shares = updated_private_key_shares
+            .into_iter()
+            .take(security_threshold as usize - 1)
+            .collect::<HashMap<_, _>>();
+        let incorrect_private_key_share =
+            combine_private_shares_at(&x_r, &domain_points, &not_enough_shares);
+        assert_ne!(incorrect_private_key_share, original_private_key_share);
     }
 
     /// Ñ parties (where t <= Ñ <= N) jointly execute a "share recovery" algorithm, and the output is 1 new share.
     /// The new share is independent of the previously existing shares. We can use this to on-board a new participant into an existing cohort.
-    // FIXME: This test is currently broken, and adjusted to allow compilation
-    #[ignore = "Re-introduce recovery tests - #193"]
     #[test_case(4; "number of shares (validators) is a power of 2")]
     #[test_case(7; "number of shares (validators) is not a power of 2")]
     fn tdec_simple_variant_share_recovery_at_random_point(shares_num: u32) {
@@ -695,17 +1013,17 @@ mod tests_refresh {
         let x_r = ScalarField::rand(rng);
 
         // Each remaining participant prepares an update for every other participant, and uses it to create a new share fragment
-        // let share_recovery_updates = create_updated_private_key_shares(
-        //     rng,
-        //     security_threshold,
-        //     &x_r,
-        //     &remaining_participants,
-        // );
+        let share_recovery_updates = create_updated_private_key_shares(
+            rng,
+            security_threshold,
+            &x_r,
+            &remaining_participants,
+        );
         // We only need `threshold` updates to recover the original share
-        // let share_recovery_updates = share_recovery_updates
-        //     .into_iter()
-        //     .take(security_threshold as usize)
-        //     .collect::<HashMap<_, _>>();
+        let share_recovery_updates = share_recovery_updates
+            .into_iter()
+            .take(security_threshold as usize)
+            .collect::<HashMap<_, _>>();
         let domain_points = &mut remaining_participants
             .into_iter()
             .map(|ctxt| {
@@ -717,43 +1035,31 @@ mod tests_refresh {
             .collect::<HashMap<_, _>>();
 
         // Now, we have to combine new share fragments into a new share
-        // let recovered_private_key_share =
-        //     PrivateKeyShare::recover_share_from_updated_private_shares(
-        //         &x_r,
-        //         domain_points,
-        //         &share_recovery_updates,
-        //     )
-        //     .unwrap();
+        let recovered_private_key_share = combine_private_shares_at(
+            &x_r,
+            domain_points,
+            &share_recovery_updates,
+        );
 
         // Finally, let's recreate the shared private key from some original shares and the recovered one
-        let _private_shares = contexts
+        let mut private_shares = contexts
             .into_iter()
+            .take(security_threshold as usize - 1)
             .map(|ctxt| (ctxt.index as u32, ctxt.private_key_share))
             .collect::<HashMap<u32, _>>();
 
         // Need to update these to account for recovered private key share
         domain_points.insert(removed_participant.index as u32, x_r);
-        // private_shares.insert(
-        //     removed_participant.index as u32,
-        //     recovered_private_key_share.0.clone(),
-        // );
-
-        // This is a workaround for a type mismatch - We need to convert the private shares to updated private shares
-        // This is just to test that we are able to recover the shared private key from the updated private shares
-        // let updated_private_key_shares = private_shares
-        //     .into_iter()
-        //     .map(|(share_index, share)| {
-        //         (share_index, UpdatedPrivateKeyShare(share))
-        //     })
-        // .collect::<HashMap<u32, _>>();
-        // let new_shared_private_key =
-        //     PrivateKeyShare::recover_share_from_updated_private_shares(
-        //         &ScalarField::zero(),
-        //         domain_points,
-        //         &updated_private_key_shares,
-        //     )
-        //     .unwrap();
-        assert_ne!(shared_private_key, shared_private_key);
+        private_shares.insert(
+            removed_participant.index as u32,
+            recovered_private_key_share,
+        );
+        let new_shared_private_key = combine_private_shares_at(
+            &ScalarField::zero(),
+            domain_points,
+            &private_shares,
+        );
+        assert_eq!(shared_private_key, new_shared_private_key);
     }
 
     /// Ñ parties (where t <= Ñ <= N) jointly execute a "share refresh" algorithm.
@@ -835,7 +1141,9 @@ mod tests_refresh {
                         .apply_share_updates(
                             &update_transcripts_by_producer,
                             participant_index,
-                        );
+                            &p.public_decryption_contexts[p.index].domain,
+                        )
+                        .unwrap();
 
                 let validator_keypair = ferveo_common::Keypair {
                     decryption_key: p.setup_params.b,
@@ -864,6 +1172,170 @@ mod tests_refresh {
         assert_eq!(shared_private_key, new_shared_private_key);
     }
 
+    fn assert_round_trip<T>(item: &T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let bytes = item.to_bytes().unwrap();
+        let decoded = T::from_bytes(&bytes).unwrap();
+        assert_eq!(&decoded, item);
+        // Encodings are deterministic
+        assert_eq!(decoded.to_bytes().unwrap(), bytes);
+    }
+
+    /// Refresh, recovery and handover artifacts can be sent over the wire
+    #[test]
+    fn test_refresh_artifacts_serialization() {
+        let rng = &mut test_rng();
+        let (shares_num, security_threshold) = (4, 3);
+        let (_, _, contexts) =
+            setup_simple::<E>(shares_num, security_threshold, rng);
+        let domain_points_and_keys = contexts
+            .iter()
+            .map(|ctxt| {
+                let public_context =
+                    &ctxt.public_decryption_contexts[ctxt.index];
+                (
+                    ctxt.index as u32,
+                    (
+                        public_context.domain,
+                        public_context.validator_public_key,
+                    ),
+                )
+            })
+            .collect::<HashMap<u32, _>>();
+
+        let refresh_transcript = UpdateTranscript::<E>::create_refresh_updates(
+            &domain_points_and_keys,
+            security_threshold as u32,
+            rng,
+        );
+        assert_round_trip(&refresh_transcript);
+        let x_r = ScalarField::rand(rng);
+        assert_round_trip(&UpdateTranscript::<E>::create_recovery_updates(
+            &domain_points_and_keys,
+            &x_r,
+            security_threshold as u32,
+            rng,
+        ));
+
+        let participant = &contexts[0];
+        let public_context =
+            &participant.public_decryption_contexts[participant.index];
+        let keypair = Keypair::<E> {
+            decryption_key: participant.setup_params.b,
+        };
+        let blinded_key_share =
+            UpdatableBlindedKeyShare::new(public_context.blinded_key_share);
+        assert_round_trip(&blinded_key_share);
+        assert_round_trip(
+            &blinded_key_share
+                .apply_share_updates(
+                    &HashMap::from([(0, refresh_transcript)]),
+                    0,
+                    &public_context.domain,
+                )
+                .unwrap(),
+        );
+
+        let incoming_keypair = Keypair::<E>::new(rng);
+        let handover_transcript = HandoverTranscript::<E>::new(
+            0,
+            &public_context.blinded_key_share,
+            keypair.public_key(),
+            &incoming_keypair,
+            rng,
+        );
+        assert_round_trip(&handover_transcript);
+        let handover_result = handover_transcript
+            .finalize_with_proof(&keypair, public_context.share_commitment, rng)
+            .unwrap();
+        assert_round_trip(&handover_result);
+        assert_round_trip(&RecoveryShare::<E> {
+            helper_index: 0,
+            blinded_key_share: handover_result.blinded_key_share,
+            recovering_pubkey: incoming_keypair.public_key(),
+        });
+        assert_round_trip(
+            &KeyRotation::<E>::new(
+                0,
+                &public_context.blinded_key_share,
+                &keypair,
+                &incoming_keypair,
+                rng,
+            )
+            .unwrap(),
+        );
+    }
+
+    /// A validator rotates its key, keeping its share and share index
+    #[test]
+    fn tdec_simple_variant_key_rotation() {
+        let rng = &mut test_rng();
+        let (shares_num, security_threshold) = (4, 3);
+        let (_, _, private_contexts) =
+            setup_simple::<E>(shares_num, security_threshold, rng);
+        let participant = &private_contexts[0];
+        let public_context =
+            &participant.public_decryption_contexts[participant.index];
+        let old_keypair = Keypair::<E> {
+            decryption_key: participant.setup_params.b,
+        };
+        let new_keypair = Keypair::<E>::new(rng);
+
+        let rotation = KeyRotation::<E>::new(
+            participant.index as u32,
+            &public_context.blinded_key_share,
+            &old_keypair,
+            &new_keypair,
+            rng,
+        )
+        .unwrap();
+        let old_share = public_context.blinded_key_share.blinded_key_share;
+        assert!(rotation
+            .verify(&old_share, public_context.share_commitment)
+            .unwrap());
+
+        // The rotated share holds the same private key share
+        let rotated_share = ferveo_tdec::BlindedKeyShare::<E> {
+            validator_public_key: new_keypair.public_key().encryption_key,
+            blinded_key_share: rotation.blinded_key_share,
+        };
+        assert_eq!(
+            rotated_share.unblind(&new_keypair).unwrap(),
+            public_context
+                .blinded_key_share
+                .unblind(&old_keypair)
+                .unwrap()
+        );
+
+        // Only the holder of the old key can rotate it
+        assert!(KeyRotation::<E>::new(
+            participant.index as u32,
+            &public_context.blinded_key_share,
+            &new_keypair,
+            &old_keypair,
+            rng,
+        )
+        .is_err());
+
+        // A rotation doesn't verify for another share, nor once tampered with
+        let other_context = &participant.public_decryption_contexts[1];
+        assert!(!rotation
+            .verify(
+                &other_context.blinded_key_share.blinded_key_share,
+                other_context.share_commitment
+            )
+            .unwrap());
+        let mut forged_rotation = rotation.clone();
+        forged_rotation.blinded_key_share = (rotation.blinded_key_share
+            * ScalarField::from(2u64))
+        .into_affine();
+        assert!(!forged_rotation
+            .verify(&old_share, public_context.share_commitment)
+            .unwrap());
+    }
+
     // TODO: Simple handover transcript unit test
 
     /// 2 parties follow a handover protocol. The output is a new blind share
@@ -942,6 +1414,39 @@ mod tests_refresh {
             .unwrap();
         assert_eq!(new_private_share, old_private_share);
 
+        // The departing participant can publish the result with a proof of
+        // re-blinding, that anyone can verify against the share commitment
+        let handover_result = handover_transcript
+            .finalize_with_proof(
+                &departing_validator_keypair,
+                departing_public_context.share_commitment,
+                rng,
+            )
+            .unwrap();
+        assert_eq!(
+            handover_result.blinded_key_share,
+            new_blinded_share.blinded_key_share
+        );
+        assert!(handover_result
+            .verify(departing_public_context.share_commitment)
+            .unwrap());
+        let other_commitment =
+            private_contexts[0].public_decryption_contexts[0].share_commitment;
+        assert!(!handover_result.verify(other_commitment).unwrap());
+        let mut forged_result = handover_result.clone();
+        forged_result.proof.response += ScalarField::one();
+        assert!(!forged_result
+            .verify(departing_public_context.share_commitment)
+            .unwrap());
+        // Only the departing participant can finalize the handover
+        assert!(handover_transcript
+            .finalize_with_proof(
+                &incoming_validator_keypair,
+                departing_public_context.share_commitment,
+                rng,
+            )
+            .is_err());
+
         // We check that the private share from the other participants plus the
         // new_private_share obtained after handover can be combined to
         // reconstruct the shared private key.
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_poly::{
+    univariate::DensePolynomial, DenseUVPolynomial, EvaluationDomain,
+    Polynomial,
+};
+use ferveo_common::{serialization, Keypair};
+use ferveo_tdec::{prepare_combine_simple, HashToG2, ShareCommitment};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use subproductdomain::fast_multiexp;
+
+use crate::{
+    batch_to_projective_g1, batch_to_projective_g2, do_verify_full,
+    evaluation_domain, Aggregated, Error, HandoverTranscript,
+    PubliclyVerifiableDkg, PubliclyVerifiableSS, Result,
+};
+
+/// A PVSS transcript of an old validator's key share `s_i`, dealt to the
+/// validators of a new committee.
+///
+/// The old validator only holds its share blinded, so each new validator
+/// starts with a handover transcript of the old share, as in a handover. The
+/// old validator finalizes it into `[s_i] ek_j`, and adds a share of a random
+/// polynomial with no constant term. The polynomial commitments are those of
+/// a polynomial with constant term `s_i`, i.e. the first one is the share
+/// commitment `A_i` of the old validator.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResharingTranscript<E: Pairing> {
+    /// Share index of the dealer in the old committee
+    pub dealer_index: u32,
+
+    /// Commitments to the resharing polynomial, starting with `A_i`
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub coeffs: Vec<E::G1Affine>,
+
+    /// The blinded shares dealt to each validator of the new committee
+    #[serde_as(as = "serialization::SerdeAs")]
+    pub shares: Vec<E::G2Affine>,
+}
+
+impl<E: HashToG2> ResharingTranscript<E> {
+    /// Deal the key share of `dealer_keypair` to the new committee of
+    /// `new_dkg`, given a handover transcript from each of its validators
+    pub fn new(
+        dealer_index: u32,
+        dealer_keypair: &Keypair<E>,
+        share_commitment: ShareCommitment<E>,
+        handover_transcripts: &HashMap<u32, HandoverTranscript<E>>,
+        new_dkg: &PubliclyVerifiableDkg<E>,
+        rng: &mut impl RngCore,
+    ) -> Result<Self> {
+        let threshold = new_dkg.dkg_params.security_threshold();
+        let mut poly = DensePolynomial::<E::ScalarField>::rand(
+            (threshold - 1) as usize,
+            rng,
+        );
+        poly.coeffs[0] = E::ScalarField::zero();
+
+        let mut coeffs =
+            fast_multiexp(&poly.coeffs, E::G1Affine::generator().into_group());
+        coeffs[0] = share_commitment.0;
+
+        let shares = new_dkg
+            .validators
+            .values()
+            .map(|validator| {
+                let handover_transcript = handover_transcripts
+                    .get(&validator.share_index)
+                    .ok_or(Error::InvalidShareIndex(validator.share_index))?;
+                if handover_transcript.share_index != dealer_index
+                    || handover_transcript.outgoing_pubkey
+                        != dealer_keypair.public_key()
+                    || handover_transcript.incoming_pubkey
+                        != validator.public_key
+                {
+                    return Err(Error::ValidatorPublicKeyMismatch);
+                }
+                // [s_i] ek_j
+                let reshared_share = handover_transcript
+                    .finalize(dealer_keypair, share_commitment)?
+                    .blinded_key_share;
+                let domain_point =
+                    new_dkg.get_domain_point(validator.share_index)?;
+                let mask = validator.public_key.encryption_key
+                    * poly.evaluate(&domain_point);
+                Ok((reshared_share + mask).into_affine())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            dealer_index,
+            coeffs,
+            shares,
+        })
+    }
+
+    /// Verify the transcript against the share commitment of the dealer
+    /// in the old committee and the validators of the new committee
+    pub fn verify(
+        &self,
+        share_commitment: &ShareCommitment<E>,
+        new_dkg: &PubliclyVerifiableDkg<E>,
+    ) -> Result<bool> {
+        if self.coeffs.len() != new_dkg.dkg_params.security_threshold() as usize
+            || self.shares.len() != new_dkg.validators.len()
+            || self.coeffs.first() != Some(&share_commitment.0)
+        {
+            return Ok(false);
+        }
+        let validators =
+            new_dkg.validators.values().cloned().collect::<Vec<_>>();
+        do_verify_full(&self.coeffs, &self.shares, &validators, &new_dkg.domain)
+    }
+}
+
+/// Combine the resharing transcripts of at least `threshold` validators of the
+/// old committee into an aggregate for the new committee. The aggregate shares
+/// the same secret, so it keeps the same public key.
+pub fn aggregate_resharing_transcripts<E: HashToG2>(
+    old_aggregate: &PubliclyVerifiableSS<E, Aggregated>,
+    new_dkg: &PubliclyVerifiableDkg<E>,
+    transcripts: &[ResharingTranscript<E>],
+) -> Result<PubliclyVerifiableSS<E, Aggregated>> {
+    let transcripts = transcripts
+        .iter()
+        .map(|transcript| (transcript.dealer_index, transcript))
+        .collect::<BTreeMap<_, _>>();
+    let threshold = old_aggregate.coeffs.len();
+    if transcripts.len() < threshold {
+        return Err(Error::InsufficientResharingDealers(
+            threshold as u32,
+            transcripts.len() as u32,
+        ));
+    }
+
+    let old_domain =
+        evaluation_domain::<E::ScalarField>(old_aggregate.shares.len())?;
+    let mut domain_points = Vec::with_capacity(transcripts.len());
+    for (&dealer_index, transcript) in &transcripts {
+        let share_commitment =
+            old_aggregate.get_share_commitment(dealer_index)?;
+        if !transcript.verify(&share_commitment, new_dkg)? {
+            return Err(Error::InvalidResharingTranscript(dealer_index));
+        }
+        domain_points.push(old_domain.element(dealer_index as usize));
+    }
+
+    // The new polynomial is the Lagrange combination of the dealt ones, so its
+    // constant term is the shared secret
+    let lagrange_coeffs = prepare_combine_simple::<E>(&domain_points);
+    let mut coeffs =
+        vec![E::G1::zero(); new_dkg.dkg_params.security_threshold() as usize];
+    let mut shares = vec![E::G2::zero(); new_dkg.validators.len()];
+    for (transcript, lambda) in transcripts.values().zip(lagrange_coeffs) {
+        let dealt_coeffs = batch_to_projective_g1::<E>(&transcript.coeffs);
+        for (coeff, dealt) in coeffs.iter_mut().zip(dealt_coeffs) {
+            *coeff += dealt * lambda;
+        }
+        let dealt_shares = batch_to_projective_g2::<E>(&transcript.shares);
+        for (share, dealt) in shares.iter_mut().zip(dealt_shares) {
+            *share += dealt * lambda;
+        }
+    }
+
+    let reshared = PubliclyVerifiableSS::new_aggregate(
+        E::G1::normalize_batch(&coeffs),
+        E::G2::normalize_batch(&shares),
+        old_aggregate.sigma,
+    );
+    if reshared.coeffs.first() != old_aggregate.coeffs.first() {
+        return Err(Error::InvalidTranscriptAggregate);
+    }
+    Ok(reshared)
+}
@@ -1,10 +1,9 @@
 /// Factory functions and variables for testing
 use std::str::FromStr;
 
-use ark_bls12_381::Bls12_381;
-pub use ark_bls12_381::Bls12_381 as E;
 use ark_ec::pairing::Pairing;
 use ferveo_common::Keypair;
+pub use ferveo_tdec::api::E;
 use rand::{seq::SliceRandom, Rng};
 
 use crate::{
@@ -117,7 +116,7 @@ pub fn setup_dealt_dkg_with_n_validators(
 
 pub fn make_messages(
     rng: &mut (impl Rng + Sized),
-    dkg: &PubliclyVerifiableDkg<Bls12_381>,
+    dkg: &PubliclyVerifiableDkg<E>,
 ) -> Vec<(Validator<E>, PubliclyVerifiableSS<E>)> {
     let mut messages = vec![];
     for i in 0..dkg.dkg_params.shares_num() {